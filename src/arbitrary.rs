@@ -0,0 +1,95 @@
+use crate::{Buffer, NumberBuf};
+use alloc::vec::Vec;
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+/// Pushes `len` arbitrary decimal digits.
+fn push_digits(u: &mut Unstructured, bytes: &mut Vec<u8>, len: usize) -> Result<()> {
+	for _ in 0..len {
+		bytes.push(b'0' + u.int_in_range(0..=9)?)
+	}
+
+	Ok(())
+}
+
+impl<'a, B: Buffer> Arbitrary<'a> for NumberBuf<B> {
+	/// Generates a valid number, made of an optional sign, an integer part,
+	/// an optional fraction and an optional exponent.
+	///
+	/// The number of digits of each part is bounded by the size of the
+	/// remaining input.
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		let mut bytes = Vec::new();
+
+		if u.arbitrary()? {
+			bytes.push(b'-')
+		}
+
+		let integer_len = u.arbitrary_len::<u8>()?;
+		if integer_len == 0 {
+			bytes.push(b'0')
+		} else {
+			bytes.push(b'0' + u.int_in_range(1..=9)?);
+			push_digits(u, &mut bytes, integer_len - 1)?
+		}
+
+		if u.arbitrary()? {
+			bytes.push(b'.');
+			let len = u.arbitrary_len::<u8>()?.max(1);
+			push_digits(u, &mut bytes, len)?
+		}
+
+		if u.arbitrary()? {
+			bytes.push(*u.choose(b"eE")?);
+			match u.int_in_range(0..=2)? {
+				0 => (),
+				1 => bytes.push(b'+'),
+				_ => bytes.push(b'-'),
+			}
+
+			let len = u.arbitrary_len::<u8>()?.max(1);
+			push_digits(u, &mut bytes, len)?
+		}
+
+		Ok(unsafe { NumberBuf::new_unchecked(B::from_vec(bytes)) })
+	}
+
+	#[inline(always)]
+	fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+		(1, None)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Number, NumberBuf};
+	use arbitrary::{Arbitrary, Unstructured};
+
+	#[test]
+	fn valid_numbers() {
+		let mut data = Vec::new();
+		let mut seed = 0x2545f491u32;
+		for _ in 0..4096 {
+			seed ^= seed << 13;
+			seed ^= seed >> 17;
+			seed ^= seed << 5;
+			data.push(seed as u8)
+		}
+
+		let mut u = Unstructured::new(&data);
+		let mut count = 0;
+		while !u.is_empty() {
+			let n = NumberBuf::<Vec<u8>>::arbitrary(&mut u).unwrap();
+			assert!(Number::new(n.as_bytes()).is_ok(), "{n}");
+			count += 1
+		}
+
+		assert!(count > 1)
+	}
+
+	#[test]
+	fn empty_input() {
+		let mut u = Unstructured::new(&[]);
+		let n = NumberBuf::<Vec<u8>>::arbitrary(&mut u).unwrap();
+		assert_eq!(n.as_str(), "0")
+	}
+}
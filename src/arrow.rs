@@ -0,0 +1,259 @@
+//! Arrow decimal columns.
+//!
+//! Converts slices of numbers into [`Decimal128Array`] and
+//! [`Decimal256Array`] columns (as written to Parquet files), and back.
+use crate::{decimal::Decimal, Buffer, Number, NumberBuf};
+use alloc::{string::ToString, vec::Vec};
+use arrow::{
+	array::{Decimal128Array, Decimal256Array, PrimitiveArray},
+	datatypes::{
+		validate_decimal_precision_and_scale, Decimal128Type, Decimal256Type, DecimalType,
+	},
+};
+use core::{fmt, str::FromStr};
+
+/// Arrow decimal conversion error.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConversionError {
+	/// The precision or scale is not valid for the decimal type.
+	InvalidPrecision { precision: u8, scale: i8 },
+
+	/// The number at the given index has more fractional digits than the
+	/// scale.
+	Scale { index: usize },
+
+	/// The number at the given index has more digits than the precision.
+	Overflow { index: usize },
+}
+
+impl fmt::Display for ConversionError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::InvalidPrecision { precision, scale } => {
+				write!(f, "invalid decimal precision {precision} and scale {scale}")
+			}
+			Self::Scale { index } => write!(f, "too many fractional digits at index {index}"),
+			Self::Overflow { index } => write!(f, "number too large at index {index}"),
+		}
+	}
+}
+
+impl core::error::Error for ConversionError {}
+
+/// Converts the given numbers into a decimal array with the given precision
+/// and scale.
+///
+/// Fails if a number cannot be represented exactly.
+fn to_array<T: DecimalType, B: Buffer>(
+	numbers: &[NumberBuf<B>],
+	precision: u8,
+	scale: i8,
+) -> Result<PrimitiveArray<T>, ConversionError>
+where
+	T::Native: FromStr,
+{
+	let invalid_precision = |_| ConversionError::InvalidPrecision { precision, scale };
+	validate_decimal_precision_and_scale::<T>(precision, scale).map_err(invalid_precision)?;
+
+	let values = numbers
+		.iter()
+		.enumerate()
+		.map(|(index, n)| {
+			let mut d = Decimal::new(n);
+			let exponent = d.exponent.saturating_add(scale as i64);
+
+			if d.is_zero() {
+				return Ok(T::Native::default());
+			}
+
+			if exponent < 0 {
+				return Err(ConversionError::Scale { index });
+			}
+
+			if d.point().saturating_add(scale as i64) > precision as i64 {
+				return Err(ConversionError::Overflow { index });
+			}
+
+			// The value has at most `precision` digits, so it fits.
+			d.exponent = exponent;
+			core::str::from_utf8(&d.to_fixed(0))
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.ok_or(ConversionError::Overflow { index })
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	PrimitiveArray::from_iter_values(values)
+		.with_precision_and_scale(precision, scale)
+		.map_err(invalid_precision)
+}
+
+/// Converts the given decimal array into numbers, written in plain
+/// decimal notation with as many fractional digits as the array scale.
+fn from_array<T: DecimalType, B: Buffer>(array: &PrimitiveArray<T>) -> Vec<Option<NumberBuf<B>>>
+where
+	T::Native: fmt::Display,
+{
+	let scale = array.scale();
+	let fraction_digits = scale.max(0) as usize;
+
+	array
+		.iter()
+		.map(|value| {
+			let value = value?.to_string();
+			let mut d = Decimal::new(unsafe { Number::new_unchecked(value.as_bytes()) });
+
+			if !d.is_zero() {
+				d.exponent -= scale as i64
+			}
+
+			Some(unsafe { NumberBuf::new_unchecked(B::from_vec(d.to_fixed(fraction_digits))) })
+		})
+		.collect()
+}
+
+/// Converts the given numbers into a [`Decimal128Array`] with the given
+/// precision and scale.
+///
+/// Fails if the precision or scale is invalid, or if any number cannot be
+/// represented exactly (without rounding) with this precision and scale.
+pub fn to_decimal128_array<B: Buffer>(
+	numbers: &[NumberBuf<B>],
+	precision: u8,
+	scale: i8,
+) -> Result<Decimal128Array, ConversionError> {
+	to_array::<Decimal128Type, B>(numbers, precision, scale)
+}
+
+/// Converts the given numbers into a [`Decimal256Array`] with the given
+/// precision and scale.
+///
+/// Fails if the precision or scale is invalid, or if any number cannot be
+/// represented exactly (without rounding) with this precision and scale.
+pub fn to_decimal256_array<B: Buffer>(
+	numbers: &[NumberBuf<B>],
+	precision: u8,
+	scale: i8,
+) -> Result<Decimal256Array, ConversionError> {
+	to_array::<Decimal256Type, B>(numbers, precision, scale)
+}
+
+/// Converts the given [`Decimal128Array`] into numbers, with `None` for
+/// null values.
+///
+/// Numbers are written in plain decimal notation, with as many fractional
+/// digits as the array scale.
+pub fn from_decimal128_array<B: Buffer>(array: &Decimal128Array) -> Vec<Option<NumberBuf<B>>> {
+	from_array(array)
+}
+
+/// Converts the given [`Decimal256Array`] into numbers, with `None` for
+/// null values.
+///
+/// Numbers are written in plain decimal notation, with as many fractional
+/// digits as the array scale.
+pub fn from_decimal256_array<B: Buffer>(array: &Decimal256Array) -> Vec<Option<NumberBuf<B>>> {
+	from_array(array)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use arrow::{array::Array, datatypes::i256};
+
+	fn numbers(list: &[&str]) -> Vec<NumberBuf> {
+		list.iter().map(|s| s.parse().unwrap()).collect()
+	}
+
+	fn strings(list: Vec<Option<NumberBuf>>) -> Vec<Option<String>> {
+		list.into_iter()
+			.map(|n| n.map(|n| n.as_str().to_owned()))
+			.collect()
+	}
+
+	#[test]
+	fn decimal128() {
+		let array =
+			to_decimal128_array(&numbers(&["0", "-12.5", "1.5e2", "42e-2", "999.99"]), 5, 2)
+				.unwrap();
+		assert_eq!(array.values(), &[0, -1250, 15000, 42, 99999]);
+		assert_eq!(
+			strings(from_decimal128_array(&array)),
+			[
+				Some("0.00".to_owned()),
+				Some("-12.50".to_owned()),
+				Some("150.00".to_owned()),
+				Some("0.42".to_owned()),
+				Some("999.99".to_owned())
+			]
+		)
+	}
+
+	#[test]
+	fn decimal256() {
+		let n = "123456789012345678901234567890123456789012345678901234567890.5";
+		let array = to_decimal256_array(&numbers(&[n]), 76, 1).unwrap();
+		assert_eq!(
+			array.value(0),
+			i256::from_string(&n.replace('.', "")).unwrap()
+		);
+		assert_eq!(strings(from_decimal256_array(&array)), [Some(n.to_owned())])
+	}
+
+	#[test]
+	fn negative_scale() {
+		let array = to_decimal128_array(&numbers(&["1200", "-3e5"]), 4, -2).unwrap();
+		assert_eq!(array.values(), &[12, -3000]);
+		assert_eq!(
+			strings(from_decimal128_array(&array)),
+			[Some("1200".to_owned()), Some("-300000".to_owned())]
+		);
+
+		assert_eq!(
+			to_decimal128_array(&numbers(&["1250"]), 4, -2),
+			Err(ConversionError::Scale { index: 0 })
+		)
+	}
+
+	#[test]
+	fn nulls() {
+		let array = Decimal128Array::from(vec![Some(5), None])
+			.with_precision_and_scale(3, 1)
+			.unwrap();
+		assert!(array.is_null(1));
+		assert_eq!(
+			strings(from_decimal128_array(&array)),
+			[Some("0.5".to_owned()), None]
+		)
+	}
+
+	#[test]
+	fn errors() {
+		assert_eq!(
+			to_decimal128_array(&numbers(&["1", "0.125"]), 5, 2),
+			Err(ConversionError::Scale { index: 1 })
+		);
+		assert_eq!(
+			to_decimal128_array(&numbers(&["1", "2", "1000"]), 5, 2),
+			Err(ConversionError::Overflow { index: 2 })
+		);
+		assert_eq!(
+			to_decimal128_array(&numbers(&["1e100000000000000000000"]), 38, 0),
+			Err(ConversionError::Overflow { index: 0 })
+		);
+		assert_eq!(
+			to_decimal128_array(&numbers(&["1"]), 39, 0),
+			Err(ConversionError::InvalidPrecision {
+				precision: 39,
+				scale: 0
+			})
+		);
+		assert_eq!(
+			to_decimal256_array(&numbers(&["1"]), 0, 0),
+			Err(ConversionError::InvalidPrecision {
+				precision: 0,
+				scale: 0
+			})
+		)
+	}
+}
@@ -0,0 +1,97 @@
+//! GraphQL `Number` scalar.
+use crate::{Buffer, InvalidNumber, Number, NumberBuf};
+use alloc::{string::ToString, vec::Vec};
+use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+/// Returns the number held by the given GraphQL value, either as a number or
+/// as a string.
+fn parse_value<B: Buffer>(value: &Value) -> Option<NumberBuf<B>> {
+	match value {
+		Value::Number(n) => {
+			let s = n.to_string();
+			Number::new(&s)
+				.ok()
+				.map(|n| unsafe { NumberBuf::new_unchecked(B::from_bytes(n.as_bytes())) })
+		}
+		Value::String(s) => Number::new(s)
+			.ok()
+			.map(|n| unsafe { NumberBuf::new_unchecked(B::from_bytes(n.as_bytes())) }),
+		_ => None,
+	}
+}
+
+/// Arbitrary-precision JSON number.
+///
+/// Accepts GraphQL numbers and strings holding a valid JSON number. Numbers
+/// are output as GraphQL numbers when this preserves their value, and as
+/// strings otherwise.
+#[Scalar(name = "Number")]
+impl<B: Buffer + Send + Sync> ScalarType for NumberBuf<B> {
+	fn parse(value: Value) -> InputValueResult<Self> {
+		match parse_value(&value) {
+			Some(n) => Ok(n),
+			None => match value {
+				Value::String(s) => Err(InputValueError::custom(InvalidNumber(s))),
+				value => Err(InputValueError::expected_type(value)),
+			},
+		}
+	}
+
+	fn is_valid(value: &Value) -> bool {
+		parse_value::<Vec<u8>>(value).is_some()
+	}
+
+	fn to_value(&self) -> Value {
+		match self.as_str().parse::<async_graphql::Number>() {
+			Ok(n) if Number::new(&n.to_string()).is_ok_and(|m| m.value_eq(self)) => {
+				Value::Number(n)
+			}
+			_ => Value::String(self.as_str().to_string()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::NumberBuf;
+	use async_graphql::{ScalarType, Value};
+
+	fn number(s: &str) -> NumberBuf {
+		s.parse().unwrap()
+	}
+
+	#[test]
+	fn parse() {
+		assert_eq!(
+			NumberBuf::<Vec<u8>>::parse(Value::Number(12.into())).unwrap(),
+			number("12")
+		);
+		assert_eq!(
+			NumberBuf::<Vec<u8>>::parse(Value::String("1.10e-3".to_owned())).unwrap(),
+			number("1.10e-3")
+		);
+		assert!(NumberBuf::<Vec<u8>>::parse(Value::String("1.".to_owned())).is_err());
+		assert!(NumberBuf::<Vec<u8>>::parse(Value::Boolean(true)).is_err());
+		assert!(NumberBuf::<Vec<u8>>::is_valid(&Value::String(
+			"-0.5".to_owned()
+		)));
+		assert!(!NumberBuf::<Vec<u8>>::is_valid(&Value::Null))
+	}
+
+	#[test]
+	fn to_value() {
+		assert_eq!(number("-12").to_value(), Value::Number((-12).into()));
+
+		// Depending on the `arbitrary_precision` feature of `serde_json`, this
+		// is either a number or a string, but always with the same value.
+		for s in [
+			"0.5",
+			"123456789012345678901234567890",
+			"0.1000000000000000000001",
+		] {
+			let n = number(s);
+			let m = NumberBuf::<Vec<u8>>::parse(n.to_value()).unwrap();
+			assert!(m.value_eq(&n), "{s}")
+		}
+	}
+}
@@ -0,0 +1,101 @@
+//! Bulk validation.
+use crate::Number;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Error returned by [`Number::validate_batch`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BatchError {
+	/// Index of the first invalid item.
+	pub index: usize,
+}
+
+impl fmt::Display for BatchError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "invalid JSON number at index {}", self.index)
+	}
+}
+
+impl core::error::Error for BatchError {}
+
+impl Number {
+	/// Checks that every item of `items` is a valid number.
+	///
+	/// Fails with the index of the first invalid item.
+	pub fn validate_batch<T: AsRef<[u8]>>(items: &[T]) -> Result<(), BatchError> {
+		match items.iter().position(|item| Number::new(item).is_err()) {
+			Some(index) => Err(BatchError { index }),
+			None => Ok(()),
+		}
+	}
+
+	/// Checks the validity of each item of `items`.
+	///
+	/// The `i`-th boolean of the result is `true` if and only if the `i`-th
+	/// item is a valid number.
+	pub fn validate_each<T: AsRef<[u8]>>(items: &[T]) -> Vec<bool> {
+		items.iter().map(|item| Number::new(item).is_ok()).collect()
+	}
+
+	/// Checks in parallel that every item of `items` is a valid number.
+	///
+	/// Fails with the index of the first invalid item.
+	#[cfg(feature = "rayon")]
+	pub fn par_validate_batch<T: AsRef<[u8]> + Sync>(items: &[T]) -> Result<(), BatchError> {
+		use rayon::prelude::*;
+		match items
+			.par_iter()
+			.position_first(|item| Number::new(item).is_err())
+		{
+			Some(index) => Err(BatchError { index }),
+			None => Ok(()),
+		}
+	}
+
+	/// Checks in parallel the validity of each item of `items`.
+	///
+	/// The `i`-th boolean of the result is `true` if and only if the `i`-th
+	/// item is a valid number.
+	#[cfg(feature = "rayon")]
+	pub fn par_validate_each<T: AsRef<[u8]> + Sync>(items: &[T]) -> Vec<bool> {
+		use rayon::prelude::*;
+		items
+			.par_iter()
+			.map(|item| Number::new(item).is_ok())
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::BatchError;
+	use crate::Number;
+
+	const ITEMS: [&str; 5] = ["1", "-2.5", "01", "3e", "4E+2"];
+
+	#[test]
+	fn validate_batch() {
+		assert_eq!(Number::validate_batch(&ITEMS[..2]), Ok(()));
+		assert_eq!(Number::validate_batch(&ITEMS), Err(BatchError { index: 2 }));
+		assert_eq!(Number::validate_batch::<&str>(&[]), Ok(()));
+		assert_eq!(
+			Number::validate_each(&ITEMS),
+			[true, true, false, false, true]
+		);
+	}
+
+	#[cfg(feature = "rayon")]
+	#[test]
+	fn par_validate_batch() {
+		let items: Vec<String> = (0..10_000).map(|i| i.to_string()).collect();
+		assert_eq!(Number::par_validate_batch(&items), Ok(()));
+		assert_eq!(
+			Number::par_validate_batch(&ITEMS),
+			Err(BatchError { index: 2 })
+		);
+		assert_eq!(
+			Number::par_validate_each(&ITEMS),
+			Number::validate_each(&ITEMS)
+		);
+	}
+}
@@ -0,0 +1,335 @@
+//! CBOR numbers, using the [RFC 8949](https://www.rfc-editor.org/rfc/rfc8949)
+//! bignum (tags 2 and 3) and decimal fraction (tag 4) extensions to preserve
+//! precision.
+use crate::{decimal::Decimal, Buffer, Number, NumberBuf};
+use alloc::{boxed::Box, vec, vec::Vec};
+use ciborium::value::{Integer, Value};
+use core::fmt;
+
+/// Positive bignum tag.
+const POSITIVE_BIGNUM: u64 = 2;
+
+/// Negative bignum tag.
+const NEGATIVE_BIGNUM: u64 = 3;
+
+/// Decimal fraction tag.
+const DECIMAL_FRACTION: u64 = 4;
+
+/// CBOR conversion error.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConversionError {
+	/// The CBOR value is not a number.
+	NotANumber,
+
+	/// The CBOR float is not finite, and hence has no JSON representation.
+	NonFinite,
+
+	/// The CBOR value is a malformed bignum or decimal fraction.
+	Invalid,
+}
+
+impl fmt::Display for ConversionError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NotANumber => f.write_str("CBOR value is not a number"),
+			Self::NonFinite => f.write_str("non-finite CBOR float"),
+			Self::Invalid => f.write_str("invalid CBOR bignum or decimal fraction"),
+		}
+	}
+}
+
+impl core::error::Error for ConversionError {}
+
+impl Number {
+	/// Converts this number into a CBOR value, without loss of precision.
+	///
+	/// Numbers without fraction or exponent are encoded as CBOR integers,
+	/// or bignums if they are too large. Other numbers are encoded as
+	/// decimal fractions.
+	pub fn to_cbor(&self) -> Value {
+		let d = Decimal::new(self);
+
+		if !self.has_decimal_point() && !self.has_exponent() {
+			let mut digits = d.digits;
+			digits.resize(digits.len() + d.exponent as usize, 0);
+			integer_value(d.negative, digits)
+		} else {
+			Value::Tag(
+				DECIMAL_FRACTION,
+				Box::new(Value::Array(vec![
+					Value::Integer(d.exponent.into()),
+					integer_value(d.negative, d.digits),
+				])),
+			)
+		}
+	}
+}
+
+impl From<&Number> for Value {
+	#[inline(always)]
+	fn from(n: &Number) -> Self {
+		n.to_cbor()
+	}
+}
+
+impl<B: Buffer> From<&NumberBuf<B>> for Value {
+	#[inline(always)]
+	fn from(n: &NumberBuf<B>) -> Self {
+		n.to_cbor()
+	}
+}
+
+impl<B: Buffer> TryFrom<&Value> for NumberBuf<B> {
+	type Error = ConversionError;
+
+	/// Converts a CBOR integer, float, bignum or decimal fraction.
+	fn try_from(value: &Value) -> Result<Self, ConversionError> {
+		match value {
+			Value::Float(f) => NumberBuf::try_from(*f).map_err(|_| ConversionError::NonFinite),
+			Value::Tag(DECIMAL_FRACTION, fraction) => {
+				let Value::Array(items) = fraction.as_ref() else {
+					return Err(ConversionError::Invalid);
+				};
+
+				let [Value::Integer(exponent), mantissa] = items.as_slice() else {
+					return Err(ConversionError::Invalid);
+				};
+
+				let exponent = i64::try_from(*exponent).map_err(|_| ConversionError::Invalid)?;
+				let (negative, digits) =
+					integer_digits(mantissa)?.ok_or(ConversionError::Invalid)?;
+
+				let mut d = Decimal {
+					negative,
+					digits,
+					exponent,
+				};
+
+				d.normalize();
+				Ok(d.into_number_buf())
+			}
+			value => {
+				let (negative, digits) =
+					integer_digits(value)?.ok_or(ConversionError::NotANumber)?;
+				let mut bytes = Vec::with_capacity(digits.len() + 1);
+
+				if digits.is_empty() {
+					bytes.push(b'0')
+				} else {
+					if negative {
+						bytes.push(b'-')
+					}
+
+					bytes.extend(digits.iter().map(|d| b'0' + d))
+				}
+
+				Ok(unsafe { NumberBuf::new_unchecked(B::from_vec(bytes)) })
+			}
+		}
+	}
+}
+
+impl<B: Buffer> TryFrom<Value> for NumberBuf<B> {
+	type Error = ConversionError;
+
+	#[inline(always)]
+	fn try_from(value: Value) -> Result<Self, ConversionError> {
+		Self::try_from(&value)
+	}
+}
+
+/// Encodes the given integer, given by its sign and decimal digits (most
+/// significant first), as a CBOR integer or bignum.
+fn integer_value(negative: bool, digits: Vec<u8>) -> Value {
+	if digits.len() <= 20 {
+		let magnitude = digits.iter().fold(0i128, |n, d| n * 10 + *d as i128);
+		let i = if negative { -magnitude } else { magnitude };
+		if let Ok(i) = Integer::try_from(i) {
+			return Value::Integer(i);
+		}
+	}
+
+	let mut d = Decimal {
+		negative: false,
+		digits,
+		exponent: 0,
+	};
+
+	if negative {
+		// The negative bignum `n` encodes the integer `-1 - n`.
+		d = d.sub(&Decimal::one());
+		Value::Tag(NEGATIVE_BIGNUM, Box::new(Value::Bytes(to_bytes(&d))))
+	} else {
+		Value::Tag(POSITIVE_BIGNUM, Box::new(Value::Bytes(to_bytes(&d))))
+	}
+}
+
+/// Decodes the sign and decimal digits (most significant first, without
+/// leading zeros) of the given CBOR integer or bignum.
+///
+/// Returns `None` if the value is not an integer.
+fn integer_digits(value: &Value) -> Result<Option<(bool, Vec<u8>)>, ConversionError> {
+	match value {
+		Value::Integer(i) => {
+			let i = i128::from(*i);
+			let d = Decimal::from_i128(i);
+			let mut digits = d.digits;
+			digits.resize(digits.len() + d.exponent as usize, 0);
+			Ok(Some((i < 0, digits)))
+		}
+		Value::Tag(tag @ (POSITIVE_BIGNUM | NEGATIVE_BIGNUM), bytes) => {
+			let Value::Bytes(bytes) = bytes.as_ref() else {
+				return Err(ConversionError::Invalid);
+			};
+
+			let mut d = Decimal {
+				negative: false,
+				digits: from_bytes(bytes),
+				exponent: 0,
+			};
+
+			d.normalize();
+			let negative = *tag == NEGATIVE_BIGNUM;
+			if negative {
+				d = d.add(&Decimal::one())
+			}
+
+			let mut digits = d.digits;
+			digits.resize(digits.len() + d.exponent as usize, 0);
+			Ok(Some((negative, digits)))
+		}
+		_ => Ok(None),
+	}
+}
+
+/// Returns the big-endian bytes of the given non-negative integer.
+fn to_bytes(d: &Decimal) -> Vec<u8> {
+	let mut digits = d.digits.clone();
+	digits.resize(digits.len() + d.exponent as usize, 0);
+
+	// Bytes, least significant first.
+	let mut bytes = Vec::new();
+	while !digits.is_empty() {
+		let mut quotient = Vec::with_capacity(digits.len());
+		let mut remainder = 0u32;
+
+		for d in digits {
+			let n = remainder * 10 + d as u32;
+			if n >= 256 || !quotient.is_empty() {
+				quotient.push((n / 256) as u8)
+			}
+
+			remainder = n % 256
+		}
+
+		bytes.push(remainder as u8);
+		digits = quotient
+	}
+
+	bytes.reverse();
+	bytes
+}
+
+/// Returns the decimal digits (most significant first) of the given
+/// big-endian integer bytes.
+fn from_bytes(bytes: &[u8]) -> Vec<u8> {
+	// Decimal digits, least significant first.
+	let mut digits = vec![0u8];
+
+	for b in bytes {
+		let mut carry = *b as u32;
+
+		for d in &mut digits {
+			let v = *d as u32 * 256 + carry;
+			*d = (v % 10) as u8;
+			carry = v / 10
+		}
+
+		while carry > 0 {
+			digits.push((carry % 10) as u8);
+			carry /= 10
+		}
+	}
+
+	digits.reverse();
+	digits
+}
+
+#[cfg(test)]
+mod tests {
+	use super::ConversionError;
+	use crate::{Number, NumberBuf};
+	use ciborium::Value;
+
+	fn round_trip(s: &str) -> String {
+		let value = Number::new(s).unwrap().to_cbor();
+		let mut bytes = Vec::new();
+		ciborium::into_writer(&value, &mut bytes).unwrap();
+		let value: Value = ciborium::from_reader(bytes.as_slice()).unwrap();
+		NumberBuf::<Vec<u8>>::try_from(value)
+			.unwrap()
+			.as_str()
+			.to_owned()
+	}
+
+	#[test]
+	fn integers() {
+		for s in [
+			"0",
+			"-12",
+			"18446744073709551615",
+			"-18446744073709551616",
+			"18446744073709551616",
+			"-18446744073709551617",
+			"123456789012345678901234567890",
+			"-123456789012345678901234567890",
+		] {
+			assert_eq!(round_trip(s), s)
+		}
+
+		let value = Number::new("18446744073709551616").unwrap().to_cbor();
+		assert_eq!(
+			value,
+			Value::Tag(2, Box::new(Value::Bytes(vec![1, 0, 0, 0, 0, 0, 0, 0, 0])))
+		);
+	}
+
+	#[test]
+	fn decimal_fractions() {
+		let value = Number::new("273.15").unwrap().to_cbor();
+		assert_eq!(
+			value,
+			Value::Tag(
+				4,
+				Box::new(Value::Array(vec![
+					Value::Integer((-2).into()),
+					Value::Integer(27315.into())
+				]))
+			)
+		);
+
+		assert_eq!(round_trip("273.15"), "273.15");
+		assert_eq!(round_trip("-1.5e-30"), "-1.5e-30");
+		assert_eq!(round_trip("0.0"), "0");
+		assert_eq!(
+			round_trip("3.14159265358979323846264338327950288"),
+			"3.14159265358979323846264338327950288"
+		);
+	}
+
+	#[test]
+	fn invalid() {
+		assert_eq!(
+			NumberBuf::<Vec<u8>>::try_from(Value::Bool(true)),
+			Err(ConversionError::NotANumber)
+		);
+		assert_eq!(
+			NumberBuf::<Vec<u8>>::try_from(Value::Float(f64::NAN)),
+			Err(ConversionError::NonFinite)
+		);
+		assert_eq!(
+			NumberBuf::<Vec<u8>>::try_from(Value::Tag(4, Box::new(Value::Null))),
+			Err(ConversionError::Invalid)
+		);
+	}
+}
@@ -0,0 +1,239 @@
+//! Value comparisons with primitive numbers.
+use crate::{decimal::Decimal, Buffer, Number, NumberBuf};
+use core::cmp::Ordering;
+
+macro_rules! impl_int_cmp {
+	($($ty:ty),*) => {
+		$(
+			impl PartialEq<$ty> for Number {
+				/// Compares the value of this number with the given integer,
+				/// regardless of its lexical representation (`1.0e2 == 100`).
+				#[inline(always)]
+				fn eq(&self, other: &$ty) -> bool {
+					self.partial_cmp(other) == Some(Ordering::Equal)
+				}
+			}
+
+			impl PartialOrd<$ty> for Number {
+				/// Compares the value of this number with the given integer.
+				#[inline(always)]
+				fn partial_cmp(&self, other: &$ty) -> Option<Ordering> {
+					Decimal::new(self).partial_cmp_signed(&Decimal::from_i128(*other as i128))
+				}
+			}
+
+			impl<B: Buffer> PartialEq<$ty> for NumberBuf<B> {
+				#[inline(always)]
+				fn eq(&self, other: &$ty) -> bool {
+					self.as_number().eq(other)
+				}
+			}
+
+			impl<B: Buffer> PartialOrd<$ty> for NumberBuf<B> {
+				#[inline(always)]
+				fn partial_cmp(&self, other: &$ty) -> Option<Ordering> {
+					self.as_number().partial_cmp(other)
+				}
+			}
+
+			impl PartialEq<Number> for $ty {
+				#[inline(always)]
+				fn eq(&self, other: &Number) -> bool {
+					other.eq(self)
+				}
+			}
+
+			impl PartialOrd<Number> for $ty {
+				#[inline(always)]
+				fn partial_cmp(&self, other: &Number) -> Option<Ordering> {
+					other.partial_cmp(self).map(Ordering::reverse)
+				}
+			}
+
+			impl<B: Buffer> PartialEq<NumberBuf<B>> for $ty {
+				#[inline(always)]
+				fn eq(&self, other: &NumberBuf<B>) -> bool {
+					other.as_number().eq(self)
+				}
+			}
+
+			impl<B: Buffer> PartialOrd<NumberBuf<B>> for $ty {
+				#[inline(always)]
+				fn partial_cmp(&self, other: &NumberBuf<B>) -> Option<Ordering> {
+					other.as_number().partial_cmp(self).map(Ordering::reverse)
+				}
+			}
+		)*
+	};
+}
+
+impl_int_cmp!(u32, i32, u64, i64);
+
+/// Compares the value of the given number with the exact value of the given
+/// float.
+///
+/// Infinities are greater (or lower) than any number, and `NaN` is not
+/// comparable.
+fn cmp_f64(n: &Number, f: f64) -> Option<Ordering> {
+	if f.is_nan() {
+		None
+	} else if f.is_infinite() {
+		Some(if f > 0.0 {
+			Ordering::Less
+		} else {
+			Ordering::Greater
+		})
+	} else {
+		Decimal::new(n).partial_cmp_signed(&Decimal::from_f64(f))
+	}
+}
+
+macro_rules! impl_float_cmp {
+	($($ty:ty),*) => {
+		$(
+			impl PartialEq<$ty> for Number {
+				/// Compares the exact value of this number with the exact value
+				/// of the given float, without rounding the number: `0.1` is
+				/// not equal to `0.1f64`, whose exact value is
+				/// `0.1000000000000000055511151231257827021181583404541015625`.
+				#[inline(always)]
+				fn eq(&self, other: &$ty) -> bool {
+					self.partial_cmp(other) == Some(Ordering::Equal)
+				}
+			}
+
+			impl PartialOrd<$ty> for Number {
+				/// Compares the exact value of this number with the exact value
+				/// of the given float.
+				///
+				/// Infinities are greater (or lower) than any number, and `NaN`
+				/// is not comparable.
+				#[inline(always)]
+				fn partial_cmp(&self, other: &$ty) -> Option<Ordering> {
+					cmp_f64(self, *other as f64)
+				}
+			}
+
+			impl<B: Buffer> PartialEq<$ty> for NumberBuf<B> {
+				#[inline(always)]
+				fn eq(&self, other: &$ty) -> bool {
+					self.as_number().eq(other)
+				}
+			}
+
+			impl<B: Buffer> PartialOrd<$ty> for NumberBuf<B> {
+				#[inline(always)]
+				fn partial_cmp(&self, other: &$ty) -> Option<Ordering> {
+					self.as_number().partial_cmp(other)
+				}
+			}
+
+			impl PartialEq<Number> for $ty {
+				#[inline(always)]
+				fn eq(&self, other: &Number) -> bool {
+					other.eq(self)
+				}
+			}
+
+			impl PartialOrd<Number> for $ty {
+				#[inline(always)]
+				fn partial_cmp(&self, other: &Number) -> Option<Ordering> {
+					other.partial_cmp(self).map(Ordering::reverse)
+				}
+			}
+
+			impl<B: Buffer> PartialEq<NumberBuf<B>> for $ty {
+				#[inline(always)]
+				fn eq(&self, other: &NumberBuf<B>) -> bool {
+					other.as_number().eq(self)
+				}
+			}
+
+			impl<B: Buffer> PartialOrd<NumberBuf<B>> for $ty {
+				#[inline(always)]
+				fn partial_cmp(&self, other: &NumberBuf<B>) -> Option<Ordering> {
+					other.as_number().partial_cmp(self).map(Ordering::reverse)
+				}
+			}
+		)*
+	};
+}
+
+impl_float_cmp!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+	use crate::{Number, NumberBuf};
+
+	fn number(s: &str) -> &Number {
+		Number::new(s).unwrap()
+	}
+
+	#[test]
+	fn int_eq() {
+		assert!(*number("0") == 0u64);
+		assert!(*number("-0.0e3") == 0i32);
+		assert!(*number("1.0e2") == 100u32);
+		assert!(*number("-12") == -12i64);
+		assert!(*number("12.5") != 12i64);
+		assert!(*number("18446744073709551615") == u64::MAX);
+		assert!(*number("-9223372036854775808") == i64::MIN);
+		assert!(*number("1e20") != u64::MAX);
+
+		let n: NumberBuf = "42".parse().unwrap();
+		assert_eq!(n, 42u64);
+		assert_eq!(42i32, n);
+		assert_eq!(-0i64, *number("0"))
+	}
+
+	#[test]
+	fn int_cmp() {
+		assert!(*number("100.5") > 100i64);
+		assert!(*number("1e3") > 100i64);
+		assert!(*number("-1e3") < -100i32);
+		assert!(*number("0.999") < 1u32);
+		assert!(*number("1e-100") > 0u64);
+		assert!(*number("-1e-100") < 0u64);
+		assert!(*number("1e99999999999999999999") > u64::MAX);
+		assert!(*number("-1e-99999999999999999999") < 0i32);
+
+		let n: NumberBuf = "-2.5".parse().unwrap();
+		assert!(n < -2i64);
+		assert!(-3i64 < n);
+		assert!(0u32 > n)
+	}
+
+	#[test]
+	fn float_eq() {
+		assert!(*number("0") == 0.0f64);
+		assert!(*number("-0") == -0.0f64);
+		assert!(*number("1.5e2") == 150f32);
+		assert!(*number("0.5") == 0.5f32);
+		assert!(*number("0.1") != 0.1f64);
+		assert!(*number("0.1000000000000000055511151231257827021181583404541015625") == 0.1f64);
+		assert!(*number("0.100000001490116119384765625") == 0.1f32);
+		assert!(!number("1").eq(&f64::NAN));
+
+		let n: NumberBuf = "-2.25".parse().unwrap();
+		assert_eq!(n, -2.25f64);
+		assert_eq!(-2.25f32, n)
+	}
+
+	#[test]
+	fn float_cmp() {
+		assert!(*number("0.1") < 0.1f64);
+		assert!(*number("0.1") < 0.1f32);
+		assert!(*number("0.10000001") > 0.1f32);
+		assert!(*number("1e308") < f64::MAX);
+		assert!(*number("1e309") > f64::MAX);
+		assert!(*number("1e999999") < f64::INFINITY);
+		assert!(*number("-1e999999") > f64::NEG_INFINITY);
+		assert!(*number("1e-400") > 0.0f64);
+		assert!(*number("1e-400") < f64::from_bits(1));
+		assert_eq!(number("1").partial_cmp(&f64::NAN), None);
+
+		let n: NumberBuf = "3".parse().unwrap();
+		assert!(n > 2.5f32);
+		assert!(2.5f64 < n)
+	}
+}
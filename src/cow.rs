@@ -0,0 +1,229 @@
+//! Clone-on-write number.
+use crate::{Buffer, Number, NumberBuf};
+use alloc::borrow::Cow;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+/// Clone-on-write number.
+///
+/// This is either a borrowed [`Number`] or an owned [`NumberBuf`]. It is
+/// similar to `Cow<'a, Number>`, but works with any buffer type `B`.
+pub enum CowNumber<'a, B = alloc::vec::Vec<u8>> {
+	/// Borrowed number.
+	Borrowed(&'a Number),
+
+	/// Owned number.
+	Owned(NumberBuf<B>),
+}
+
+impl<'a, B: Buffer> CowNumber<'a, B> {
+	/// Checks if the number is borrowed.
+	#[inline(always)]
+	pub fn is_borrowed(&self) -> bool {
+		matches!(self, Self::Borrowed(_))
+	}
+
+	/// Checks if the number is owned.
+	#[inline(always)]
+	pub fn is_owned(&self) -> bool {
+		matches!(self, Self::Owned(_))
+	}
+
+	/// Returns a reference to the number.
+	#[inline(always)]
+	pub fn as_number(&self) -> &Number {
+		match self {
+			Self::Borrowed(n) => n,
+			Self::Owned(n) => n.as_number(),
+		}
+	}
+
+	/// Returns a mutable reference to the owned number, cloning the
+	/// borrowed number first if necessary.
+	pub fn to_mut(&mut self) -> &mut NumberBuf<B> {
+		if let Self::Borrowed(n) = *self {
+			*self = Self::Owned(unsafe { NumberBuf::new_unchecked(B::from_bytes(n.as_bytes())) })
+		}
+
+		match self {
+			Self::Owned(n) => n,
+			Self::Borrowed(_) => unreachable!(),
+		}
+	}
+
+	/// Returns the owned number, cloning the borrowed number if necessary.
+	pub fn into_owned(self) -> NumberBuf<B> {
+		match self {
+			Self::Borrowed(n) => unsafe { NumberBuf::new_unchecked(B::from_bytes(n.as_bytes())) },
+			Self::Owned(n) => n,
+		}
+	}
+}
+
+impl<B: Buffer> Deref for CowNumber<'_, B> {
+	type Target = Number;
+
+	#[inline(always)]
+	fn deref(&self) -> &Number {
+		self.as_number()
+	}
+}
+
+impl<B: Buffer> AsRef<Number> for CowNumber<'_, B> {
+	#[inline(always)]
+	fn as_ref(&self) -> &Number {
+		self.as_number()
+	}
+}
+
+impl<B: Buffer> Borrow<Number> for CowNumber<'_, B> {
+	#[inline(always)]
+	fn borrow(&self) -> &Number {
+		self.as_number()
+	}
+}
+
+impl<B: Clone> Clone for CowNumber<'_, B> {
+	fn clone(&self) -> Self {
+		match self {
+			Self::Borrowed(n) => Self::Borrowed(n),
+			Self::Owned(n) => Self::Owned(n.clone()),
+		}
+	}
+}
+
+impl<'a, B> From<&'a Number> for CowNumber<'a, B> {
+	#[inline(always)]
+	fn from(n: &'a Number) -> Self {
+		Self::Borrowed(n)
+	}
+}
+
+impl<'a, B: Buffer> From<&'a NumberBuf<B>> for CowNumber<'a, B> {
+	#[inline(always)]
+	fn from(n: &'a NumberBuf<B>) -> Self {
+		Self::Borrowed(n.as_number())
+	}
+}
+
+impl<B> From<NumberBuf<B>> for CowNumber<'_, B> {
+	#[inline(always)]
+	fn from(n: NumberBuf<B>) -> Self {
+		Self::Owned(n)
+	}
+}
+
+impl<'a> From<Cow<'a, Number>> for CowNumber<'a> {
+	#[inline(always)]
+	fn from(n: Cow<'a, Number>) -> Self {
+		match n {
+			Cow::Borrowed(n) => Self::Borrowed(n),
+			Cow::Owned(n) => Self::Owned(n),
+		}
+	}
+}
+
+impl<'a> From<CowNumber<'a>> for Cow<'a, Number> {
+	#[inline(always)]
+	fn from(n: CowNumber<'a>) -> Self {
+		match n {
+			CowNumber::Borrowed(n) => Cow::Borrowed(n),
+			CowNumber::Owned(n) => Cow::Owned(n),
+		}
+	}
+}
+
+impl<B: Buffer, C: Buffer> PartialEq<CowNumber<'_, C>> for CowNumber<'_, B> {
+	#[inline(always)]
+	fn eq(&self, other: &CowNumber<'_, C>) -> bool {
+		self.as_number() == other.as_number()
+	}
+}
+
+impl<B: Buffer> PartialEq<Number> for CowNumber<'_, B> {
+	#[inline(always)]
+	fn eq(&self, other: &Number) -> bool {
+		self.as_number() == other
+	}
+}
+
+impl<B: Buffer> PartialEq<&Number> for CowNumber<'_, B> {
+	#[inline(always)]
+	fn eq(&self, other: &&Number) -> bool {
+		self.as_number() == *other
+	}
+}
+
+impl<B: Buffer> Eq for CowNumber<'_, B> {}
+
+impl<B: Buffer> PartialOrd for CowNumber<'_, B> {
+	#[inline(always)]
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<B: Buffer> Ord for CowNumber<'_, B> {
+	#[inline(always)]
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.as_number().cmp(other.as_number())
+	}
+}
+
+impl<B: Buffer> Hash for CowNumber<'_, B> {
+	#[inline(always)]
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.as_number().hash(state)
+	}
+}
+
+impl<B: Buffer> fmt::Display for CowNumber<'_, B> {
+	#[inline(always)]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(self.as_number(), f)
+	}
+}
+
+impl<B: Buffer> fmt::Debug for CowNumber<'_, B> {
+	#[inline(always)]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(self.as_number(), f)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::CowNumber;
+	use crate::{Number, NumberBuf};
+	use std::borrow::Cow;
+
+	#[test]
+	fn clone_on_write() {
+		let n = Number::new("1.5").unwrap();
+		let mut cow: CowNumber = n.into();
+		assert!(cow.is_borrowed());
+		assert_eq!(cow, n);
+		assert_eq!(cow.as_f64_lossy(), 1.5);
+
+		cow.to_mut().increment();
+		assert!(cow.is_owned());
+		assert_eq!(cow.to_string(), "2.5");
+		assert_eq!(format!("{cow:+.2}"), "+2.50");
+		assert_eq!(cow.clone().into_owned().as_str(), "2.5");
+		assert_eq!(CowNumber::<Vec<u8>>::from(n).into_owned().as_str(), "1.5");
+		assert_ne!(cow, CowNumber::<Vec<u8>>::Borrowed(n));
+	}
+
+	#[test]
+	fn std_cow() {
+		let n = Number::new("-7").unwrap();
+		let cow: Cow<Number> = CowNumber::Borrowed(n).into();
+		assert!(matches!(cow, Cow::Borrowed(_)));
+		let cow: CowNumber = Cow::<Number>::Owned(n.to_owned()).into();
+		assert!(cow.is_owned());
+		assert_eq!(cow, NumberBuf::<Vec<u8>>::from_number(n).as_number());
+	}
+}
@@ -0,0 +1,1089 @@
+//! Exact decimal decomposition and arithmetic.
+use crate::{Buffer, Number, NumberBuf, RoundingMode};
+use alloc::{string::ToString, vec, vec::Vec};
+use core::cmp::Ordering;
+
+/// Maximum absolute value of exact exponents.
+const MAX_EXPONENT: i64 = Number::MAX_EXACT_EXPONENT as i64;
+
+/// Maximum number of zeros appended to digits to align them with another
+/// decimal.
+const MAX_PADDING: u64 = u32::MAX as u64;
+
+/// Decimal value of a number, decomposed into a sign, a sequence of
+/// significant digits and a power of ten.
+///
+/// The value represented is `digits × 10^exponent`, negated if `negative` is
+/// `true`. Digits are stored as values in `0..=9`, most significant first,
+/// without any leading or trailing zero. Zero is represented with no digits.
+///
+/// Exponents greater than [`Number::MAX_EXACT_EXPONENT`] in absolute value
+/// are saturated to `i64::MAX` or `i64::MIN`. Such saturated decimals only
+/// keep their sign and digits: see [`Decimal::partial_cmp_abs`] to compare
+/// them. Arithmetic operations panic on them.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Decimal {
+	pub negative: bool,
+	pub digits: Vec<u8>,
+	pub exponent: i64,
+}
+
+impl Decimal {
+	/// Decomposes the given number.
+	pub fn new(n: &Number) -> Self {
+		let bytes = n.as_bytes();
+		let mut negative = false;
+		let mut digits = Vec::with_capacity(bytes.len());
+		let mut fraction_len: i64 = 0;
+		let mut fractional_part = false;
+		let mut i = 0;
+
+		while i < bytes.len() {
+			match bytes[i] {
+				b'-' => negative = true,
+				b'.' => fractional_part = true,
+				b'e' | b'E' => break,
+				b => {
+					if fractional_part {
+						fraction_len += 1
+					}
+
+					if b != b'0' || !digits.is_empty() {
+						digits.push(b - b'0')
+					}
+				}
+			}
+
+			i += 1
+		}
+
+		let mut exponent: i64 = 0;
+		if i < bytes.len() {
+			let mut exponent_negative = false;
+			for &b in &bytes[(i + 1)..] {
+				match b {
+					b'-' => exponent_negative = true,
+					b'+' => (),
+					b => {
+						exponent = exponent
+							.saturating_mul(10)
+							.saturating_add((b - b'0') as i64)
+					}
+				}
+			}
+
+			if exponent_negative {
+				exponent = -exponent
+			}
+		}
+
+		let mut result = Self {
+			negative,
+			digits,
+			exponent: exponent.saturating_sub(fraction_len),
+		};
+
+		result.normalize();
+		result
+	}
+
+	/// Returns zero.
+	#[inline(always)]
+	pub fn zero() -> Self {
+		Self {
+			negative: false,
+			digits: Vec::new(),
+			exponent: 0,
+		}
+	}
+
+	/// Returns one.
+	#[inline(always)]
+	pub fn one() -> Self {
+		Self {
+			negative: false,
+			digits: vec![1],
+			exponent: 0,
+		}
+	}
+
+	/// Returns the decimal value of the given integer.
+	pub fn from_i128(i: i128) -> Self {
+		let mut result = Self {
+			negative: i < 0,
+			digits: i
+				.unsigned_abs()
+				.to_string()
+				.bytes()
+				.map(|d| d - b'0')
+				.collect(),
+			exponent: 0,
+		};
+
+		result.normalize();
+		result
+	}
+
+	/// Builds the decimal value of an integer given as ASCII digits.
+	pub fn from_ascii_integer(negative: bool, digits: &[u8]) -> Self {
+		let mut result = Self {
+			negative,
+			digits: digits.iter().map(|d| d - b'0').collect(),
+			exponent: 0,
+		};
+
+		result.normalize();
+		result
+	}
+
+	/// Decomposes the given number into the value of its significand (the
+	/// part before the exponent marker) and the value of its exponent part,
+	/// as an integer.
+	///
+	/// Unlike [`Decimal::new`], this is exact whatever the exponent.
+	pub fn split_exponent(n: &Number) -> (Self, Self) {
+		let bytes = n.as_bytes();
+		let e = bytes
+			.iter()
+			.position(|b| matches!(b, b'e' | b'E'))
+			.unwrap_or(bytes.len());
+
+		// The part before the exponent is itself a valid number.
+		let significand = Self::new(unsafe { Number::new_unchecked(&bytes[..e]) });
+		let exponent = match bytes.get(e + 1..).unwrap_or_default() {
+			[b'-', digits @ ..] => Self::from_ascii_integer(true, digits),
+			[b'+', digits @ ..] => Self::from_ascii_integer(false, digits),
+			digits => Self::from_ascii_integer(false, digits),
+		};
+
+		(significand, exponent)
+	}
+
+	/// Returns the exact decimal value of the given finite float.
+	pub fn from_f64(f: f64) -> Self {
+		debug_assert!(f.is_finite());
+		let bits = f.to_bits();
+		let biased_exponent = ((bits >> 52) & 0x7ff) as i64;
+		let fraction = bits & ((1 << 52) - 1);
+
+		// `f = mantissa × 2^exponent`.
+		let (mantissa, exponent) = if biased_exponent == 0 {
+			(fraction, -1074)
+		} else {
+			(fraction | (1 << 52), biased_exponent - 1075)
+		};
+
+		let mut result = Self::from_i128(mantissa as i128);
+		if exponent >= 0 {
+			result = result.mul(&Self::pow(2, exponent as u32))
+		} else if !result.is_zero() {
+			// `2^-n = 5^n × 10^-n`.
+			result = result.mul(&Self::pow(5, -exponent as u32));
+			result.exponent -= -exponent
+		}
+
+		result.negative = f.is_sign_negative() && !result.is_zero();
+		result
+	}
+
+	/// Computes `base^n`.
+	pub fn pow(base: u8, mut n: u32) -> Self {
+		let mut result = Self::one();
+		let mut base = Self::from_i128(base as i128);
+		while n > 0 {
+			if n & 1 == 1 {
+				result = result.mul(&base)
+			}
+
+			n >>= 1;
+			if n > 0 {
+				base = base.mul(&base)
+			}
+		}
+
+		result
+	}
+
+	/// Checks if the exponent of this decimal is saturated, in which case
+	/// only its sign and digits are known.
+	#[inline(always)]
+	pub fn is_saturated(&self) -> bool {
+		!self.is_zero() && (self.exponent == i64::MAX || self.exponent == i64::MIN)
+	}
+
+	/// Checks if this is zero.
+	#[inline(always)]
+	pub fn is_zero(&self) -> bool {
+		self.digits.is_empty()
+	}
+
+	/// Removes leading and trailing zeros, and the sign of zero.
+	pub fn normalize(&mut self) {
+		let leading = self.digits.iter().take_while(|d| **d == 0).count();
+		self.digits.drain(..leading);
+
+		let trailing = self.digits.iter().rev().take_while(|d| **d == 0).count();
+		self.digits.truncate(self.digits.len() - trailing);
+		self.exponent = self.exponent.saturating_add(trailing as i64);
+
+		if self.digits.is_empty() {
+			self.negative = false;
+			self.exponent = 0
+		} else if self.exponent > MAX_EXPONENT {
+			self.exponent = i64::MAX
+		} else if self.exponent < -MAX_EXPONENT {
+			self.exponent = i64::MIN
+		}
+	}
+
+	/// Formats this decimal into a JSON number.
+	///
+	/// Following the ECMAScript `Number::toString` convention, the plain
+	/// decimal notation is used when the decimal point position is between
+	/// `-6` and `21`, and the scientific notation otherwise.
+	pub fn into_number_buf<B: Buffer>(self) -> NumberBuf<B> {
+		let mut bytes = Vec::with_capacity(self.digits.len() + 8);
+
+		if self.digits.is_empty() {
+			bytes.push(b'0')
+		} else {
+			if self.negative {
+				bytes.push(b'-')
+			}
+
+			let len = self.digits.len() as i64;
+			let point = len.saturating_add(self.exponent);
+			let digits = self.digits.iter().map(|d| b'0' + d);
+
+			if self.exponent >= 0 && point <= 21 {
+				bytes.extend(digits);
+				bytes.resize(bytes.len() + self.exponent as usize, b'0')
+			} else if 0 < point && point <= 21 {
+				let (integer, fraction) = self.digits.split_at(point as usize);
+				bytes.extend(integer.iter().map(|d| b'0' + d));
+				bytes.push(b'.');
+				bytes.extend(fraction.iter().map(|d| b'0' + d))
+			} else if -6 < point && point <= 0 {
+				bytes.extend_from_slice(b"0.");
+				bytes.resize(bytes.len() + (-point) as usize, b'0');
+				bytes.extend(digits)
+			} else {
+				bytes.push(b'0' + self.digits[0]);
+				if self.digits.len() > 1 {
+					bytes.push(b'.');
+					bytes.extend(self.digits[1..].iter().map(|d| b'0' + d))
+				}
+
+				bytes.push(b'e');
+				bytes.extend_from_slice((point - 1).to_string().as_bytes())
+			}
+		}
+
+		unsafe { NumberBuf::new_unchecked(B::from_vec(bytes)) }
+	}
+
+	/// Formats this decimal in plain decimal notation with exactly
+	/// `fraction_digits` digits after the decimal point (and no decimal
+	/// point if `fraction_digits` is zero).
+	///
+	/// The exponent must be greater or equal to `-fraction_digits`.
+	pub fn to_fixed(&self, fraction_digits: usize) -> Vec<u8> {
+		debug_assert!(self.exponent >= -(fraction_digits as i64));
+		let mut bytes = Vec::with_capacity(self.digits.len() + fraction_digits + 2);
+
+		if self.negative {
+			bytes.push(b'-')
+		}
+
+		// All the digits, down to the last fractional digit.
+		let mut digits: Vec<u8> = self.digits.iter().map(|d| b'0' + d).collect();
+		digits.resize(
+			(digits.len() as i64 + self.exponent + fraction_digits as i64) as usize,
+			b'0',
+		);
+
+		if digits.len() <= fraction_digits {
+			bytes.push(b'0');
+			if fraction_digits > 0 {
+				bytes.push(b'.');
+				bytes.resize(bytes.len() + fraction_digits - digits.len(), b'0');
+				bytes.extend_from_slice(&digits)
+			}
+		} else {
+			let (integer, fraction) = digits.split_at(digits.len() - fraction_digits);
+			bytes.extend_from_slice(integer);
+			if fraction_digits > 0 {
+				bytes.push(b'.');
+				bytes.extend_from_slice(fraction)
+			}
+		}
+
+		bytes
+	}
+
+	/// Formats this decimal in scientific notation `d.ddde<x>`, with a
+	/// single leading digit and the given exponent `marker`.
+	///
+	/// If `fraction_digits` is given, the fractional digits are padded with
+	/// zeros up to this number of digits. This decimal must not have more
+	/// significant digits than that.
+	pub fn to_scientific(&self, marker: u8, fraction_digits: Option<usize>) -> Vec<u8> {
+		let exponent = if self.digits.is_empty() {
+			0
+		} else {
+			self.point() - 1
+		};
+
+		self.to_scientific_with_exponent(marker, fraction_digits, exponent.to_string().as_bytes())
+	}
+
+	/// Formats this decimal in scientific notation like
+	/// [`Decimal::to_scientific`], with the given exponent digits.
+	fn to_scientific_with_exponent(
+		&self,
+		marker: u8,
+		fraction_digits: Option<usize>,
+		exponent: &[u8],
+	) -> Vec<u8> {
+		let mut bytes = Vec::with_capacity(self.digits.len() + exponent.len() + 4);
+
+		if self.negative {
+			bytes.push(b'-')
+		}
+
+		let (first, rest) = self.digits.split_first().unwrap_or((&0, &[]));
+		bytes.push(b'0' + first);
+
+		let fraction_digits = fraction_digits.unwrap_or(rest.len());
+		debug_assert!(rest.len() <= fraction_digits);
+		if fraction_digits > 0 {
+			bytes.push(b'.');
+			bytes.extend(rest.iter().map(|d| b'0' + d));
+			bytes.resize(bytes.len() + fraction_digits - rest.len(), b'0')
+		}
+
+		bytes.push(marker);
+		bytes.extend_from_slice(exponent);
+		bytes
+	}
+
+	/// Formats this decimal into its shortest JSON number representation.
+	///
+	/// The plain decimal notation is preferred on ties, then the scientific
+	/// notation with the fewest fraction digits.
+	pub fn to_shortest(&self) -> Vec<u8> {
+		/// Length of the decimal representation of an integer.
+		fn int_len(i: i64) -> i128 {
+			i.unsigned_abs().checked_ilog10().unwrap_or(0) as i128 + 1 + (i < 0) as i128
+		}
+
+		if self.digits.is_empty() {
+			return vec![b'0'];
+		}
+
+		let len = self.digits.len() as i128;
+		let point = self.point();
+		let plain_len = if self.exponent >= 0 {
+			len + self.exponent as i128
+		} else if point > 0 {
+			len + 1
+		} else {
+			len + 2 - point as i128
+		};
+
+		// Scientific notation with `k` integer digits.
+		let (best_len, best_k) = (1..=self.digits.len())
+			.rev()
+			.map(|k| {
+				let exponent = point.saturating_sub(k as i64);
+				let len = len + (k < self.digits.len()) as i128 + 1 + int_len(exponent);
+				(len, k)
+			})
+			.min_by_key(|(len, _)| *len)
+			.unwrap();
+
+		if plain_len <= best_len {
+			let mut bytes = Vec::with_capacity(plain_len as usize + 1);
+			if self.negative {
+				bytes.push(b'-')
+			}
+
+			let digits = self.digits.iter().map(|d| b'0' + d);
+			if self.exponent >= 0 {
+				bytes.extend(digits);
+				bytes.resize(bytes.len() + self.exponent as usize, b'0')
+			} else if point > 0 {
+				let (integer, fraction) = self.digits.split_at(point as usize);
+				bytes.extend(integer.iter().map(|d| b'0' + d));
+				bytes.push(b'.');
+				bytes.extend(fraction.iter().map(|d| b'0' + d))
+			} else {
+				bytes.extend_from_slice(b"0.");
+				bytes.resize(bytes.len() + (-point) as usize, b'0');
+				bytes.extend(digits)
+			}
+
+			bytes
+		} else {
+			let mut bytes = Vec::with_capacity(best_len as usize + 1);
+			if self.negative {
+				bytes.push(b'-')
+			}
+
+			let (integer, fraction) = self.digits.split_at(best_k);
+			bytes.extend(integer.iter().map(|d| b'0' + d));
+			if !fraction.is_empty() {
+				bytes.push(b'.');
+				bytes.extend(fraction.iter().map(|d| b'0' + d))
+			}
+
+			bytes.push(b'e');
+			bytes.extend_from_slice(point.saturating_sub(best_k as i64).to_string().as_bytes());
+			bytes
+		}
+	}
+
+	/// Formats the given number, whose decomposition is saturated, into its
+	/// shortest JSON number representation.
+	///
+	/// The exponent has at least 19 digits, so the shortest representation
+	/// is always the scientific notation with an integer significand.
+	pub fn saturated_shortest(n: &Number) -> Vec<u8> {
+		let (significand, exponent) = Self::split_exponent(n);
+		let exponent = shifted_exponent(&exponent, significand.exponent);
+		let mut bytes = Vec::with_capacity(significand.digits.len() + exponent.len() + 2);
+		if significand.negative {
+			bytes.push(b'-')
+		}
+
+		bytes.extend(significand.digits.iter().map(|d| b'0' + d));
+		bytes.push(b'e');
+		bytes.extend_from_slice(&exponent);
+		bytes
+	}
+
+	/// Formats the given number, whose decomposition is saturated, in
+	/// scientific notation like [`Decimal::to_scientific`].
+	///
+	/// If `precision` is given, the significand is rounded (half to even) to
+	/// this number of fractional digits.
+	pub fn saturated_scientific(n: &Number, marker: u8, precision: Option<usize>) -> Vec<u8> {
+		let (mut significand, exponent) = Self::split_exponent(n);
+		if let Some(precision) = precision {
+			let last = significand.point().saturating_sub(precision as i64 + 1);
+			significand = significand.round(last, RoundingMode::HalfEven)
+		}
+
+		significand.to_scientific_shifted(marker, precision, &exponent)
+	}
+
+	/// Formats this non-zero decimal multiplied by `10^shift` in scientific
+	/// notation like [`Decimal::to_scientific`], where `shift` is an integer
+	/// that may be too large for the exponent of a decimal.
+	pub fn to_scientific_shifted(
+		&self,
+		marker: u8,
+		fraction_digits: Option<usize>,
+		shift: &Self,
+	) -> Vec<u8> {
+		let exponent = shifted_exponent(shift, self.point() - 1);
+		self.to_scientific_with_exponent(marker, fraction_digits, &exponent)
+	}
+
+	/// Returns the position of the decimal point relative to the first
+	/// significant digit.
+	#[inline(always)]
+	pub fn point(&self) -> i64 {
+		(self.digits.len() as i64).saturating_add(self.exponent)
+	}
+
+	/// Compares the absolute values of two decimals.
+	pub fn cmp_abs(&self, other: &Self) -> Ordering {
+		match (self.is_zero(), other.is_zero()) {
+			(true, true) => Ordering::Equal,
+			(true, false) => Ordering::Less,
+			(false, true) => Ordering::Greater,
+			(false, false) => self
+				.point()
+				.cmp(&other.point())
+				.then_with(|| self.digits.cmp(&other.digits)),
+		}
+	}
+
+	/// Compares the values of two decimals.
+	pub fn cmp_signed(&self, other: &Self) -> Ordering {
+		match (self.negative, other.negative) {
+			(false, true) => Ordering::Greater,
+			(true, false) => Ordering::Less,
+			(false, false) => self.cmp_abs(other),
+			(true, true) => other.cmp_abs(self),
+		}
+	}
+
+	/// Returns the range of possible positions of the decimal point,
+	/// relative to the first significant digit, of this non-zero decimal.
+	fn point_range(&self) -> (i128, i128) {
+		let len = self.digits.len() as i128;
+		match self.exponent {
+			i64::MAX => (MAX_EXPONENT as i128 + 1 + len, i128::MAX),
+			i64::MIN => (i128::MIN, -(MAX_EXPONENT as i128) - 1 + len),
+			e => (e as i128 + len, e as i128 + len),
+		}
+	}
+
+	/// Compares the absolute values of two decimals, which may be
+	/// saturated.
+	///
+	/// Returns `None` if the order depends on the exact value of a saturated
+	/// exponent.
+	pub fn partial_cmp_abs(&self, other: &Self) -> Option<Ordering> {
+		if self.is_zero() || other.is_zero() || !(self.is_saturated() || other.is_saturated()) {
+			return Some(self.cmp_abs(other));
+		}
+
+		let (low, high) = self.point_range();
+		let (other_low, other_high) = other.point_range();
+		if high < other_low {
+			Some(Ordering::Less)
+		} else if low > other_high {
+			Some(Ordering::Greater)
+		} else {
+			None
+		}
+	}
+
+	/// Compares the values of two decimals, which may be saturated.
+	///
+	/// Returns `None` if the order depends on the exact value of a saturated
+	/// exponent.
+	pub fn partial_cmp_signed(&self, other: &Self) -> Option<Ordering> {
+		match (self.negative, other.negative) {
+			(false, true) => Some(Ordering::Greater),
+			(true, false) => Some(Ordering::Less),
+			(false, false) => self.partial_cmp_abs(other),
+			(true, true) => other.partial_cmp_abs(self),
+		}
+	}
+
+	/// Returns the digits of this decimal followed by `zeros` zeros.
+	///
+	/// # Panics
+	///
+	/// Panics if `zeros` is greater than [`MAX_PADDING`].
+	fn padded_digits(&self, zeros: u64) -> Vec<u8> {
+		assert!(zeros <= MAX_PADDING, "exponent gap too large");
+		let mut digits = self.digits.clone();
+		digits.resize(digits.len() + zeros as usize, 0);
+		digits
+	}
+
+	/// Returns the digits of this decimal, padded with zeros so that they
+	/// represent the value at the given `exponent`, lower or equal to the
+	/// decimal's exponent.
+	///
+	/// # Panics
+	///
+	/// Panics if the exponent gap is greater than [`MAX_PADDING`].
+	fn aligned_digits(&self, exponent: i64) -> Vec<u8> {
+		let zeros = self
+			.exponent
+			.checked_sub(exponent)
+			.and_then(|zeros| u64::try_from(zeros).ok())
+			.expect("exponent gap too large");
+		self.padded_digits(zeros)
+	}
+
+	/// Checks that the given decimals can be operated on.
+	///
+	/// # Panics
+	///
+	/// Panics if one of the decimals is saturated.
+	fn assert_exact(&self, other: &Self) {
+		assert!(
+			!self.is_saturated() && !other.is_saturated(),
+			"exponent out of range"
+		)
+	}
+
+	/// Computes `self + other`, where `other` is negated if `negate` is
+	/// `true`.
+	///
+	/// # Panics
+	///
+	/// Panics if an operand is saturated, or if their exponent gap is
+	/// greater than [`MAX_PADDING`].
+	fn add_signed(&self, other: &Self, negate: bool) -> Self {
+		self.assert_exact(other);
+		let other_negative = other.negative != negate;
+
+		if other.is_zero() {
+			return self.clone();
+		}
+
+		if self.is_zero() {
+			let mut result = other.clone();
+			result.negative = other_negative;
+			return result;
+		}
+
+		let exponent = self.exponent.min(other.exponent);
+		let a = self.aligned_digits(exponent);
+		let b = other.aligned_digits(exponent);
+
+		let (negative, digits) = if self.negative == other_negative {
+			(self.negative, add_magnitudes(&a, &b))
+		} else {
+			match self.cmp_abs(other) {
+				Ordering::Greater => (self.negative, sub_magnitudes(&a, &b)),
+				Ordering::Less => (other_negative, sub_magnitudes(&b, &a)),
+				Ordering::Equal => return Self::zero(),
+			}
+		};
+
+		let mut result = Self {
+			negative,
+			digits,
+			exponent,
+		};
+
+		result.normalize();
+		result
+	}
+
+	/// Computes `self + other`.
+	#[inline(always)]
+	pub fn add(&self, other: &Self) -> Self {
+		self.add_signed(other, false)
+	}
+
+	/// Computes `self - other`.
+	#[inline(always)]
+	pub fn sub(&self, other: &Self) -> Self {
+		self.add_signed(other, true)
+	}
+
+	/// Computes `self × other`.
+	///
+	/// # Panics
+	///
+	/// Panics if an operand is saturated.
+	pub fn mul(&self, other: &Self) -> Self {
+		self.assert_exact(other);
+		if self.is_zero() || other.is_zero() {
+			return Self::zero();
+		}
+
+		// Little-endian accumulator.
+		let mut acc = vec![0u32; self.digits.len() + other.digits.len()];
+		for (i, a) in self.digits.iter().rev().enumerate() {
+			let mut carry = 0;
+			for (j, b) in other.digits.iter().rev().enumerate() {
+				let v = acc[i + j] + (*a as u32) * (*b as u32) + carry;
+				acc[i + j] = v % 10;
+				carry = v / 10
+			}
+
+			acc[i + other.digits.len()] += carry
+		}
+
+		let mut result = Self {
+			negative: self.negative != other.negative,
+			digits: acc.into_iter().rev().map(|d| d as u8).collect(),
+			exponent: self.exponent.saturating_add(other.exponent),
+		};
+
+		result.normalize();
+		result
+	}
+
+	/// Computes `self ÷ other`, rounded to `scale` fractional digits.
+	///
+	/// # Panics
+	///
+	/// Panics if `other` is zero, or if an operand is saturated.
+	pub fn div(&self, other: &Self, scale: u32, rounding: RoundingMode) -> Self {
+		assert!(!other.is_zero(), "division by zero");
+		self.assert_exact(other);
+
+		// Computes the quotient of `|self| × 10^scale / |other|`, and how the
+		// remainder compares to half the divisor (`None` if the remainder is
+		// zero).
+		let k = self
+			.exponent
+			.saturating_sub(other.exponent)
+			.saturating_add(scale as i64);
+		let (quotient, half) = if self.is_zero() {
+			(Vec::new(), None)
+		} else if k < 0 && k.unsigned_abs() > self.digits.len() as u64 + 1 {
+			// The divisor is more than ten times larger than the dividend.
+			(Vec::new(), Some(Ordering::Less))
+		} else {
+			let (n, d) = if k >= 0 {
+				(self.padded_digits(k as u64), other.digits.clone())
+			} else {
+				(self.digits.clone(), other.padded_digits(k.unsigned_abs()))
+			};
+
+			let (quotient, remainder) = div_magnitudes(&n, &d);
+			let half = (!remainder.is_empty())
+				.then(|| cmp_magnitudes(&add_magnitudes(&remainder, &remainder), &d));
+			(quotient, half)
+		};
+
+		let mut result = Self {
+			negative: self.negative != other.negative,
+			digits: quotient,
+			exponent: -(scale as i64),
+		};
+
+		if let Some(half) = half {
+			let odd = result.digits.last().is_some_and(|d| d % 2 == 1);
+			if rounding.rounds_away_from_zero(result.negative, odd, half) {
+				result.digits = add_magnitudes(&result.digits, &[1])
+			}
+		}
+
+		result.normalize();
+		result
+	}
+
+	/// Rounds this decimal so that its least significant digit is at the
+	/// given `exponent` or above.
+	pub fn round(&self, exponent: i64, rounding: RoundingMode) -> Self {
+		if self.exponent >= exponent {
+			return self.clone();
+		}
+
+		// Number of kept digits.
+		let kept = self.point().saturating_sub(exponent);
+
+		let (digits, half) = if kept < 0 {
+			(Vec::new(), Ordering::Less)
+		} else {
+			let (digits, dropped) = self.digits.split_at(kept as usize);
+			let half = match dropped[0].cmp(&5) {
+				Ordering::Equal if dropped.len() > 1 => Ordering::Greater,
+				half => half,
+			};
+
+			(digits.to_vec(), half)
+		};
+
+		let mut result = Self {
+			negative: self.negative,
+			digits,
+			exponent,
+		};
+
+		let odd = result.digits.last().is_some_and(|d| d % 2 == 1);
+		if rounding.rounds_away_from_zero(result.negative, odd, half) {
+			result.digits = add_magnitudes(&result.digits, &[1])
+		}
+
+		result.normalize();
+		result
+	}
+
+	/// Computes the remainder of the truncated division of `self` by
+	/// `other`, which has the sign of `self`.
+	///
+	/// # Panics
+	///
+	/// Panics if `other` is zero, or if an operand is saturated.
+	pub fn rem(&self, other: &Self) -> Self {
+		assert!(!other.is_zero(), "division by zero");
+		self.assert_exact(other);
+
+		if self.cmp_abs(other) == Ordering::Less {
+			return self.clone();
+		}
+
+		let exponent = self.exponent.min(other.exponent);
+		let (_, remainder) = div_magnitudes(
+			&self.aligned_digits(exponent),
+			&other.aligned_digits(exponent),
+		);
+
+		let mut result = Self {
+			negative: self.negative,
+			digits: remainder,
+			exponent,
+		};
+
+		result.normalize();
+		result
+	}
+
+	/// Returns the value of this decimal as an integer, if it is an integer
+	/// fitting in an `i128`.
+	pub fn to_i128(&self) -> Option<i128> {
+		let magnitude = self.to_u128()?;
+		if self.negative {
+			0i128.checked_sub_unsigned(magnitude)
+		} else {
+			i128::try_from(magnitude).ok()
+		}
+	}
+
+	/// Returns the absolute value of this decimal as an integer, if it is an
+	/// integer fitting in an `u128`.
+	pub fn to_u128(&self) -> Option<u128> {
+		if self.exponent < 0 {
+			return None;
+		}
+
+		let mut result: u128 = 0;
+		for &d in &self.digits {
+			result = result.checked_mul(10)?.checked_add(d as u128)?;
+		}
+
+		for _ in 0..self.exponent {
+			if result == 0 {
+				break;
+			}
+
+			result = result.checked_mul(10)?
+		}
+
+		Some(result)
+	}
+}
+
+/// Returns the digits of the integer `exponent + shift`, preceded by `-` if
+/// it is negative.
+fn shifted_exponent(exponent: &Decimal, shift: i64) -> Vec<u8> {
+	exponent.add(&Decimal::from_i128(shift as i128)).to_fixed(0)
+}
+
+/// Adds two digit sequences (most significant first).
+fn add_magnitudes(a: &[u8], b: &[u8]) -> Vec<u8> {
+	let len = a.len().max(b.len());
+	let mut result = Vec::with_capacity(len + 1);
+	let mut carry = 0;
+
+	for i in 0..len {
+		let da = if i < a.len() { a[a.len() - 1 - i] } else { 0 };
+		let db = if i < b.len() { b[b.len() - 1 - i] } else { 0 };
+		let v = da + db + carry;
+		result.push(v % 10);
+		carry = v / 10
+	}
+
+	if carry > 0 {
+		result.push(carry)
+	}
+
+	result.reverse();
+	result
+}
+
+/// Subtracts `b` from `a` (most significant first), where `a >= b`.
+fn sub_magnitudes(a: &[u8], b: &[u8]) -> Vec<u8> {
+	let mut result = Vec::with_capacity(a.len());
+	let mut borrow = 0;
+
+	for i in 0..a.len() {
+		let da = a[a.len() - 1 - i] as i8;
+		let db = if i < b.len() {
+			b[b.len() - 1 - i] as i8
+		} else {
+			0
+		};
+		let mut v = da - db - borrow;
+		if v < 0 {
+			v += 10;
+			borrow = 1
+		} else {
+			borrow = 0
+		}
+
+		result.push(v as u8)
+	}
+
+	result.reverse();
+	result
+}
+
+/// Removes the leading zeros of a digit sequence (most significant first).
+fn strip_leading_zeros(digits: &mut Vec<u8>) {
+	let leading = digits.iter().take_while(|d| **d == 0).count();
+	digits.drain(..leading);
+}
+
+/// Compares two digit sequences (most significant first) without leading
+/// zeros.
+fn cmp_magnitudes(a: &[u8], b: &[u8]) -> Ordering {
+	a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+/// Divides `n` by `d` (most significant first, without leading zeros).
+///
+/// Returns the quotient and remainder, both without leading zeros.
+fn div_magnitudes(n: &[u8], d: &[u8]) -> (Vec<u8>, Vec<u8>) {
+	let mut quotient = Vec::with_capacity(n.len());
+	let mut remainder = Vec::with_capacity(d.len() + 1);
+
+	for &digit in n {
+		remainder.push(digit);
+		strip_leading_zeros(&mut remainder);
+
+		let mut q = 0;
+		while cmp_magnitudes(&remainder, d) != Ordering::Less {
+			remainder = sub_magnitudes(&remainder, d);
+			strip_leading_zeros(&mut remainder);
+			q += 1
+		}
+
+		quotient.push(q)
+	}
+
+	strip_leading_zeros(&mut quotient);
+	(quotient, remainder)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn decompose(s: &str) -> (bool, Vec<u8>, i64) {
+		let d = Decimal::new(Number::new(s).unwrap());
+		(d.negative, d.digits, d.exponent)
+	}
+
+	#[test]
+	fn decomposition() {
+		assert_eq!(decompose("0"), (false, vec![], 0));
+		assert_eq!(decompose("-0.000e12"), (false, vec![], 0));
+		assert_eq!(decompose("123"), (false, vec![1, 2, 3], 0));
+		assert_eq!(decompose("-1200"), (true, vec![1, 2], 2));
+		assert_eq!(decompose("0.0120"), (false, vec![1, 2], -3));
+		assert_eq!(decompose("1.5E+3"), (false, vec![1, 5], 2));
+		assert_eq!(decompose("25e-4"), (false, vec![2, 5], -4));
+	}
+
+	#[test]
+	fn formatting() {
+		fn format(s: &str) -> String {
+			let n: NumberBuf = Decimal::new(Number::new(s).unwrap()).into_number_buf();
+			n.as_str().to_owned()
+		}
+
+		assert_eq!(format("-0.0"), "0");
+		assert_eq!(format("1.50e2"), "150");
+		assert_eq!(format("1e20"), "100000000000000000000");
+		assert_eq!(format("1e21"), "1e21");
+		assert_eq!(format("-12.5e-1"), "-1.25");
+		assert_eq!(format("1.5e-6"), "0.0000015");
+		assert_eq!(format("1.5e-7"), "1.5e-7");
+		assert_eq!(format("123e-30"), "1.23e-28");
+	}
+
+	#[test]
+	fn fixed_formatting() {
+		fn fixed(s: &str, fraction_digits: usize) -> String {
+			String::from_utf8(decimal(s).to_fixed(fraction_digits)).unwrap()
+		}
+
+		assert_eq!(fixed("0", 0), "0");
+		assert_eq!(fixed("0", 2), "0.00");
+		assert_eq!(fixed("1.5", 2), "1.50");
+		assert_eq!(fixed("-12e1", 0), "-120");
+		assert_eq!(fixed("-0.05", 3), "-0.050");
+		assert_eq!(fixed("123.456", 3), "123.456");
+	}
+
+	fn decimal(s: &str) -> Decimal {
+		Decimal::new(Number::new(s).unwrap())
+	}
+
+	#[test]
+	fn from_f64() {
+		fn exact(f: f64) -> String {
+			let n: NumberBuf = Decimal::from_f64(f).into_number_buf();
+			n.as_str().to_owned()
+		}
+
+		assert_eq!(exact(0.0), "0");
+		assert_eq!(exact(-0.0), "0");
+		assert_eq!(exact(-1.5), "-1.5");
+		assert_eq!(exact(1e21), "1e21");
+		assert_eq!(
+			exact(0.1),
+			"0.1000000000000000055511151231257827021181583404541015625"
+		);
+		assert_eq!(exact(2f64.powi(70)), "1.180591620717411303424e21");
+		assert_eq!(Decimal::from_f64(f64::MIN_POSITIVE / 4.0).point(), -308);
+		assert_eq!(Decimal::from_f64(f64::MAX).point(), 309)
+	}
+
+	#[test]
+	fn comparison() {
+		assert_eq!(decimal("1").cmp_abs(&decimal("0.1e1")), Ordering::Equal);
+		assert_eq!(decimal("-2").cmp_abs(&decimal("1.5")), Ordering::Greater);
+		assert_eq!(decimal("0.99").cmp_abs(&decimal("1")), Ordering::Less);
+		assert_eq!(decimal("0").cmp_abs(&decimal("1e-99")), Ordering::Less);
+		assert_eq!(decimal("123").cmp_abs(&decimal("12.4e1")), Ordering::Less);
+		assert_eq!(decimal("-2").cmp_signed(&decimal("1.5")), Ordering::Less);
+		assert_eq!(decimal("-2").cmp_signed(&decimal("-1.5")), Ordering::Less);
+		assert_eq!(decimal("-0").cmp_signed(&decimal("0e5")), Ordering::Equal);
+		assert_eq!(
+			decimal("0").cmp_signed(&decimal("-1e-99")),
+			Ordering::Greater
+		);
+	}
+
+	#[test]
+	fn saturation() {
+		assert_eq!(
+			decompose("1e4611686018427387904"),
+			(false, vec![1], 1 << 62)
+		);
+		assert_eq!(
+			decompose("1e4611686018427387905"),
+			(false, vec![1], i64::MAX)
+		);
+		assert_eq!(
+			decompose("-1.5e-99999999999999999999"),
+			(true, vec![1, 5], i64::MIN)
+		);
+
+		let cmp = |a: &str, b: &str| decimal(a).partial_cmp_signed(&decimal(b));
+		assert_eq!(cmp("1e9223372036854775807", "1e9223372036854775808"), None);
+		assert_eq!(
+			cmp("-1e-99999999999999999999", "-2e-99999999999999999999"),
+			None
+		);
+		assert_eq!(
+			cmp("1e99999999999999999999", "1e100"),
+			Some(Ordering::Greater)
+		);
+		assert_eq!(
+			cmp("-1e99999999999999999999", "1e99999999999999999999"),
+			Some(Ordering::Less)
+		);
+		assert_eq!(
+			cmp("1e-99999999999999999999", "1e-4611686018427387904"),
+			Some(Ordering::Less)
+		);
+		assert_eq!(
+			cmp("1234e-4611686018427387907", "1e-4611686018427387904"),
+			None
+		);
+		assert_eq!(
+			cmp("1e-99999999999999999999", "-0"),
+			Some(Ordering::Greater)
+		)
+	}
+
+	#[test]
+	#[should_panic(expected = "exponent out of range")]
+	fn saturated_arithmetic() {
+		decimal("1e99999999999999999999").add(&decimal("1"));
+	}
+
+	#[test]
+	#[should_panic(expected = "exponent gap too large")]
+	fn exponent_gap() {
+		decimal("1e4611686018427387904").add(&decimal("1e-4611686018427387904"));
+	}
+}
@@ -0,0 +1,74 @@
+use crate::{Buffer, Number, NumberBuf, TryFromFloatError};
+use alloc::string::ToString;
+use dec::{Context, Decimal128};
+
+impl Number {
+	/// Returns the nearest IEEE 754 decimal128 value, rounding half to even
+	/// to 34 significant digits.
+	///
+	/// Numbers too large to be represented produce an infinity.
+	pub fn as_d128_lossy(&self) -> Decimal128 {
+		let mut context = Context::<Decimal128>::default();
+		context.parse(self.as_str()).unwrap()
+	}
+
+	/// Returns the number as an IEEE 754 decimal128 value only if the
+	/// operation does not induce imprecisions/approximations.
+	pub fn as_d128_lossless(&self) -> Option<Decimal128> {
+		let mut context = Context::<Decimal128>::default();
+		let d = context.parse(self.as_str()).unwrap();
+		if context.status().inexact() || !d.is_finite() {
+			None
+		} else {
+			Some(d)
+		}
+	}
+}
+
+impl<B: Buffer> TryFrom<Decimal128> for NumberBuf<B> {
+	type Error = TryFromFloatError;
+
+	fn try_from(d: Decimal128) -> Result<Self, Self::Error> {
+		if d.is_finite() {
+			Ok(unsafe { Self::new_unchecked(B::from_vec(d.to_string().into_bytes())) })
+		} else if d.is_nan() {
+			Err(TryFromFloatError::Nan)
+		} else {
+			Err(TryFromFloatError::Infinite)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Number, NumberBuf};
+	use dec::Decimal128;
+
+	#[test]
+	fn number_to_d128() {
+		let n = Number::new("1.10").unwrap();
+		assert_eq!(n.as_d128_lossy().to_string(), "1.10");
+		assert_eq!(n.as_d128_lossless().unwrap().to_string(), "1.10");
+
+		let n = Number::new("1.2345678901234567890123456789012345").unwrap();
+		assert_eq!(
+			n.as_d128_lossy().to_string(),
+			"1.234567890123456789012345678901234"
+		);
+		assert!(n.as_d128_lossless().is_none());
+
+		let n = Number::new("1e9999").unwrap();
+		assert!(n.as_d128_lossy().is_infinite());
+		assert!(n.as_d128_lossless().is_none());
+	}
+
+	#[test]
+	fn d128_to_number() {
+		let d: Decimal128 = "-12.5E+10".parse().unwrap();
+		let n: NumberBuf = d.try_into().unwrap();
+		assert_eq!(n.as_str(), "-1.25E+11");
+		assert!(NumberBuf::<Vec<u8>>::try_from(Decimal128::NAN).is_err());
+		let infinity: Decimal128 = "Infinity".parse().unwrap();
+		assert!(NumberBuf::<Vec<u8>>::try_from(infinity).is_err());
+	}
+}
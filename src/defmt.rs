@@ -0,0 +1,33 @@
+//! `defmt` logging support.
+use crate::{Buffer, Number, NumberBuf};
+use defmt::{Format, Formatter};
+
+impl Format for Number {
+	/// Logs the number as written, without allocating.
+	#[inline(always)]
+	fn format(&self, f: Formatter) {
+		defmt::write!(f, "{=str}", self.as_str())
+	}
+}
+
+impl<B: Buffer> Format for NumberBuf<B> {
+	#[inline(always)]
+	fn format(&self, f: Formatter) {
+		self.as_number().format(f)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Number, NumberBuf};
+	use defmt::Format;
+
+	fn assert_format<T: Format + ?Sized>() {}
+
+	#[test]
+	fn implemented() {
+		assert_format::<Number>();
+		assert_format::<NumberBuf>();
+		assert_format::<NumberBuf<std::borrow::Cow<[u8]>>>()
+	}
+}
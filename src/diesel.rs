@@ -0,0 +1,202 @@
+//! Postgres `NUMERIC` support for `diesel`.
+use crate::{
+	pg_numeric::{PgNumeric, PgNumericError},
+	Buffer, Number, NumberBuf,
+};
+use diesel::{
+	backend::Backend,
+	deserialize::{self, FromSql, Queryable},
+	expression::AsExpression,
+	internal::derives::as_expression::Bound,
+	pg::{data_types, Pg, PgValue},
+	serialize::{self, Output, ToSql},
+	sql_types::{Nullable, Numeric, SingleValue},
+};
+
+impl From<PgNumeric> for data_types::PgNumeric {
+	fn from(n: PgNumeric) -> Self {
+		if n.negative {
+			Self::Negative {
+				weight: n.weight,
+				scale: n.scale,
+				digits: n.digits,
+			}
+		} else {
+			Self::Positive {
+				weight: n.weight,
+				scale: n.scale,
+				digits: n.digits,
+			}
+		}
+	}
+}
+
+impl TryFrom<data_types::PgNumeric> for PgNumeric {
+	type Error = PgNumericError;
+
+	fn try_from(n: data_types::PgNumeric) -> Result<Self, PgNumericError> {
+		match n {
+			data_types::PgNumeric::Positive {
+				weight,
+				scale,
+				digits,
+			} => Ok(Self {
+				negative: false,
+				weight,
+				scale,
+				digits,
+			}),
+			data_types::PgNumeric::Negative {
+				weight,
+				scale,
+				digits,
+			} => Ok(Self {
+				negative: true,
+				weight,
+				scale,
+				digits,
+			}),
+			data_types::PgNumeric::NaN => Err(PgNumericError::NonFinite),
+		}
+	}
+}
+
+impl ToSql<Numeric, Pg> for Number {
+	/// Writes the number in the binary `NUMERIC` format, preserving its
+	/// digits and scale.
+	fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+		let numeric: data_types::PgNumeric = PgNumeric::new(self)?.into();
+		ToSql::<Numeric, Pg>::to_sql(&numeric, &mut out.reborrow())
+	}
+}
+
+impl<B: Buffer> ToSql<Numeric, Pg> for NumberBuf<B> {
+	#[inline(always)]
+	fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+		ToSql::<Numeric, Pg>::to_sql(self.as_number(), out)
+	}
+}
+
+impl<DB: Backend> ToSql<Nullable<Numeric>, DB> for Number
+where
+	Self: ToSql<Numeric, DB>,
+{
+	#[inline(always)]
+	fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+		ToSql::<Numeric, DB>::to_sql(self, out)
+	}
+}
+
+impl<B, DB: Backend> ToSql<Nullable<Numeric>, DB> for NumberBuf<B>
+where
+	Self: ToSql<Numeric, DB>,
+{
+	#[inline(always)]
+	fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+		ToSql::<Numeric, DB>::to_sql(self, out)
+	}
+}
+
+impl<B: Buffer> FromSql<Numeric, Pg> for NumberBuf<B> {
+	/// Reads a `NUMERIC` value, in plain decimal notation with as many
+	/// digits after the decimal point as its scale.
+	///
+	/// Fails on `NaN` and infinite values.
+	fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+		let numeric = data_types::PgNumeric::from_sql(value)?;
+		Ok(PgNumeric::try_from(numeric)?.to_number_buf()?)
+	}
+}
+
+impl<B, ST: SingleValue, DB: Backend> Queryable<ST, DB> for NumberBuf<B>
+where
+	Self: FromSql<ST, DB>,
+{
+	type Row = Self;
+
+	#[inline(always)]
+	fn build(row: Self) -> deserialize::Result<Self> {
+		Ok(row)
+	}
+}
+
+/// Implements `AsExpression` for `Numeric` and `Nullable<Numeric>`.
+macro_rules! as_expression {
+	($([$($params:tt)*] $ty:ty),*) => {
+		$(
+			impl<$($params)*> AsExpression<Numeric> for $ty {
+				type Expression = Bound<Numeric, Self>;
+
+				#[inline(always)]
+				fn as_expression(self) -> Self::Expression {
+					Bound::new(self)
+				}
+			}
+
+			impl<$($params)*> AsExpression<Nullable<Numeric>> for $ty {
+				type Expression = Bound<Nullable<Numeric>, Self>;
+
+				#[inline(always)]
+				fn as_expression(self) -> Self::Expression {
+					Bound::new(self)
+				}
+			}
+		)*
+	};
+}
+
+as_expression! {
+	['a] &'a Number,
+	['a, 'b] &'b &'a Number,
+	[B] NumberBuf<B>,
+	['a, B] &'a NumberBuf<B>,
+	['a, 'b, B] &'b &'a NumberBuf<B>
+}
+
+#[cfg(test)]
+mod tests {
+	use super::PgNumeric;
+	use crate::{Number, NumberBuf};
+	use diesel::pg::data_types;
+
+	fn round_trip(s: &str) -> NumberBuf {
+		let numeric: data_types::PgNumeric =
+			PgNumeric::new(Number::new(s).unwrap()).unwrap().into();
+		PgNumeric::try_from(numeric)
+			.unwrap()
+			.to_number_buf()
+			.unwrap()
+	}
+
+	#[test]
+	fn round_trips() {
+		for (input, expected) in [
+			("0", "0"),
+			("-12.50", "-12.50"),
+			("123456789.000001", "123456789.000001"),
+			("1.5e3", "1500"),
+			("1.5e-3", "0.0015"),
+		] {
+			assert_eq!(round_trip(input).as_str(), expected)
+		}
+	}
+
+	#[test]
+	fn sign() {
+		let numeric: data_types::PgNumeric =
+			PgNumeric::new(Number::new("-1").unwrap()).unwrap().into();
+		assert_eq!(
+			numeric,
+			data_types::PgNumeric::Negative {
+				weight: 0,
+				scale: 0,
+				digits: vec![1]
+			}
+		)
+	}
+
+	#[test]
+	fn nan() {
+		assert!(PgNumeric::try_from(data_types::PgNumeric::NaN).is_err())
+	}
+}
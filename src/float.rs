@@ -0,0 +1,193 @@
+//! Float parsing and formatting backend.
+//!
+//! By default, floats are parsed and formatted using the
+//! [`lexical`](https://crates.io/crates/lexical) crate. When the `std-float`
+//! feature is enabled, floats are parsed using the standard library, and
+//! formatted using [`ryu`](https://crates.io/crates/ryu) with the same
+//! notation as `lexical`.
+//!
+//! Both backends produce digits that round-trip to the original float, but
+//! in rare cases (ties between two shortest representations) they may pick
+//! a different last digit.
+
+#[cfg(not(any(feature = "lexical", feature = "std-float")))]
+compile_error!("either the `lexical` or `std-float` feature must be enabled");
+
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std-float"))]
+mod backend {
+	use alloc::vec::Vec;
+
+	const LOSSY_PARSE_FLOAT: lexical::ParseFloatOptions = lexical::ParseFloatOptions::builder()
+		.lossy(true)
+		.build_unchecked();
+
+	const WRITE_FLOAT: lexical::WriteFloatOptions = lexical::WriteFloatOptions::builder()
+		.trim_floats(true)
+		.exponent(b'e')
+		.build_unchecked();
+
+	macro_rules! float_functions {
+		($($ty:ident: $parse:ident, $format:ident),*) => {
+			$(
+				#[inline(always)]
+				pub fn $parse(bytes: &[u8]) -> $ty {
+					lexical::parse_with_options::<_, _, { lexical::format::JSON }>(
+						bytes,
+						&LOSSY_PARSE_FLOAT,
+					)
+					.unwrap()
+				}
+
+				#[inline(always)]
+				pub fn $format(f: $ty) -> Vec<u8> {
+					lexical::to_string_with_options::<_, { lexical::format::JSON }>(f, &WRITE_FLOAT)
+						.into_bytes()
+				}
+			)*
+		};
+	}
+
+	float_functions!(f32: parse_f32, format_f32, f64: parse_f64, format_f64);
+}
+
+#[cfg(feature = "std-float")]
+mod backend {
+	use crate::{decimal::Decimal, Number};
+	use alloc::{string::ToString, vec::Vec};
+
+	macro_rules! float_functions {
+		($($ty:ident: $parse:ident, $format:ident),*) => {
+			$(
+				#[inline(always)]
+				pub fn $parse(bytes: &[u8]) -> $ty {
+					// `bytes` always holds a valid JSON number, which is
+					// accepted by the standard float parser.
+					unsafe { core::str::from_utf8_unchecked(bytes) }
+						.parse()
+						.unwrap()
+				}
+
+				pub fn $format(f: $ty) -> Vec<u8> {
+					if f == 0.0 {
+						return if f.is_sign_negative() {
+							b"-0".to_vec()
+						} else {
+							b"0".to_vec()
+						};
+					}
+
+					let mut buffer = ryu::Buffer::new();
+					let n = unsafe { Number::new_unchecked(buffer.format_finite(f)) };
+					format_shortest(Decimal::new(n))
+				}
+			)*
+		};
+	}
+
+	float_functions!(f32: parse_f32, format_f32, f64: parse_f64, format_f64);
+
+	/// Formats the shortest decimal representation of a float using the same
+	/// notation as `lexical`: the plain notation is used when the scientific
+	/// exponent is between `-5` and `9`, and the scientific notation
+	/// otherwise.
+	fn format_shortest(d: Decimal) -> Vec<u8> {
+		let mut bytes = Vec::with_capacity(d.digits.len() + 8);
+		let digits = |digits: &[u8]| digits.iter().map(|d| b'0' + d).collect::<Vec<_>>();
+
+		if d.negative {
+			bytes.push(b'-')
+		}
+
+		let len = d.digits.len() as i64;
+		let scientific_exponent = len - 1 + d.exponent;
+
+		if (-5..=9).contains(&scientific_exponent) {
+			if d.exponent >= 0 {
+				bytes.extend(digits(&d.digits));
+				bytes.resize(bytes.len() + d.exponent as usize, b'0')
+			} else if scientific_exponent >= 0 {
+				let (integer, fraction) = d.digits.split_at((scientific_exponent + 1) as usize);
+				bytes.extend(digits(integer));
+				bytes.push(b'.');
+				bytes.extend(digits(fraction))
+			} else {
+				bytes.extend_from_slice(b"0.");
+				bytes.resize(bytes.len() + (-scientific_exponent - 1) as usize, b'0');
+				bytes.extend(digits(&d.digits))
+			}
+		} else {
+			bytes.push(b'0' + d.digits[0]);
+			if d.digits.len() > 1 {
+				bytes.push(b'.');
+				bytes.extend(digits(&d.digits[1..]))
+			}
+
+			bytes.push(b'e');
+			bytes.extend_from_slice(scientific_exponent.to_string().as_bytes())
+		}
+
+		bytes
+	}
+}
+
+pub(crate) use backend::*;
+
+/// Formats the given integer.
+#[cfg(not(feature = "std-float"))]
+#[inline(always)]
+pub(crate) fn format_int<I: lexical::ToLexical>(i: I) -> Vec<u8> {
+	lexical::to_string(i).into_bytes()
+}
+
+/// Formats the given integer.
+#[cfg(feature = "std-float")]
+#[inline(always)]
+pub(crate) fn format_int<I: alloc::string::ToString>(i: I) -> Vec<u8> {
+	i.to_string().into_bytes()
+}
+
+#[cfg(all(test, feature = "std-float"))]
+mod tests {
+	#[test]
+	fn round_trip() {
+		let mut x: u64 = 0x2545f4914f6cdd1d;
+		for _ in 0..100_000 {
+			// xorshift64
+			x ^= x << 13;
+			x ^= x >> 7;
+			x ^= x << 17;
+
+			let f = f64::from_bits(x);
+			if f.is_finite() {
+				assert_eq!(super::parse_f64(&super::format_f64(f)).to_bits(), x)
+			}
+
+			let f = f32::from_bits(x as u32);
+			if f.is_finite() {
+				assert_eq!(super::parse_f32(&super::format_f32(f)).to_bits(), x as u32)
+			}
+		}
+	}
+
+	#[test]
+	fn notation() {
+		for (f, expected) in [
+			(0.0, "0"),
+			(-0.0, "-0"),
+			(1.0, "1"),
+			(-1.5, "-1.5"),
+			(1e9, "1000000000"),
+			(1234567890.5, "1234567890.5"),
+			(1e10, "1e10"),
+			(1.25e15, "1.25e15"),
+			(1.5e-5, "0.000015"),
+			(1e-6, "1e-6"),
+			(2.8e-10, "2.8e-10"),
+			(f64::MAX, "1.7976931348623157e308"),
+		] {
+			assert_eq!(super::format_f64(f), expected.as_bytes())
+		}
+	}
+}
@@ -0,0 +1,105 @@
+//! Integer numbers.
+use crate::{decimal::Decimal, Buffer, Number, NumberBuf};
+
+number_newtype! {
+	/// Integer JSON number, without fraction nor exponent part.
+	///
+	/// Integers can be converted into primitive integers with
+	/// [`Number::as_i64`], [`Number::as_i128`], etc., which only fail if the
+	/// value is out of range.
+	IntNumber,
+
+	/// Integer JSON number buffer, without fraction nor exponent part.
+	IntNumberBuf,
+	as_int_number,
+	|n: &Number| !n.has_decimal_point() && !n.has_exponent(),
+	"an integer without fraction nor exponent"
+}
+
+impl<B: Buffer> IntNumberBuf<B> {
+	/// Converts the given number into an integer, if its value is an
+	/// integer.
+	///
+	/// Contrarily to [`IntNumberBuf::new`], fraction and exponent parts are
+	/// accepted as long as the value is an integer: `5.0` and `5e2` are
+	/// converted into `5` and `500`.
+	///
+	/// Beware that small inputs such as `1e1000000000` produce enormous
+	/// outputs.
+	pub fn from_integral(n: &Number) -> Option<Self> {
+		if !n.has_decimal_point() && !n.has_exponent() {
+			return Some(unsafe {
+				Self::new_unchecked(NumberBuf::new_unchecked(B::from_bytes(n.as_bytes())))
+			});
+		}
+
+		let d = Decimal::new(n);
+		if d.exponent < 0 {
+			return None;
+		}
+
+		Some(unsafe { Self::new_unchecked(NumberBuf::new_unchecked(B::from_vec(d.to_fixed(0)))) })
+	}
+}
+
+macro_rules! impl_from_int {
+	($($ty:ty),*) => {
+		$(
+			impl<B: Buffer> From<$ty> for IntNumberBuf<B> {
+				#[inline(always)]
+				fn from(i: $ty) -> Self {
+					unsafe { Self::new_unchecked(NumberBuf::from(i)) }
+				}
+			}
+		)*
+	};
+}
+
+impl_from_int!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize);
+
+#[cfg(test)]
+mod tests {
+	use super::{IntNumber, IntNumberBuf};
+	use crate::{Number, NumberBuf};
+
+	#[test]
+	fn new() {
+		assert!(IntNumber::new(Number::new("-12").unwrap()).is_some());
+		assert!(IntNumber::new(Number::new("-0").unwrap()).is_some());
+		assert!(IntNumber::new(Number::new("5.0").unwrap()).is_none());
+		assert!(IntNumber::new(Number::new("5e2").unwrap()).is_none());
+
+		let n: NumberBuf = "1.5".parse().unwrap();
+		assert_eq!(IntNumberBuf::new(n.clone()), Err(n));
+		assert_eq!(
+			IntNumberBuf::<Vec<u8>>::from(-42i64)
+				.as_int_number()
+				.as_i64(),
+			Some(-42)
+		)
+	}
+
+	#[test]
+	fn from_integral() {
+		fn integral(s: &str) -> Option<String> {
+			IntNumberBuf::<Vec<u8>>::from_integral(Number::new(s).unwrap())
+				.map(|n| n.as_str().to_owned())
+		}
+
+		assert_eq!(integral("12").as_deref(), Some("12"));
+		assert_eq!(integral("5.0").as_deref(), Some("5"));
+		assert_eq!(integral("5e2").as_deref(), Some("500"));
+		assert_eq!(integral("-1.25E+2").as_deref(), Some("-125"));
+		assert_eq!(integral("-0.0").as_deref(), Some("0"));
+		assert_eq!(integral("1.5"), None);
+		assert_eq!(integral("5e-1"), None)
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde() {
+		let n: IntNumberBuf = serde_json::from_str("123456789012345678901234567890").unwrap();
+		assert_eq!(n.as_i128(), Some(123456789012345678901234567890));
+		assert!(serde_json::from_str::<IntNumberBuf>("1.0").is_err())
+	}
+}
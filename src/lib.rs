@@ -4,22 +4,189 @@
 //! and the `NumberBuf<B>` type owning the data inside the `B` type
 //! (by default `Vec<u8>`).
 //!
+//! # Arithmetic
+//!
+//! The `Add`, `Sub`, `Mul` and `Rem` operators are implemented for `&Number` and
+//! `NumberBuf`, performing exact arbitrary-precision decimal arithmetic.
+//! The result is a new `NumberBuf` written in plain decimal notation when
+//! the decimal point position is between `-6` and `21`, and in scientific
+//! notation otherwise. The `Sum` and `Product` traits are also implemented
+//! for `NumberBuf`, so iterators of numbers can be summed exactly.
+//!
+//! Since the quotient of two numbers may have no finite decimal expansion,
+//! division is provided by the [`Number::div_with`] method, rounding the
+//! result according to the given [`DivOptions`].
+//!
+//! The exact result of an operation can be arbitrarily large (think of
+//! `1e1000000000 + 1`, with a billion digits), and the memory it requires is
+//! allocated accordingly. Additions, subtractions and remainders whose
+//! operands are more than `u32::MAX` digits apart panic. When working with
+//! untrusted numbers, use the checked variants such as
+//! [`Number::checked_add`] instead, to bound the resources spent on the
+//! operation with [`ArithmeticLimits`].
+//!
+//! Exponents are handled up to [`Number::MAX_EXACT_EXPONENT`] (`2^62`) in
+//! absolute value. Arithmetic operations panic on numbers with larger
+//! exponents (checked variants return an error), and comparisons only
+//! order them when the result does not depend on the exact exponent.
+//!
+//! Numbers can be rounded to a given number of decimal places or
+//! significant digits with [`Number::round_to_places`] and
+//! [`Number::round_to_significant`], using a [`RoundingMode`].
+//!
+//! # Incremental parsing
+//!
+//! Numbers split across multiple input buffers can be validated
+//! byte by byte using a [`NumberParser`], and [`Number::parse_prefix`]
+//! extracts the number at the start of a larger input.
+//!
+//! # Relaxed parsing
+//!
+//! The [`Number::new_relaxed`] function accepts
+//! [JSON5 numbers](https://spec.json5.org/#numbers), such as `+1`, `.5` or
+//! `0x1F`, and normalizes them into JSON numbers. The
+//! [`Number::new_lenient`] function only tolerates leading zeros and an
+//! explicit `+` sign, which are commonly emitted by non-conforming producers.
+//!
 //! # Features
 //!
+//! ## `no_std` support
+//!
+//! This crate only requires the `alloc` crate. The default `std` feature
+//! adds the functions reading from and writing to `std::io` sources and
+//! sinks. Disable the default features (and enable a float conversion
+//! backend) to use this crate in `no_std` environments.
+//!
 //! ## Store small owned numbers on the stack
 //!
 //! By enabling the `smallnumberbuf` feature, the `SmallNumberBuf<LEN>` type is
 //! defined as `NumberBuf<SmallVec<[u8; LEN]>>` (where `LEN=8` by default)
 //! thanks to the [`smallvec`](https://crates.io/crates/smallvec) crate.
 //!
+//! ## Fixed capacity buffers
+//!
+//! By enabling the `heapless` feature, the `HeaplessNumberBuf<N>` type is
+//! defined as `NumberBuf<heapless::Vec<u8, N>>` thanks to the
+//! [`heapless`](https://crates.io/crates/heapless) crate, for targets without
+//! a heap allocator.
+//!
+//! ## Zero-copy shared buffers
+//!
+//! By enabling the `bytes` feature, [`Buffer`] is implemented for the
+//! reference-counted `Bytes` type of the [`bytes`](https://crates.io/crates/bytes)
+//! crate, allowing numbers to share the memory of the payload they were
+//! parsed from.
+//!
 //! ## Serde support
 //!
 //! Enable the `serde` feature to add `Serialize`, `Deserialize` and
 //! `Deserializer` implementations to `NumberBuf`.
-use std::borrow::{Borrow, ToOwned};
-use std::fmt;
-use std::ops::Deref;
-use std::str::FromStr;
+//!
+//! The `arbitrary-precision` feature enables the `serde_json` feature along
+//! with the `arbitrary_precision` feature of `serde_json`, so that
+//! `serde_json::Number` keeps the exact digits of the numbers.
+//!
+//! `&Number` and `CowNumber` can also be deserialized without copying when
+//! the deserializer lends the lexical representation of the number, as
+//! non human-readable formats do.
+//!
+//! ## JSON Schema
+//!
+//! Enable the `schemars` feature to implement
+//! [`schemars::JsonSchema`](https://docs.rs/schemars) for `Number`,
+//! `NumberBuf` and the serde wrappers, so they can be used directly in types
+//! deriving `JsonSchema`.
+//!
+//! Similarly, the `utoipa` feature implements
+//! [`utoipa::ToSchema`](https://docs.rs/utoipa) for `Number` and
+//! `NumberBuf`, to document OpenAPI request and response models.
+//!
+//! ## Fuzzing and property testing
+//!
+//! Enable the `arbitrary` feature to implement
+//! [`arbitrary::Arbitrary`](https://docs.rs/arbitrary) for `NumberBuf`,
+//! generating structurally valid numbers from fuzzer input.
+//!
+//! The `proptest` feature provides [`proptest`](https://docs.rs/proptest)
+//! strategies generating numbers that shrink toward minimal lexical forms.
+//! Similarly, the `quickcheck` feature implements
+//! [`quickcheck::Arbitrary`](https://docs.rs/quickcheck) for `NumberBuf`.
+//!
+//! ## Random numbers
+//!
+//! Enable the `rand` feature to generate random numbers with the
+//! [`rand`](https://docs.rs/rand) crate, either uniformly between two
+//! bounds with `NumberBuf::random_in`, or with a chosen shape (digit count,
+//! fraction length, exponent range) with `rand::NumberDistribution`.
+//!
+//! ## SIMD validation
+//!
+//! Enable the `simd` feature to validate long runs of digits 16 bytes at a
+//! time using SSE2 instructions on `x86_64` targets. On other targets, this
+//! feature has no effect.
+//!
+//! ## Parallel validation
+//!
+//! Enable the `rayon` feature to validate large batches of numbers in
+//! parallel with `Number::par_validate_batch` and
+//! `Number::par_validate_each`, thanks to the
+//! [`rayon`](https://crates.io/crates/rayon) crate.
+//!
+//! ## Float conversion backend
+//!
+//! Floats are parsed and formatted using the
+//! [`lexical`](https://crates.io/crates/lexical) crate, enabled by the default
+//! `lexical` feature. Enabling the `std-float` feature instead uses the
+//! standard library float parser and the [`ryu`](https://crates.io/crates/ryu)
+//! crate to format floats. The `lexical` dependency can then be dropped by
+//! disabling the default features.
+//!
+//! ## Interoperability
+//!
+//! The following features provide conversions from and to the number types
+//! of other crates:
+//!
+//! - `rust_decimal`: [`rust_decimal::Decimal`](https://docs.rs/rust_decimal).
+//! - `ratio`: exact rational value as a
+//!   [`num_rational::BigRational`](https://docs.rs/num-rational).
+//! - `malachite`: [`malachite`](https://docs.rs/malachite) `Natural`,
+//!   `Integer` and `Rational`.
+//! - `decimal128`: IEEE 754 decimal128 values, as implemented by the
+//!   [`dec`](https://docs.rs/dec) crate's `Decimal128`.
+//! - `simd-json`: [`simd_json`](https://docs.rs/simd-json) static nodes,
+//!   values and tape nodes.
+//! - `toml`: [`toml::Value`](https://docs.rs/toml) integers and floats.
+//! - `ciborium`: [`ciborium::Value`](https://docs.rs/ciborium) integers,
+//!   floats, bignums and decimal fractions.
+//! - `rmp`: MessagePack extension type, as understood by
+//!   [`rmp-serde`](https://docs.rs/rmp-serde).
+//! - `wasm`: JavaScript `Number`, `BigInt` and `JsValue`, through
+//!   [`js-sys`](https://docs.rs/js-sys) and
+//!   [`wasm-bindgen`](https://docs.rs/wasm-bindgen).
+//! - `pyo3`: Python `int` and `decimal.Decimal`, through
+//!   [`pyo3`](https://docs.rs/pyo3).
+//! - `sqlx-postgres`: Postgres `NUMERIC` values, encoded and decoded by
+//!   [`sqlx`](https://docs.rs/sqlx) in their binary format.
+//! - `diesel`: [`diesel`](https://docs.rs/diesel) `Numeric` values on
+//!   Postgres, stored losslessly in the binary `NUMERIC` format.
+//! - `arrow`: [`arrow`](https://docs.rs/arrow) `Decimal128Array` and
+//!   `Decimal256Array` columns, as found in Parquet files.
+//! - `async-graphql`: a `Number` GraphQL scalar for
+//!   [`async-graphql`](https://docs.rs/async-graphql). Enables `std`.
+//! - `defmt`: logging of `Number` and `NumberBuf` through
+//!   [`defmt`](https://docs.rs/defmt), without allocation.
+//! - `rkyv`: zero-copy [`rkyv`](https://docs.rs/rkyv) archives of
+//!   `NumberBuf`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+extern crate alloc;
+
+use alloc::borrow::{Borrow, ToOwned};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt;
+use core::ops::Deref;
+use core::str::FromStr;
+
+use parser::State;
 
 /// `serde` support.
 #[cfg(feature = "serde")]
@@ -29,6 +196,134 @@ pub mod serde;
 #[cfg(feature = "serde_json")]
 pub mod serde_json;
 
+/// `simd-json` support.
+#[cfg(feature = "simd-json")]
+pub mod simd_json;
+
+/// `toml` support.
+#[cfg(feature = "toml")]
+pub mod toml;
+
+/// `ciborium` (CBOR) support.
+#[cfg(feature = "ciborium")]
+pub mod ciborium;
+
+/// MessagePack support.
+#[cfg(feature = "rmp")]
+pub mod rmp;
+
+/// `rkyv` support.
+#[cfg(feature = "rkyv")]
+pub mod rkyv;
+
+/// `schemars` support.
+#[cfg(feature = "schemars")]
+mod schemars;
+
+/// `utoipa` support.
+#[cfg(feature = "utoipa")]
+mod utoipa;
+
+/// `arbitrary` support.
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+/// `proptest` strategies.
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
+/// `quickcheck` support.
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+
+/// Random number generation.
+#[cfg(feature = "rand")]
+pub mod rand;
+
+/// JavaScript interoperability.
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// `pyo3` support.
+#[cfg(feature = "pyo3")]
+mod pyo3;
+
+#[cfg(any(feature = "sqlx-postgres", feature = "diesel"))]
+mod pg_numeric;
+
+/// `sqlx` Postgres support.
+#[cfg(feature = "sqlx-postgres")]
+mod sqlx_postgres;
+
+/// `diesel` support.
+#[cfg(feature = "diesel")]
+mod diesel;
+
+/// Arrow decimal columns support.
+#[cfg(feature = "arrow")]
+pub mod arrow;
+
+/// `async-graphql` support.
+#[cfg(feature = "async-graphql")]
+mod async_graphql;
+
+/// `defmt` support.
+#[cfg(feature = "defmt")]
+mod defmt;
+
+/// `rust_decimal` support.
+#[cfg(feature = "rust_decimal")]
+pub mod rust_decimal;
+
+/// `malachite` support.
+#[cfg(feature = "malachite")]
+pub mod malachite;
+
+/// IEEE 754 decimal128 support.
+#[cfg(feature = "decimal128")]
+pub mod decimal128;
+
+#[macro_use]
+mod newtype;
+
+mod batch;
+mod cmp;
+mod cow;
+mod decimal;
+mod float;
+mod int;
+mod limit;
+mod non_zero;
+mod notation;
+mod ops;
+mod packed;
+mod parser;
+mod pointer;
+mod range;
+mod relaxed;
+mod round;
+mod scaled;
+pub mod schema;
+mod small;
+mod time;
+mod unsigned;
+mod xsd;
+
+pub use batch::BatchError;
+pub use cow::CowNumber;
+pub use int::{IntNumber, IntNumberBuf};
+pub use limit::LimitedNumberError;
+pub use non_zero::{NonZeroNumber, NonZeroNumberBuf};
+pub use notation::Grouped;
+pub use ops::{ArithmeticLimits, DivOptions, LimitExceeded};
+pub use packed::PackedNumberError;
+pub use parser::{NumberParser, ParseError, ParseErrorKind, Step};
+pub use relaxed::RelaxedNumberError;
+pub use round::RoundingMode;
+pub use scaled::ScaleError;
+pub use unsigned::{UNumber, UNumberBuf};
+pub use xsd::XsdNumericType;
+
 #[cfg(feature = "smallnumberbuf")]
 mod smallnumberbuf {
 	use super::*;
@@ -45,12 +340,244 @@ mod smallnumberbuf {
 		fn from_bytes(bytes: &[u8]) -> Self {
 			bytes.into()
 		}
+
+		fn into_vec(self) -> Vec<u8> {
+			SmallVec::into_vec(self)
+		}
+	}
+
+	impl<A: smallvec::Array<Item = u8>> From<NumberBuf<SmallVec<A>>> for NumberBuf {
+		#[inline(always)]
+		fn from(n: NumberBuf<SmallVec<A>>) -> Self {
+			n.convert()
+		}
+	}
+
+	impl<A: smallvec::Array<Item = u8>> From<NumberBuf> for NumberBuf<SmallVec<A>> {
+		#[inline(always)]
+		fn from(n: NumberBuf) -> Self {
+			n.convert()
+		}
+	}
+
+	unsafe impl<A: smallvec::Array<Item = u8>> crate::BufferMut for SmallVec<A> {
+		fn push(&mut self, byte: u8) {
+			SmallVec::push(self, byte)
+		}
+
+		fn truncate(&mut self, len: usize) {
+			SmallVec::truncate(self, len)
+		}
+
+		fn as_mut_slice(&mut self) -> &mut [u8] {
+			self
+		}
+
+		fn extend_from_slice(&mut self, bytes: &[u8]) {
+			SmallVec::extend_from_slice(self, bytes)
+		}
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+
+		#[test]
+		fn convert() {
+			let n: SmallNumberBuf<4> = "1.25e-10".parse().unwrap();
+			assert!(n.buffer().spilled());
+			let ptr = n.as_bytes().as_ptr();
+			let n: NumberBuf = n.into();
+			assert_eq!(n.as_bytes().as_ptr(), ptr);
+
+			let n: SmallNumberBuf = n.into();
+			assert!(!n.buffer().spilled());
+			assert_eq!(n.as_str(), "1.25e-10")
+		}
 	}
 }
 
 #[cfg(feature = "smallnumberbuf")]
 pub use smallnumberbuf::*;
 
+#[cfg(feature = "heapless")]
+mod heapless_buffer {
+	use super::*;
+
+	/// JSON number buffer based on [`heapless::Vec`], with a fixed capacity
+	/// of `N` bytes.
+	pub type HeaplessNumberBuf<const N: usize> = NumberBuf<heapless::Vec<u8, N>>;
+
+	/// Error returned when a number does not fit in a fixed capacity buffer.
+	#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+	pub struct CapacityExceeded;
+
+	impl fmt::Display for CapacityExceeded {
+		fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+			f.write_str("number exceeds the buffer capacity")
+		}
+	}
+
+	impl core::error::Error for CapacityExceeded {}
+
+	/// # Panics
+	///
+	/// The infallible constructor functions panic if the number does not fit
+	/// in `N` bytes, meaning that operations producing new numbers panic if
+	/// the result is too long. Parsing with [`FromStr`] returns an error
+	/// instead, and [`NumberBuf::try_from_number`] can be used to copy an
+	/// existing number.
+	unsafe impl<const N: usize> crate::Buffer for heapless::Vec<u8, N> {
+		fn from_vec(bytes: Vec<u8>) -> Self {
+			Self::from_bytes(&bytes)
+		}
+
+		fn from_bytes(bytes: &[u8]) -> Self {
+			heapless::Vec::from_slice(bytes).expect("number exceeds the buffer capacity")
+		}
+
+		fn try_from_bytes(bytes: &[u8]) -> Option<Self> {
+			heapless::Vec::from_slice(bytes).ok()
+		}
+	}
+
+	impl<const N: usize> NumberBuf<heapless::Vec<u8, N>> {
+		/// Creates a number buffer from the given `number`, provided it fits
+		/// in `N` bytes.
+		pub fn try_from_number(n: &Number) -> Result<Self, CapacityExceeded> {
+			heapless::Vec::from_slice(n.as_bytes())
+				.map(|bytes| unsafe { Self::new_unchecked(bytes) })
+				.map_err(|_| CapacityExceeded)
+		}
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+
+		#[test]
+		fn try_from_number() {
+			let n = HeaplessNumberBuf::<4>::try_from_number(Number::new("-1.5").unwrap()).unwrap();
+			assert_eq!(n.as_str(), "-1.5");
+			assert_eq!(
+				HeaplessNumberBuf::<4>::try_from_number(Number::new("-1.25").unwrap()),
+				Err(CapacityExceeded)
+			);
+
+			let n: HeaplessNumberBuf<8> = "12.5".parse().unwrap();
+			assert_eq!((n.clone() + n).as_str(), "25");
+		}
+
+		#[test]
+		fn from_str_capacity() {
+			let n = HeaplessNumberBuf::<8>::from_str("12345678").unwrap();
+			assert_eq!(n.as_str(), "12345678");
+
+			let InvalidNumber(bytes) = HeaplessNumberBuf::<8>::from_str("123456789").unwrap_err();
+			assert!(bytes.is_empty());
+			assert!(HeaplessNumberBuf::<8>::from_str("1.").is_err())
+		}
+	}
+}
+
+#[cfg(feature = "heapless")]
+pub use heapless_buffer::*;
+
+#[cfg(feature = "bytes")]
+mod bytes_buffer {
+	use super::*;
+	use bytes::Bytes;
+
+	unsafe impl crate::Buffer for Bytes {
+		fn from_vec(bytes: Vec<u8>) -> Self {
+			bytes.into()
+		}
+
+		fn from_bytes(bytes: &[u8]) -> Self {
+			Bytes::copy_from_slice(bytes)
+		}
+
+		fn into_vec(self) -> Vec<u8> {
+			self.into()
+		}
+	}
+
+	impl From<NumberBuf<Bytes>> for NumberBuf {
+		#[inline(always)]
+		fn from(n: NumberBuf<Bytes>) -> Self {
+			n.convert()
+		}
+	}
+
+	impl From<NumberBuf> for NumberBuf<Bytes> {
+		#[inline(always)]
+		fn from(n: NumberBuf) -> Self {
+			n.convert()
+		}
+	}
+
+	impl NumberBuf<Bytes> {
+		/// Creates a new number buffer by parsing the given shared `bytes`,
+		/// without copying them.
+		///
+		/// Numbers sliced out of a larger payload with
+		/// [`Bytes::slice`] keep sharing the payload memory.
+		#[inline(always)]
+		pub fn from_shared(bytes: Bytes) -> Result<Self, InvalidNumber<Bytes>> {
+			Self::new(bytes)
+		}
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+
+		#[test]
+		fn from_shared() {
+			let payload = Bytes::from_static(b"[12.5,-3]");
+			let n = NumberBuf::from_shared(payload.slice(1..5)).unwrap();
+			assert_eq!(n.as_str(), "12.5");
+			assert_eq!(n.buffer().as_ptr(), payload[1..].as_ptr());
+			assert!(NumberBuf::from_shared(payload.slice(0..5)).is_err());
+
+			let sum: NumberBuf<Bytes> = n.clone() + n;
+			assert_eq!(sum.as_str(), "25")
+		}
+
+		#[test]
+		fn convert() {
+			let n: NumberBuf = "-12.5".parse().unwrap();
+			let ptr = n.as_bytes().as_ptr();
+			let n: NumberBuf<Bytes> = n.into();
+			assert_eq!(n.as_bytes().as_ptr(), ptr);
+			let n: NumberBuf = n.into();
+			assert_eq!(n.as_str(), "-12.5")
+		}
+	}
+}
+
+/// Creates a `&'static Number` from a string literal, validated at compile
+/// time.
+///
+/// ```
+/// use json_number::{number, Number};
+///
+/// const LIMIT: &Number = number!("1.25e-3");
+/// assert_eq!(number!("-12").as_i32(), Some(-12));
+/// ```
+///
+/// Invalid literals are rejected at compile time:
+///
+/// ```compile_fail
+/// let n = json_number::number!("01");
+/// ```
+#[macro_export]
+macro_rules! number {
+	($s:literal) => {
+		const { $crate::Number::from_static($s) }
+	};
+}
+
 /// Invalid number error.
 ///
 /// The inner value is the data failed to be parsed.
@@ -63,7 +590,7 @@ impl<T: fmt::Display> fmt::Display for InvalidNumber<T> {
 	}
 }
 
-impl<T: fmt::Display + fmt::Debug> std::error::Error for InvalidNumber<T> {}
+impl<T: fmt::Display + fmt::Debug> core::error::Error for InvalidNumber<T> {}
 
 /// Number sign.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
@@ -111,6 +638,7 @@ impl Sign {
 /// All the comparison operations are done on this *lexical* representation,
 /// meaning that `1` is actually greater than `0.1e+80` for instance.
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
 pub struct Number {
 	data: [u8],
 }
@@ -120,79 +648,120 @@ impl Number {
 	pub fn new<B: AsRef<[u8]> + ?Sized>(data: &B) -> Result<&Number, InvalidNumber<&B>> {
 		let s = data.as_ref();
 
-		enum State {
-			Init,
-			FirstDigit,
-			Zero,
-			NonZero,
-			FractionalFirst,
-			FractionalRest,
-			ExponentSign,
-			ExponentFirst,
-			ExponentRest,
-		}
-
 		let mut state = State::Init;
+		let mut i = 0;
+		while i < s.len() {
+			match state.next(s[i]) {
+				Some(next) => state = next,
+				None => return Err(InvalidNumber(data)),
+			}
 
-		for b in s {
-			match state {
-				State::Init => match *b {
-					b'-' => state = State::FirstDigit,
-					b'0' => state = State::Zero,
-					b'1'..=b'9' => state = State::NonZero,
-					_ => return Err(InvalidNumber(data)),
-				},
-				State::FirstDigit => match *b {
-					b'0' => state = State::Zero,
-					b'1'..=b'9' => state = State::NonZero,
-					_ => return Err(InvalidNumber(data)),
-				},
-				State::Zero => match *b {
-					b'.' => state = State::FractionalFirst,
-					b'e' | b'E' => state = State::ExponentSign,
-					_ => return Err(InvalidNumber(data)),
-				},
-				State::NonZero => match *b {
-					b'0'..=b'9' => state = State::NonZero,
-					b'.' => state = State::FractionalFirst,
-					b'e' | b'E' => state = State::ExponentSign,
-					_ => return Err(InvalidNumber(data)),
-				},
-				State::FractionalFirst => match *b {
-					b'0'..=b'9' => state = State::FractionalRest,
-					_ => return Err(InvalidNumber(data)),
-				},
-				State::FractionalRest => match *b {
-					b'0'..=b'9' => state = State::FractionalRest,
-					b'e' | b'E' => state = State::ExponentSign,
-					_ => return Err(InvalidNumber(data)),
-				},
-				State::ExponentSign => match *b {
-					b'+' | b'-' => state = State::ExponentFirst,
-					b'0'..=b'9' => state = State::ExponentRest,
-					_ => return Err(InvalidNumber(data)),
-				},
-				State::ExponentFirst => match *b {
-					b'0'..=b'9' => state = State::ExponentRest,
-					_ => return Err(InvalidNumber(data)),
-				},
-				State::ExponentRest => match *b {
-					b'0'..=b'9' => state = State::ExponentRest,
-					_ => return Err(InvalidNumber(data)),
-				},
+			i += 1;
+			if state.is_digit_run() {
+				i += parser::digit_run(&s[i..])
 			}
 		}
 
-		if matches!(
-			state,
-			State::Zero | State::NonZero | State::FractionalRest | State::ExponentRest
-		) {
+		if state.is_final() {
 			Ok(unsafe { Self::new_unchecked(s) })
 		} else {
 			Err(InvalidNumber(data))
 		}
 	}
 
+	/// Creates a new number by parsing the given input `data`, ignoring
+	/// leading and trailing ASCII whitespace.
+	///
+	/// The returned number borrows the trimmed part of `data`, without
+	/// copying it.
+	pub fn new_trimmed_input<B: AsRef<[u8]> + ?Sized>(
+		data: &B,
+	) -> Result<&Number, InvalidNumber<&B>> {
+		Number::new(data.as_ref().trim_ascii()).map_err(|_| InvalidNumber(data))
+	}
+
+	/// Parses the longest valid number at the start of `input`.
+	///
+	/// Returns the number and the remaining input, starting with the first
+	/// byte that is not part of the number. This is useful to tokenize a
+	/// JSON document without duplicating the number grammar.
+	///
+	/// Fails if `input` does not start with a valid number.
+	pub fn parse_prefix(input: &[u8]) -> Result<(&Number, &[u8]), InvalidNumber<&[u8]>> {
+		let mut state = State::Init;
+		let mut end = None;
+		let mut i = 0;
+
+		while i < input.len() {
+			match state.next(input[i]) {
+				Some(next) => state = next,
+				None => break,
+			}
+
+			i += 1;
+			if state.is_digit_run() {
+				i += parser::digit_run(&input[i..])
+			}
+
+			if state.is_final() {
+				end = Some(i)
+			}
+		}
+
+		match end {
+			Some(end) => {
+				let (number, rest) = input.split_at(end);
+				Ok((unsafe { Self::new_unchecked(number) }, rest))
+			}
+			None => Err(InvalidNumber(input)),
+		}
+	}
+
+	/// Checks if the given `data` is a valid JSON number.
+	///
+	/// Contrarily to [`Number::new`], this function can be used in const
+	/// contexts.
+	pub const fn is_valid(data: &[u8]) -> bool {
+		let mut state = State::Init;
+		let mut i = 0;
+		while i < data.len() {
+			match state.next(data[i]) {
+				Some(next) => state = next,
+				None => return false,
+			}
+
+			i += 1
+		}
+
+		state.is_final()
+	}
+
+	/// Creates a new number from a static string.
+	///
+	/// This function is meant to be used in const contexts, such as
+	/// `const LIMIT: &Number = Number::from_static("1e6");`, in which case an
+	/// invalid input is reported at compile time.
+	///
+	/// # Panics
+	///
+	/// Panics if `s` is not a valid JSON number.
+	pub const fn from_static(s: &'static str) -> &'static Number {
+		if !Self::is_valid(s.as_bytes()) {
+			panic!("invalid JSON number")
+		}
+
+		unsafe { core::mem::transmute::<&'static [u8], &'static Number>(s.as_bytes()) }
+	}
+
+	/// Zero (`0`).
+	pub const ZERO: &'static Number = Number::from_static("0");
+
+	/// One (`1`).
+	pub const ONE: &'static Number = Number::from_static("1");
+
+	/// Minus one (`-1`).
+	pub const NEG_ONE: &'static Number = Number::from_static("-1");
+
 	/// Creates a new number without parsing the given input `data`.
 	///
 	/// ## Safety
@@ -200,18 +769,41 @@ impl Number {
 	/// The `data` input **must** be a valid JSON number.
 	#[inline(always)]
 	pub unsafe fn new_unchecked<B: AsRef<[u8]> + ?Sized>(data: &B) -> &Number {
-		std::mem::transmute(data.as_ref())
+		core::mem::transmute(data.as_ref())
 	}
 
 	#[inline(always)]
 	pub fn as_str(&self) -> &str {
 		unsafe {
 			// safe because `self.data` is always a valid UTF-8 sequence.
-			std::str::from_utf8_unchecked(&self.data)
+			core::str::from_utf8_unchecked(&self.data)
 		}
 	}
 
+	/// Writes the number into the given `io::Write` output.
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	pub fn write_to(&self, mut w: impl std::io::Write) -> std::io::Result<()> {
+		w.write_all(&self.data)
+	}
+
+	/// Writes the number into the given `fmt::Write` output.
+	#[inline(always)]
+	pub fn write_to_fmt(&self, mut w: impl fmt::Write) -> fmt::Result {
+		w.write_str(self.as_str())
+	}
+
+	/// Returns this number without the trailing zeros of its fractional
+	/// part, and without the decimal point if no fraction digit remains.
+	///
+	/// Numbers with an exponent part are returned as is, since their
+	/// fractional part is not at the end of the number. Use
+	/// [`NumberBuf::trim`] to trim them.
 	pub fn trimmed(&self) -> &Self {
+		if self.has_exponent() {
+			return self;
+		}
+
 		let mut end = 1;
 		let mut i = 1;
 		let mut fractional_part = false;
@@ -228,6 +820,63 @@ impl Number {
 		unsafe { Self::new_unchecked(&self.data[0..end]) }
 	}
 
+	/// Returns this number with a normalized exponent part.
+	///
+	/// The exponent marker is lowercased, the redundant `+` sign and leading
+	/// zeros of the exponent are removed, and a zero exponent is removed
+	/// entirely. For instance `1E+05` becomes `1e5` and `2e0` becomes `2`.
+	pub fn normalize_exponent(&self) -> NumberBuf {
+		let Some(e) = self.data.iter().position(|b| matches!(b, b'e' | b'E')) else {
+			return self.to_owned();
+		};
+
+		let (significand, exponent) = (&self.data[..e], &self.data[e + 1..]);
+		let (negative, digits) = match exponent {
+			[b'-', digits @ ..] => (true, digits),
+			[b'+', digits @ ..] => (false, digits),
+			digits => (false, digits),
+		};
+
+		let leading_zeros = digits.iter().take_while(|b| **b == b'0').count();
+		let digits = &digits[leading_zeros..];
+
+		let mut result = significand.to_vec();
+		if !digits.is_empty() {
+			result.push(b'e');
+			if negative {
+				result.push(b'-')
+			}
+
+			result.extend_from_slice(digits)
+		}
+
+		unsafe { NumberBuf::new_unchecked(result) }
+	}
+
+	/// Returns the shortest representation of the value of this number.
+	///
+	/// Insignificant zeros, dangling decimal points and the sign of zero are
+	/// removed, and the exponent is adjusted (or removed) to minimize the
+	/// length of the output. The plain decimal notation is preferred over the
+	/// scientific notation when both have the same length. For instance
+	/// `-0.0` becomes `0`, `1.50E+1` becomes `15`, `1000` becomes `1e3` and
+	/// `0.000012` becomes `12e-6`.
+	///
+	/// Numbers with the same value have the same normalized form, which can
+	/// hence be used as a deduplication key. This holds even for exponents
+	/// beyond [`Number::MAX_EXACT_EXPONENT`], which are computed exactly from
+	/// the lexical representation.
+	pub fn normalize(&self) -> NumberBuf {
+		let d = decimal::Decimal::new(self);
+		let bytes = if d.is_saturated() {
+			decimal::Decimal::saturated_shortest(self)
+		} else {
+			d.to_shortest()
+		};
+
+		unsafe { NumberBuf::new_unchecked(bytes) }
+	}
+
 	/// Checks if the number is equal to zero (`0`).
 	///
 	/// This include every lexical representation where
@@ -246,6 +895,33 @@ impl Number {
 		true
 	}
 
+	/// Checks if the value of this number is one, whatever its lexical
+	/// representation (`1.0`, `0.1e1`, `10e-1`, etc.).
+	#[inline(always)]
+	pub fn is_one(&self) -> bool {
+		self.is_power_of_ten() == Some(0)
+	}
+
+	/// Checks if the value of this number is minus one, whatever its lexical
+	/// representation (`-1.0`, `-0.1e1`, `-10e-1`, etc.).
+	pub fn is_negative_one(&self) -> bool {
+		let d = decimal::Decimal::new(self);
+		d.negative && d.digits == [1] && d.exponent == 0
+	}
+
+	/// Checks if the value of this number is a power of ten (`100`, `1e-3`,
+	/// `0.1e3`, etc.), and returns the exponent of this power.
+	///
+	/// The exponent is saturated to the `i64` range.
+	pub fn is_power_of_ten(&self) -> Option<i64> {
+		let d = decimal::Decimal::new(self);
+		if !d.negative && d.digits == [1] {
+			Some(d.exponent)
+		} else {
+			None
+		}
+	}
+
 	/// Returns the sign of the number.
 	pub fn sign(&self) -> Sign {
 		let mut non_negative = true;
@@ -358,13 +1034,19 @@ impl Number {
 		self.as_str().parse().ok()
 	}
 
+	#[inline(always)]
+	pub fn as_i128(&self) -> Option<i128> {
+		self.as_str().parse().ok()
+	}
+
+	#[inline(always)]
+	pub fn as_u128(&self) -> Option<u128> {
+		self.as_str().parse().ok()
+	}
+
 	#[inline(always)]
 	pub fn as_f32_lossy(&self) -> f32 {
-		lexical::parse_with_options::<_, _, { lexical::format::JSON }>(
-			self.as_bytes(),
-			&LOSSY_PARSE_FLOAT,
-		)
-		.unwrap()
+		float::parse_f32(self.as_bytes())
 	}
 
 	/// Returns the number as a `f32` only if the operation does not induce
@@ -375,8 +1057,7 @@ impl Number {
 	#[inline(always)]
 	pub fn as_f32_lossless(&self) -> Option<f32> {
 		let f = self.as_f32_lossy();
-		let n: NumberBuf = f.try_into().unwrap();
-		eprintln!("n = {n} = {f}");
+		let n: NumberBuf = f.try_into().ok()?;
 		if n.as_number() == self.trimmed() {
 			Some(f)
 		} else {
@@ -386,11 +1067,7 @@ impl Number {
 
 	#[inline(always)]
 	pub fn as_f64_lossy(&self) -> f64 {
-		lexical::parse_with_options::<_, _, { lexical::format::JSON }>(
-			self.as_bytes(),
-			&LOSSY_PARSE_FLOAT,
-		)
-		.unwrap()
+		float::parse_f64(self.as_bytes())
 	}
 
 	/// Returns the number as a `f64` only if the operation does not induce
@@ -401,7 +1078,7 @@ impl Number {
 	#[inline(always)]
 	pub fn as_f64_lossless(&self) -> Option<f64> {
 		let f = self.as_f64_lossy();
-		let n: NumberBuf = f.try_into().unwrap();
+		let n: NumberBuf = f.try_into().ok()?;
 		if n.as_number() == self {
 			Some(f)
 		} else {
@@ -411,23 +1088,86 @@ impl Number {
 
 	/// Returns the canonical representation of this number according to
 	/// [RFC8785](https://www.rfc-editor.org/rfc/rfc8785#name-serialization-of-numbers).
+	///
+	/// Numbers out of the `f64` range (such as `1e999`) are clamped to
+	/// `f64::MAX` or `f64::MIN`, so this never fails. Use
+	/// [`Self::try_canonical`] to detect such numbers.
 	#[cfg(feature = "canonical")]
 	pub fn canonical_with<'b>(&self, buffer: &'b mut ryu_js::Buffer) -> &'b Number {
-		unsafe { Number::new_unchecked(buffer.format_finite(self.as_f64_lossy())) }
+		let f = self.as_f64_lossy().clamp(f64::MIN, f64::MAX);
+		unsafe { Number::new_unchecked(buffer.format_finite(f)) }
 	}
 
 	/// Returns the canonical representation of this number according to
 	/// [RFC8785](https://www.rfc-editor.org/rfc/rfc8785#name-serialization-of-numbers).
+	///
+	/// See [`Self::canonical_with`] for details.
 	#[cfg(feature = "canonical")]
 	pub fn canonical(&self) -> NumberBuf {
 		let mut buffer = ryu_js::Buffer::new();
 		self.canonical_with(&mut buffer).to_owned()
 	}
-}
 
-const LOSSY_PARSE_FLOAT: lexical::ParseFloatOptions = lexical::ParseFloatOptions::builder()
-	.lossy(true)
-	.build_unchecked();
+	/// Returns the canonical representation of this number according to
+	/// [RFC8785](https://www.rfc-editor.org/rfc/rfc8785#name-serialization-of-numbers),
+	/// provided it has the same value as this number.
+	///
+	/// Contrarily to [`Self::canonical`], this fails if the number cannot be
+	/// represented by an `f64` without losing digits (such as
+	/// `0.10000000000000000001`) or is out of the `f64` range (such as
+	/// `1e999`).
+	#[cfg(feature = "canonical")]
+	pub fn try_canonical(&self) -> Result<NumberBuf, CanonicalError> {
+		let f = self.as_f64_lossy();
+		if !f.is_finite() {
+			return Err(CanonicalError::OutOfRange);
+		}
+
+		let mut buffer = ryu_js::Buffer::new();
+		let canonical = unsafe { Number::new_unchecked(buffer.format_finite(f)) };
+		if decimal::Decimal::new(canonical) == decimal::Decimal::new(self) {
+			Ok(canonical.to_owned())
+		} else {
+			Err(CanonicalError::Inexact)
+		}
+	}
+
+	/// Maximum absolute value of the exponent accepted by
+	/// [`Number::to_ratio`].
+	#[cfg(feature = "ratio")]
+	pub const MAX_RATIO_EXPONENT: u32 = 100_000;
+
+	/// Returns the exact rational value of this number.
+	///
+	/// The result is always reduced, meaning that `0.50` and `5e-1` both
+	/// produce `1/2`.
+	///
+	/// The numerator or denominator holds a power of ten as large as the
+	/// exponent of the number (once the fraction digits are accounted for),
+	/// whose computation cost grows with the exponent. Hence this returns
+	/// `None` if the absolute value of this exponent is greater than
+	/// [`Number::MAX_RATIO_EXPONENT`], even though `1e1000000` is a valid
+	/// number.
+	#[cfg(feature = "ratio")]
+	pub fn to_ratio(&self) -> Option<num_rational::BigRational> {
+		use num_bigint::{BigInt, Sign};
+
+		let d = decimal::Decimal::new(self);
+		let exponent = u32::try_from(d.exponent.unsigned_abs())
+			.ok()
+			.filter(|e| *e <= Self::MAX_RATIO_EXPONENT)?;
+
+		let sign = if d.negative { Sign::Minus } else { Sign::Plus };
+		let numer = BigInt::from_radix_be(sign, &d.digits, 10).unwrap();
+		let pow = BigInt::from(10u32).pow(exponent);
+
+		if d.exponent < 0 {
+			Some(num_rational::BigRational::new(numer, pow))
+		} else {
+			Some(num_rational::BigRational::from_integer(numer * pow))
+		}
+	}
+}
 
 impl Deref for Number {
 	type Target = str;
@@ -468,6 +1208,24 @@ impl<'a> TryFrom<&'a str> for &'a Number {
 	}
 }
 
+impl<'a> TryFrom<&'a [u8]> for &'a Number {
+	type Error = InvalidNumber<&'a [u8]>;
+
+	#[inline(always)]
+	fn try_from(bytes: &'a [u8]) -> Result<&'a Number, InvalidNumber<&'a [u8]>> {
+		Number::new(bytes)
+	}
+}
+
+impl TryFrom<Vec<u8>> for NumberBuf {
+	type Error = InvalidNumber<Vec<u8>>;
+
+	#[inline(always)]
+	fn try_from(bytes: Vec<u8>) -> Result<Self, InvalidNumber<Vec<u8>>> {
+		Self::from_utf8(bytes)
+	}
+}
+
 impl ToOwned for Number {
 	type Owned = NumberBuf;
 
@@ -476,10 +1234,107 @@ impl ToOwned for Number {
 	}
 }
 
+/// Maximum number of integer digits written by [`Number`]'s `Display`
+/// implementation when a precision is given, past which the scientific
+/// notation is used.
+const MAX_DISPLAY_INTEGER_DIGITS: i64 = 1000;
+
 impl fmt::Display for Number {
-	#[inline(always)]
+	/// Formats the number, honoring the formatter flags like primitive
+	/// numbers do.
+	///
+	/// The `+` flag adds a `+` sign to non-negative numbers, the precision
+	/// rounds the number (half to even) to the given number of fractional
+	/// digits, and the width pads the number without ever truncating it.
+	/// With the `0` flag, zeros are inserted between the sign and the digits.
+	///
+	/// When a precision is given, numbers with more than 1000 integer digits
+	/// are written in scientific notation as with [`LowerExp`](fmt::LowerExp)
+	/// instead of being expanded: `{:.2}` formats `1e1000000000` as
+	/// `1.00e1000000000`.
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		self.as_str().fmt(f)
+		let (non_negative, digits) = match self.as_bytes() {
+			[b'-', rest @ ..] => (false, rest),
+			bytes => (true, bytes),
+		};
+
+		match f.precision() {
+			Some(precision) => {
+				let d = decimal::Decimal::new(self);
+				if d.point() > MAX_DISPLAY_INTEGER_DIGITS {
+					return self.fmt_exp(f, b'e');
+				}
+
+				let mut d = d.round(-(precision as i64), RoundingMode::HalfEven);
+				d.negative = false;
+				let fixed = d.to_fixed(precision);
+				// `fixed` only contains ASCII digits and `.`.
+				f.pad_integral(non_negative, "", unsafe {
+					core::str::from_utf8_unchecked(&fixed)
+				})
+			}
+			None => {
+				// `digits` is a suffix of a valid number, hence valid UTF-8.
+				f.pad_integral(non_negative, "", unsafe {
+					core::str::from_utf8_unchecked(digits)
+				})
+			}
+		}
+	}
+}
+
+impl Number {
+	/// Formats the number in scientific notation with the given exponent
+	/// `marker`, honoring the formatter flags.
+	fn fmt_exp(&self, f: &mut fmt::Formatter, marker: u8) -> fmt::Result {
+		let mut d = decimal::Decimal::new(self);
+		let non_negative = !self.as_bytes().starts_with(b"-");
+		d.negative = false;
+
+		let bytes = if d.is_saturated() {
+			decimal::Decimal::saturated_scientific(self, marker, f.precision())
+		} else {
+			match f.precision() {
+				Some(precision) => {
+					if !d.is_zero() {
+						let exponent = d.point().saturating_sub(precision as i64 + 1);
+						d = d.round(exponent, RoundingMode::HalfEven)
+					}
+
+					d.to_scientific(marker, Some(precision))
+				}
+				None => d.to_scientific(marker, None),
+			}
+		};
+
+		// `bytes` only contains ASCII characters, and the sign if saturated.
+		let digits = bytes.strip_prefix(b"-").unwrap_or(&bytes);
+		f.pad_integral(non_negative, "", unsafe {
+			core::str::from_utf8_unchecked(digits)
+		})
+	}
+}
+
+impl fmt::LowerExp for Number {
+	/// Formats the number in normalized scientific notation, with a single
+	/// leading digit and a lowercase `e` exponent marker.
+	///
+	/// This is computed from the lexical representation of the number, so
+	/// no digit is lost. The formatter flags are honored as in
+	/// [`Display`](fmt::Display), the precision specifying the number of
+	/// fractional digits of the significand.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.fmt_exp(f, b'e')
+	}
+}
+
+impl fmt::UpperExp for Number {
+	/// Formats the number in normalized scientific notation, with a single
+	/// leading digit and an uppercase `E` exponent marker.
+	///
+	/// See [`LowerExp`](fmt::LowerExp) for details.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.fmt_exp(f, b'E')
 	}
 }
 
@@ -495,11 +1350,35 @@ impl fmt::Debug for Number {
 /// # Safety
 ///
 /// The `AsRef<[u8]>` implementation *must* return the bytes provided using
-/// the `from_bytes` and `from_vec` constructor functions.
+/// the `from_bytes` and `from_vec` constructor functions, and `into_vec`
+/// *must* return the bytes given by `AsRef<[u8]>`.
 pub unsafe trait Buffer: AsRef<[u8]> {
 	fn from_bytes(bytes: &[u8]) -> Self;
 
+	/// Creates a buffer from the given bytes, or returns `None` if they do
+	/// not fit in the buffer.
+	///
+	/// The default implementation never fails, fixed capacity buffers
+	/// should override it.
+	fn try_from_bytes(bytes: &[u8]) -> Option<Self>
+	where
+		Self: Sized,
+	{
+		Some(Self::from_bytes(bytes))
+	}
+
 	fn from_vec(bytes: Vec<u8>) -> Self;
+
+	/// Converts the buffer into a vector of bytes.
+	///
+	/// The default implementation copies the bytes, implementations should
+	/// reuse their allocation when possible.
+	fn into_vec(self) -> Vec<u8>
+	where
+		Self: Sized,
+	{
+		self.as_ref().to_vec()
+	}
 }
 
 unsafe impl Buffer for Vec<u8> {
@@ -510,6 +1389,98 @@ unsafe impl Buffer for Vec<u8> {
 	fn from_vec(bytes: Vec<u8>) -> Self {
 		bytes
 	}
+
+	fn into_vec(self) -> Vec<u8> {
+		self
+	}
+}
+
+/// Growable buffer type, allowing numbers to be edited in place.
+///
+/// # Safety
+///
+/// The `AsRef<[u8]>` implementation *must* reflect the modifications made
+/// with `push`, `truncate`, `extend_from_slice` and `as_mut_slice`.
+pub unsafe trait BufferMut: Buffer {
+	/// Appends a byte at the end of the buffer.
+	fn push(&mut self, byte: u8);
+
+	/// Shortens the buffer, keeping the first `len` bytes.
+	///
+	/// Has no effect if `len` is greater than the buffer's current length.
+	fn truncate(&mut self, len: usize);
+
+	/// Returns the content of the buffer as a mutable slice.
+	fn as_mut_slice(&mut self) -> &mut [u8];
+
+	/// Appends all the given bytes at the end of the buffer.
+	fn extend_from_slice(&mut self, bytes: &[u8]) {
+		for &b in bytes {
+			self.push(b)
+		}
+	}
+}
+
+unsafe impl BufferMut for Vec<u8> {
+	fn push(&mut self, byte: u8) {
+		Vec::push(self, byte)
+	}
+
+	fn truncate(&mut self, len: usize) {
+		Vec::truncate(self, len)
+	}
+
+	fn as_mut_slice(&mut self) -> &mut [u8] {
+		self
+	}
+
+	fn extend_from_slice(&mut self, bytes: &[u8]) {
+		Vec::extend_from_slice(self, bytes)
+	}
+}
+
+unsafe impl Buffer for String {
+	fn from_bytes(bytes: &[u8]) -> Self {
+		core::str::from_utf8(bytes)
+			.expect("invalid UTF-8 buffer")
+			.to_owned()
+	}
+
+	fn from_vec(bytes: Vec<u8>) -> Self {
+		String::from_utf8(bytes).expect("invalid UTF-8 buffer")
+	}
+
+	fn into_vec(self) -> Vec<u8> {
+		self.into_bytes()
+	}
+}
+
+unsafe impl Buffer for Box<[u8]> {
+	fn from_bytes(bytes: &[u8]) -> Self {
+		bytes.into()
+	}
+
+	fn from_vec(bytes: Vec<u8>) -> Self {
+		bytes.into_boxed_slice()
+	}
+
+	fn into_vec(self) -> Vec<u8> {
+		<[u8]>::into_vec(self)
+	}
+}
+
+unsafe impl Buffer for alloc::borrow::Cow<'_, [u8]> {
+	fn from_bytes(bytes: &[u8]) -> Self {
+		Self::Owned(bytes.to_vec())
+	}
+
+	fn from_vec(bytes: Vec<u8>) -> Self {
+		Self::Owned(bytes)
+	}
+
+	fn into_vec(self) -> Vec<u8> {
+		self.into_owned()
+	}
 }
 
 /// JSON number buffer.
@@ -561,6 +1532,96 @@ impl<B> NumberBuf<B> {
 	}
 }
 
+impl NumberBuf<Vec<u8>> {
+	/// Creates a new number buffer by parsing the given bytes, returning
+	/// them back if they are not a valid JSON number.
+	///
+	/// This is the counterpart of [`String::from_utf8`] for numbers.
+	#[inline(always)]
+	pub fn from_utf8(bytes: Vec<u8>) -> Result<Self, InvalidNumber<Vec<u8>>> {
+		Self::new(bytes)
+	}
+
+	/// Returns the capacity of the underlying buffer, in bytes.
+	#[inline(always)]
+	pub fn capacity(&self) -> usize {
+		self.data.capacity()
+	}
+
+	/// Shrinks the capacity of the underlying buffer as much as possible.
+	#[inline(always)]
+	pub fn shrink_to_fit(&mut self) {
+		self.data.shrink_to_fit()
+	}
+
+	/// Converts this buffer into a boxed number, dropping any excess
+	/// capacity.
+	#[inline(always)]
+	pub fn into_boxed_number(self) -> Box<Number> {
+		self.into()
+	}
+}
+
+impl<B: BufferMut> NumberBuf<B> {
+	/// Trims the number in place, removing trailing zeros of the fractional
+	/// part, and the decimal point if no fraction digit remains.
+	///
+	/// Contrarily to [`Number::trimmed`], the exponent part is kept after
+	/// the trimmed fractional part: `1.50e10` becomes `1.5e10`.
+	pub fn trim(&mut self) {
+		let bytes = self.data.as_mut_slice();
+		let len = bytes.len();
+		let e = bytes
+			.iter()
+			.position(|b| matches!(b, b'e' | b'E'))
+			.unwrap_or(len);
+
+		// The part before the exponent is itself a valid number.
+		let end = unsafe { Number::new_unchecked(&bytes[..e]) }
+			.trimmed()
+			.len();
+		bytes.copy_within(e.., end);
+		self.data.truncate(end + len - e)
+	}
+
+	/// Normalizes the exponent part of this number in place.
+	///
+	/// See [`Number::normalize_exponent`]. The normalized exponent is never
+	/// longer than the original one, so the buffer is never reallocated.
+	pub fn normalize_exponent_in_place(&mut self) {
+		let bytes = self.data.as_mut_slice();
+		let Some(e) = bytes.iter().position(|b| matches!(b, b'e' | b'E')) else {
+			return;
+		};
+
+		let (negative, start) = match bytes.get(e + 1) {
+			Some(b'-') => (true, e + 2),
+			Some(b'+') => (false, e + 2),
+			_ => (false, e + 1),
+		};
+
+		let leading_zeros = bytes[start..].iter().take_while(|b| **b == b'0').count();
+		let digits = start + leading_zeros..bytes.len();
+
+		let len = if digits.is_empty() {
+			e
+		} else {
+			bytes[e] = b'e';
+			let mut i = e + 1;
+			if negative {
+				bytes[i] = b'-';
+				i += 1
+			}
+
+			let digits_len = digits.len();
+			bytes.copy_within(digits, i);
+			i + digits_len
+		};
+
+		self.data.truncate(len)
+	}
+}
+
 impl NumberBuf<String> {
 	#[inline(always)]
 	pub fn into_string(self) -> String {
@@ -578,14 +1639,47 @@ impl<B: Buffer> NumberBuf<B> {
 	pub fn as_number(&self) -> &Number {
 		unsafe { Number::new_unchecked(&self.data) }
 	}
+
+	/// Converts this number into a number with a different buffer type.
+	///
+	/// The bytes are moved rather than copied when both buffer types allow
+	/// it, for instance from `Vec<u8>` to `Box<[u8]>` or `Bytes`, or from a
+	/// spilled `SmallVec` to `Vec<u8>`.
+	#[inline(always)]
+	pub fn convert<C: Buffer>(self) -> NumberBuf<C> {
+		unsafe { NumberBuf::new_unchecked(C::from_vec(self.data.into_vec())) }
+	}
 }
 
+impl Default for &Number {
+	/// Returns [`Number::ZERO`].
+	#[inline(always)]
+	fn default() -> Self {
+		Number::ZERO
+	}
+}
+
+impl<B: Buffer> Default for NumberBuf<B> {
+	/// Returns a buffer holding `0`.
+	#[inline(always)]
+	fn default() -> Self {
+		unsafe { Self::new_unchecked(B::from_bytes(Number::ZERO.as_bytes())) }
+	}
+}
+
+/// Parses a number.
+///
+/// If `s` does not fit in the buffer (see [`Buffer::try_from_bytes`]), the
+/// returned error holds an empty buffer.
 impl<B: Buffer> FromStr for NumberBuf<B> {
 	type Err = InvalidNumber<B>;
 
 	#[inline(always)]
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		Self::new(B::from_bytes(s.as_bytes()))
+		match B::try_from_bytes(s.as_bytes()) {
+			Some(bytes) => Self::new(bytes),
+			None => Err(InvalidNumber(B::from_bytes(&[]))),
+		}
 	}
 }
 
@@ -642,7 +1736,19 @@ impl<B: Buffer> Borrow<[u8]> for NumberBuf<B> {
 
 impl<B: Buffer> fmt::Display for NumberBuf<B> {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		self.as_str().fmt(f)
+		self.as_number().fmt(f)
+	}
+}
+
+impl<B: Buffer> fmt::LowerExp for NumberBuf<B> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::LowerExp::fmt(self.as_number(), f)
+	}
+}
+
+impl<B: Buffer> fmt::UpperExp for NumberBuf<B> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::UpperExp::fmt(self.as_number(), f)
 	}
 }
 
@@ -652,14 +1758,58 @@ impl<B: Buffer> fmt::Debug for NumberBuf<B> {
 	}
 }
 
+macro_rules! impl_str_eq {
+	($($ty:ty),*) => {
+		$(
+			impl PartialEq<$ty> for Number {
+				/// Compares the lexical representation of this number with the
+				/// given string (`1.0` is not equal to `"1"`).
+				#[inline(always)]
+				fn eq(&self, other: &$ty) -> bool {
+					self.as_str() == AsRef::<str>::as_ref(other)
+				}
+			}
+
+			impl<B: Buffer> PartialEq<$ty> for NumberBuf<B> {
+				/// Compares the lexical representation of this number with the
+				/// given string (`1.0` is not equal to `"1"`).
+				#[inline(always)]
+				fn eq(&self, other: &$ty) -> bool {
+					self.as_str() == AsRef::<str>::as_ref(other)
+				}
+			}
+
+			impl PartialEq<Number> for $ty {
+				#[inline(always)]
+				fn eq(&self, other: &Number) -> bool {
+					other == self
+				}
+			}
+
+			impl<B: Buffer> PartialEq<NumberBuf<B>> for $ty {
+				#[inline(always)]
+				fn eq(&self, other: &NumberBuf<B>) -> bool {
+					other == self
+				}
+			}
+		)*
+	};
+}
+
+impl_str_eq!(str, &str, String);
+
 macro_rules! impl_from_int {
 	($($ty:ty),*) => {
 		$(
 			impl<B: Buffer> From<$ty> for NumberBuf<B> {
 				#[inline(always)]
 				fn from(i: $ty) -> Self {
+					if let Some(n) = i64::try_from(i).ok().and_then(small::small_integer) {
+						return unsafe { Self::new_unchecked(B::from_bytes(n.as_bytes())) };
+					}
+
 					unsafe {
-						Self::new_unchecked(B::from_vec(lexical::to_string(i).into_bytes()))
+						Self::new_unchecked(B::from_vec(float::format_int(i)))
 					}
 				}
 			}
@@ -677,13 +1827,33 @@ pub enum TryFromFloatError {
 	Infinite,
 }
 
-const WRITE_FLOAT: lexical::WriteFloatOptions = lexical::WriteFloatOptions::builder()
-	.trim_floats(true)
-	.exponent(b'e')
-	.build_unchecked();
+/// Canonicalization error.
+#[cfg(feature = "canonical")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CanonicalError {
+	/// The number is not exactly represented by its nearest `f64`, meaning
+	/// that canonicalization would lose digits.
+	Inexact,
+
+	/// The number is too large in magnitude to be represented by an `f64`.
+	OutOfRange,
+}
+
+#[cfg(feature = "canonical")]
+impl fmt::Display for CanonicalError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Inexact => f.write_str("number is not exactly representable as a `f64`"),
+			Self::OutOfRange => f.write_str("number is out of the `f64` range"),
+		}
+	}
+}
+
+#[cfg(feature = "canonical")]
+impl core::error::Error for CanonicalError {}
 
 macro_rules! impl_try_from_float {
-	($($ty:ty),*) => {
+	($($ty:ty: $format:ident),*) => {
 		$(
 			impl<B: Buffer> TryFrom<$ty> for NumberBuf<B> {
 				type Error = TryFromFloatError;
@@ -692,7 +1862,7 @@ macro_rules! impl_try_from_float {
 				fn try_from(f: $ty) -> Result<Self, Self::Error> {
 					if f.is_finite() {
 						Ok(unsafe {
-							Self::new_unchecked(B::from_vec(lexical::to_string_with_options::<_, {lexical::format::JSON}>(f, &WRITE_FLOAT).into_bytes()))
+							Self::new_unchecked(B::from_vec(float::$format(f)))
 						})
 					} else if f.is_nan() {
 						Err(TryFromFloatError::Nan)
@@ -705,13 +1875,89 @@ macro_rules! impl_try_from_float {
 	};
 }
 
-impl_from_int!(u8, i8, u16, i16, u32, i32, u64, i64, usize, isize);
-impl_try_from_float!(f32, f64);
+impl_from_int!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize);
+impl_try_from_float!(f32: format_f32, f64: format_f64);
 
 #[cfg(test)]
 mod tests {
 	use super::*;
 
+	#[test]
+	fn constants() {
+		assert_eq!(Number::ZERO, "0");
+		assert_eq!(*Number::ONE, 1u64);
+		assert_eq!(*Number::NEG_ONE, -1i64);
+		assert_eq!(<&Number>::default(), Number::ZERO);
+		assert_eq!(NumberBuf::<Vec<u8>>::default(), "0");
+
+		let mut sum = NumberBuf::<Vec<u8>>::default();
+		sum += 2;
+		assert_eq!(sum, "2")
+	}
+
+	#[test]
+	fn identities() {
+		for s in ["1", "1.0", "0.1e1", "10e-1", "100E-2", "0.001e+3"] {
+			assert!(Number::new(s).unwrap().is_one(), "{s}");
+			assert!(
+				Number::new(&format!("-{s}")).unwrap().is_negative_one(),
+				"{s}"
+			)
+		}
+
+		for s in ["0", "-1", "11e-1", "1.01", "10"] {
+			assert!(!Number::new(s).unwrap().is_one(), "{s}")
+		}
+
+		assert!(!Number::ONE.is_negative_one());
+		assert!(!Number::new("-0.1").unwrap().is_negative_one());
+
+		let power = |s| Number::new(s).unwrap().is_power_of_ten();
+		assert_eq!(power("1"), Some(0));
+		assert_eq!(power("100"), Some(2));
+		assert_eq!(power("0.0010"), Some(-3));
+		assert_eq!(power("0.1e3"), Some(2));
+		assert_eq!(power("1e-999"), Some(-999));
+		assert_eq!(power("0"), None);
+		assert_eq!(power("-10"), None);
+		assert_eq!(power("20"), None);
+		assert_eq!(power("11"), None)
+	}
+
+	#[test]
+	fn try_from_bytes() {
+		let n: &Number = b"1.5e3".as_slice().try_into().unwrap();
+		assert_eq!(n.as_str(), "1.5e3");
+		assert!(<&Number>::try_from(b"1.".as_slice()).is_err());
+
+		let n = NumberBuf::try_from(b"-12".to_vec()).unwrap();
+		assert_eq!(n.as_str(), "-12");
+
+		let bytes = b"12a".to_vec();
+		let ptr = bytes.as_ptr();
+		let InvalidNumber(bytes) = NumberBuf::from_utf8(bytes).unwrap_err();
+		assert_eq!(bytes, b"12a");
+		assert_eq!(bytes.as_ptr(), ptr)
+	}
+
+	#[test]
+	fn str_eq() {
+		let n = Number::new("1.50").unwrap();
+		assert_eq!(*n, "1.50");
+		assert_eq!(n, &"1.50");
+		assert_eq!(*n, "1.50".to_owned());
+		assert_ne!(*n, "1.5");
+		assert_eq!("1.50", *n);
+
+		let m: NumberBuf = "-2e3".parse().unwrap();
+		assert_eq!(m, "-2e3");
+		assert_eq!(m, *"-2e3");
+		assert_eq!(m, "-2e3".to_owned());
+		assert_ne!(m, "-2000");
+		assert_eq!("-2e3".to_owned(), m);
+		assert_eq!(*"-2e3", m)
+	}
+
 	fn trimming_test(a: &str, b: &str) {
 		let a = Number::new(a).unwrap();
 		let b = Number::new(b).unwrap();
@@ -728,6 +1974,9 @@ mod tests {
 		trimming_test("1.10000", "1.1");
 		trimming_test("100.0", "100");
 		trimming_test("100.1000", "100.1");
+		trimming_test("1.5e10", "1.5e10");
+		trimming_test("1.50E100", "1.50E100");
+		trimming_test("10e0", "10e0");
 	}
 
 	macro_rules! positive_tests {
@@ -825,8 +2074,317 @@ mod tests {
 		sign_neg_05: "-0.00001" => Negative
 	}
 
+	#[test]
+	fn from_static() {
+		const LIMIT: &Number = Number::from_static("1e6");
+		const { assert!(Number::is_valid(b"-0.5E+2")) };
+		assert_eq!(LIMIT.as_str(), "1e6");
+		assert!(!Number::is_valid(b"01"));
+		assert!(!Number::is_valid(b""));
+	}
+
+	#[test]
+	#[should_panic]
+	fn from_static_invalid() {
+		Number::from_static("1.");
+	}
+
+	#[test]
+	fn display_flags() {
+		let n = |s| Number::new(s).unwrap();
+		assert_eq!(format!("{}", n("-1.50e3")), "-1.50e3");
+		assert_eq!(format!("{:+}", n("1.5")), "+1.5");
+		assert_eq!(format!("{:+}", n("-1.5")), "-1.5");
+		assert_eq!(format!("{:.2}", n("2.675")), "2.68");
+		assert_eq!(format!("{:.2}", n("2.665")), "2.66");
+		assert_eq!(format!("{:.2}", n("1")), "1.00");
+		assert_eq!(format!("{:.0}", n("-2.5")), "-2");
+		assert_eq!(format!("{:.1}", n("-0.01")), "-0.0");
+		assert_eq!(format!("{:.3}", n("1.5e-2")), "0.015");
+		assert_eq!(format!("{:>8}", n("-1.5")), "    -1.5");
+		assert_eq!(format!("{:8}", n("-1.5")), "    -1.5");
+		assert_eq!(format!("{:<8}|", n("-1.5")), "-1.5    |");
+		assert_eq!(format!("{:*^+9.1}", n("1.25")), "**+1.2***");
+		assert_eq!(format!("{:08.2}", n("-1.5")), "-0001.50");
+		assert_eq!(format!("{:3}", n("123456")), "123456");
+		assert_eq!(format!("{:+}", "-7".parse::<NumberBuf>().unwrap()), "-7");
+		assert_eq!(format!("{:.2}", n("1e1000000000")), "1.00e1000000000");
+		assert_eq!(format!("{:.0}", n("-1.5e1000")), "-2e1000");
+		assert_eq!(format!("{:.1}", n("1e999")).len(), 1002);
+		assert_eq!(
+			format!("{:.2}", n("1e99999999999999999999")),
+			"1.00e99999999999999999999"
+		);
+		assert_eq!(format!("{:.2}", n("-1e-99999999999999999999")), "-0.00");
+	}
+
+	#[test]
+	fn exp() {
+		let n = |s| Number::new(s).unwrap();
+		assert_eq!(format!("{:e}", n("1500")), "1.5e3");
+		assert_eq!(format!("{:E}", n("-0.00125")), "-1.25E-3");
+		assert_eq!(format!("{:e}", n("12.5e10")), "1.25e11");
+		assert_eq!(format!("{:e}", n("0.0e5")), "0e0");
+		assert_eq!(format!("{:e}", n("7")), "7e0");
+		assert_eq!(
+			format!("{:e}", n("12345678901234567890.123")),
+			"1.2345678901234567890123e19"
+		);
+		assert_eq!(format!("{:.2e}", n("1500")), "1.50e3");
+		assert_eq!(format!("{:.1e}", n("9.96")), "1.0e1");
+		assert_eq!(format!("{:.0E}", n("2.5")), "2E0");
+		assert_eq!(format!("{:.2e}", n("0")), "0.00e0");
+		assert_eq!(format!("{:+e}", n("1")), "+1e0");
+		assert_eq!(format!("{:>8e}", n("-15")), "  -1.5e1");
+		assert_eq!(
+			format!("{:e}", n("15e99999999999999999999")),
+			"1.5e100000000000000000000"
+		);
+		assert_eq!(
+			format!("{:E}", n("-0.012e-99999999999999999999")),
+			"-1.2E-100000000000000000001"
+		);
+		assert_eq!(
+			format!("{:.1e}", n("9.96e99999999999999999999")),
+			"1.0e100000000000000000000"
+		);
+		assert_eq!(
+			format!("{:>25.0e}", n("-2.5e99999999999999999999")),
+			"  -2e99999999999999999999"
+		);
+		let b: NumberBuf = "1500".parse().unwrap();
+		assert_eq!(format!("{b:e} {b:E}"), "1.5e3 1.5E3");
+	}
+
+	#[test]
+	fn normalize() {
+		for (a, b) in [
+			("0", "0"),
+			("-0.0e5", "0"),
+			("1.0", "1"),
+			("-1.50E+1", "-15"),
+			("100", "100"),
+			("1000", "1e3"),
+			("12000", "12e3"),
+			("0.5", "0.5"),
+			("0.000012", "12e-6"),
+			("0.012", "0.012"),
+			("0.0012", "12e-4"),
+			("1.25e1", "12.5"),
+			("1.2345e-10", "12345e-14"),
+			("1e1000000000", "1e1000000000"),
+			("10e-1", "1"),
+			("1e99999999999999999999", "1e99999999999999999999"),
+			("-1.50E+099999999999999999999", "-15e99999999999999999998"),
+			("1200e-99999999999999999999", "12e-99999999999999999997"),
+			("0.001e-9223372036854775806", "1e-9223372036854775809"),
+		] {
+			assert_eq!(Number::new(a).unwrap().normalize().as_str(), b, "{a}")
+		}
+
+		let key = |s| Number::new(s).unwrap().normalize();
+		assert_eq!(key("1.5e2"), key("150.000"));
+		assert_eq!(key("0.015E+4"), key("15e1"));
+		assert_ne!(key("1e99999999999999999999"), key("1e99999999999999999998"));
+		assert_eq!(
+			key("10e99999999999999999998"),
+			key("1e99999999999999999999")
+		)
+	}
+
+	#[test]
+	fn normalize_exponent() {
+		for (a, b) in [
+			("1", "1"),
+			("1.50", "1.50"),
+			("1E+05", "1e5"),
+			("1e5", "1e5"),
+			("-1.5E-007", "-1.5e-7"),
+			("2e0", "2"),
+			("2E-000", "2"),
+			("0.5e+10", "0.5e10"),
+		] {
+			assert_eq!(Number::new(a).unwrap().normalize_exponent().as_str(), b)
+		}
+	}
+
+	#[test]
+	fn in_place_editing() {
+		for (a, b) in [
+			("1.500", "1.5"),
+			("2.0", "2"),
+			("10", "10"),
+			("-0.00", "-0"),
+			("1.5e10", "1.5e10"),
+			("1.50E100", "1.5E100"),
+			("10e0", "10e0"),
+			("1.0e-5", "1e-5"),
+			("100.000E+0", "100E+0"),
+		] {
+			let mut n: NumberBuf = a.parse().unwrap();
+			n.trim();
+			assert_eq!(n.as_str(), b)
+		}
+
+		for (a, b) in [
+			("1", "1"),
+			("1E+05", "1e5"),
+			("-1.5E-007", "-1.5e-7"),
+			("2E-000", "2"),
+		] {
+			let mut n: NumberBuf = a.parse().unwrap();
+			n.normalize_exponent_in_place();
+			assert_eq!(n.as_str(), b)
+		}
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn write_to() {
+		let n: NumberBuf = "-1.5e3".parse().unwrap();
+		let mut bytes = b"[".to_vec();
+		n.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes, b"[-1.5e3");
+
+		let mut s = String::from("[");
+		n.write_to_fmt(&mut s).unwrap();
+		assert_eq!(s, "[-1.5e3")
+	}
+
+	#[test]
+	fn buffers() {
+		use alloc::borrow::Cow;
+
+		let n: NumberBuf<String> = "-1.5e3".parse().unwrap();
+		assert_eq!(n.as_number(), Number::new("-1.5e3").unwrap());
+		assert_eq!((n.clone() + &*n).into_string(), "-3000");
+
+		let n: NumberBuf<Box<[u8]>> = "12".parse().unwrap();
+		assert_eq!(n.as_i32(), Some(12));
+		assert_eq!((n.clone() * n).as_str(), "144");
+
+		let n = NumberBuf::new(Cow::Borrowed(&b"0.5"[..])).unwrap();
+		assert!(matches!(n.buffer(), Cow::Borrowed(_)));
+		assert_eq!((n.clone() - n).as_str(), "0");
+	}
+
+	#[test]
+	fn shrink_to_fit() {
+		let mut bytes = Vec::with_capacity(64);
+		bytes.extend_from_slice(b"12.5");
+		let mut n = NumberBuf::new(bytes).unwrap();
+		assert_eq!(n.capacity(), 64);
+		n.shrink_to_fit();
+		assert_eq!(n.capacity(), 4);
+
+		let b = n.into_boxed_number();
+		assert_eq!(b.as_str(), "12.5")
+	}
+
+	#[test]
+	fn convert() {
+		let n: NumberBuf = "-12.5".parse().unwrap();
+		let ptr = n.as_bytes().as_ptr();
+		let n: NumberBuf<Box<[u8]>> = n.convert();
+		assert_eq!(n.as_bytes().as_ptr(), ptr);
+		let n: NumberBuf<String> = n.convert();
+		assert_eq!(n.as_bytes().as_ptr(), ptr);
+		assert_eq!(n.into_string(), "-12.5")
+	}
+
+	#[test]
+	fn lossless_floats() {
+		let n = Number::new("1.5").unwrap();
+		assert_eq!(n.as_f32_lossless(), Some(1.5));
+		assert_eq!(n.as_f64_lossless(), Some(1.5));
+		assert_eq!(Number::new("16777217").unwrap().as_f32_lossless(), None);
+		assert_eq!(Number::new("1e39").unwrap().as_f32_lossless(), None);
+		assert_eq!(Number::new("1e309").unwrap().as_f64_lossless(), None)
+	}
+
+	#[test]
+	fn new_trimmed_input() {
+		let input = " \t12.5e3\r\n";
+		let n = Number::new_trimmed_input(input).unwrap();
+		assert_eq!(n.as_str(), "12.5e3");
+		assert_eq!(n.as_bytes().as_ptr(), input[2..].as_ptr());
+		assert_eq!(Number::new_trimmed_input("-1").unwrap().as_str(), "-1");
+		assert_eq!(Number::new_trimmed_input("1 2").unwrap_err().0, "1 2");
+		assert!(Number::new_trimmed_input("  ").is_err());
+		assert!(Number::new(" 1").is_err())
+	}
+
+	#[test]
+	fn parse_prefix() {
+		let prefix = |s: &'static str| {
+			Number::parse_prefix(s.as_bytes())
+				.map(|(n, rest)| (n.as_str(), core::str::from_utf8(rest).unwrap()))
+		};
+
+		assert_eq!(prefix("12.5e3").unwrap(), ("12.5e3", ""));
+		assert_eq!(prefix("-1, 2]").unwrap(), ("-1", ", 2]"));
+		assert_eq!(prefix("1.}").unwrap(), ("1", ".}"));
+		assert_eq!(prefix("1e+}").unwrap(), ("1", "e+}"));
+		assert_eq!(prefix("01").unwrap(), ("0", "1"));
+		assert_eq!(prefix("0.5E-2x").unwrap(), ("0.5E-2", "x"));
+		assert!(prefix("-").is_err());
+		assert!(prefix("-a").is_err());
+		assert!(prefix(".5").is_err());
+		assert!(prefix("").is_err());
+	}
+
+	#[cfg(feature = "ratio")]
+	#[test]
+	fn to_ratio() {
+		use num_bigint::BigInt;
+		use num_rational::BigRational;
+
+		let ratio = |n: i64, d: i64| Some(BigRational::new(BigInt::from(n), BigInt::from(d)));
+		assert_eq!(Number::new("0.50").unwrap().to_ratio(), ratio(1, 2));
+		assert_eq!(Number::new("-5e-1").unwrap().to_ratio(), ratio(-1, 2));
+		assert_eq!(Number::new("1.5E3").unwrap().to_ratio(), ratio(1500, 1));
+		assert_eq!(Number::new("-0.0e12").unwrap().to_ratio(), ratio(0, 1));
+		assert_eq!(Number::new("0e9999999999").unwrap().to_ratio(), ratio(0, 1));
+
+		let exponent = Number::MAX_RATIO_EXPONENT;
+		let max = Number::new(&format!("1e-{exponent}")).unwrap().to_ratio();
+		assert_eq!(
+			max,
+			Some(BigRational::new(1.into(), BigInt::from(10).pow(exponent)))
+		);
+		assert!(Number::new(&format!("0.1e-{exponent}"))
+			.unwrap()
+			.to_ratio()
+			.is_none());
+		assert!(Number::new("1e9999999999").unwrap().to_ratio().is_none());
+		assert!(Number::new("1e-4294967295").unwrap().to_ratio().is_none())
+	}
+
+	#[cfg(feature = "canonical")]
+	#[test]
+	fn try_canonical() {
+		let try_canonical = |s| Number::new(s).unwrap().try_canonical();
+		assert_eq!(try_canonical("1.50E2").unwrap().as_str(), "150");
+		assert_eq!(try_canonical("-0.0").unwrap().as_str(), "0");
+		assert_eq!(try_canonical("0.1").unwrap().as_str(), "0.1");
+		assert_eq!(try_canonical("1e21").unwrap().as_str(), "1e+21");
+		assert_eq!(
+			try_canonical("0.10000000000000000001"),
+			Err(CanonicalError::Inexact)
+		);
+		assert_eq!(
+			try_canonical("9007199254740993"),
+			Err(CanonicalError::Inexact)
+		);
+		assert_eq!(try_canonical("1e999"), Err(CanonicalError::OutOfRange));
+		assert_eq!(try_canonical("1e-999"), Err(CanonicalError::Inexact));
+	}
+
 	canonical_tests! {
 		canonical_01: "-0.0000" => "0",
-		canonical_02: "0.00000000028" => "2.8e-10"
+		canonical_02: "0.00000000028" => "2.8e-10",
+		canonical_03: "1e999" => "1.7976931348623157e+308",
+		canonical_04: "-1e999" => "-1.7976931348623157e+308",
+		canonical_05: "1e-999" => "0"
 	}
 }
@@ -0,0 +1,95 @@
+//! Length-limited parsing.
+use crate::{Number, NumberBuf};
+use core::fmt;
+
+/// Length-limited number parsing error.
+///
+/// The inner value is the data failed to be parsed.
+#[derive(Clone, Copy, Debug)]
+pub enum LimitedNumberError<T> {
+	/// The input is not a valid JSON number.
+	Invalid(T),
+
+	/// The input is longer than the allowed number of bytes.
+	TooLong(T),
+}
+
+impl<T> LimitedNumberError<T> {
+	/// Returns the data that failed to be parsed.
+	pub fn into_inner(self) -> T {
+		match self {
+			Self::Invalid(t) | Self::TooLong(t) => t,
+		}
+	}
+}
+
+impl<T: AsRef<[u8]>> fmt::Display for LimitedNumberError<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Invalid(_) => f.write_str("invalid JSON number"),
+			Self::TooLong(t) => write!(f, "JSON number too long ({} bytes)", t.as_ref().len()),
+		}
+	}
+}
+
+impl<T: AsRef<[u8]> + fmt::Debug> core::error::Error for LimitedNumberError<T> {}
+
+impl Number {
+	/// Creates a new number by parsing the given input `data`, provided it is
+	/// at most `max_len` bytes long.
+	///
+	/// The length is checked before parsing, so this can be used to reject
+	/// huge inputs early.
+	pub fn new_with_limit<B: AsRef<[u8]> + ?Sized>(
+		data: &B,
+		max_len: usize,
+	) -> Result<&Number, LimitedNumberError<&B>> {
+		if data.as_ref().len() > max_len {
+			return Err(LimitedNumberError::TooLong(data));
+		}
+
+		Number::new(data).map_err(|e| LimitedNumberError::Invalid(e.0))
+	}
+}
+
+impl<B: AsRef<[u8]>> NumberBuf<B> {
+	/// Creates a new number buffer by parsing the given input `data` buffer,
+	/// provided it is at most `max_len` bytes long.
+	///
+	/// The length is checked before parsing, so this can be used to reject
+	/// huge inputs early.
+	pub fn new_with_limit(data: B, max_len: usize) -> Result<Self, LimitedNumberError<B>> {
+		match Number::new_with_limit(&data, max_len) {
+			Ok(_) => Ok(unsafe { Self::new_unchecked(data) }),
+			Err(LimitedNumberError::Invalid(_)) => Err(LimitedNumberError::Invalid(data)),
+			Err(LimitedNumberError::TooLong(_)) => Err(LimitedNumberError::TooLong(data)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::LimitedNumberError;
+	use crate::{Number, NumberBuf};
+
+	#[test]
+	fn new_with_limit() {
+		assert!(Number::new_with_limit("12.5", 4).is_ok());
+		assert!(matches!(
+			Number::new_with_limit("12.50", 4),
+			Err(LimitedNumberError::TooLong("12.50"))
+		));
+		assert!(matches!(
+			Number::new_with_limit("1.", 4),
+			Err(LimitedNumberError::Invalid("1."))
+		));
+
+		let long = "9".repeat(1_000_000);
+		assert!(matches!(
+			NumberBuf::new_with_limit(long.into_bytes(), 1024),
+			Err(LimitedNumberError::TooLong(_))
+		));
+		let n = NumberBuf::new_with_limit(b"-1e3".to_vec(), 4).unwrap();
+		assert_eq!(n.as_str(), "-1e3")
+	}
+}
@@ -0,0 +1,254 @@
+use crate::{decimal::Decimal, Buffer, Number, NumberBuf};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use malachite::num::arithmetic::traits::{Pow, Sign};
+use malachite::num::conversion::traits::Digits;
+use malachite::{Integer, Natural, Rational};
+
+/// Error returned when converting a [`Number`] into an [`Integer`] or a
+/// [`Natural`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TryIntoIntegerError {
+	/// The number has a non-zero fractional part.
+	Fractional,
+
+	/// The number is negative, and hence not a natural number.
+	Negative,
+}
+
+impl fmt::Display for TryIntoIntegerError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Fractional => f.write_str("number is not an integer"),
+			Self::Negative => f.write_str("number is negative"),
+		}
+	}
+}
+
+impl core::error::Error for TryIntoIntegerError {}
+
+/// Error returned when converting a [`Rational`] with no finite decimal
+/// expansion (such as `1/3`) into a number.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NonTerminatingDecimal;
+
+impl fmt::Display for NonTerminatingDecimal {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("rational has no finite decimal expansion")
+	}
+}
+
+impl core::error::Error for NonTerminatingDecimal {}
+
+fn ten() -> Natural {
+	Natural::from(10u32)
+}
+
+/// Returns the significant digits of the given decimal as a natural number.
+fn significand(d: &Decimal) -> Natural {
+	Natural::from_digits_desc(&10u8, d.digits.iter().copied()).unwrap()
+}
+
+/// Converts a natural number into a decimal, without any exponent.
+fn natural_decimal(negative: bool, n: &Natural) -> Decimal {
+	let mut result = Decimal {
+		negative,
+		digits: n.to_digits_desc(&10u8),
+		exponent: 0,
+	};
+
+	result.normalize();
+	result
+}
+
+/// Returns the absolute value of the given integer number.
+fn integer_magnitude(n: &Number) -> Result<(bool, Natural), TryIntoIntegerError> {
+	let d = Decimal::new(n);
+	if d.exponent < 0 {
+		Err(TryIntoIntegerError::Fractional)
+	} else {
+		Ok((d.negative, significand(&d) * ten().pow(d.exponent as u64)))
+	}
+}
+
+impl<'n> TryFrom<&'n Number> for Natural {
+	type Error = TryIntoIntegerError;
+
+	/// Converts the number into a natural number.
+	///
+	/// Beware that the exponent is expanded, meaning that small inputs such
+	/// as `1e1000000000` require a very large amount of memory.
+	fn try_from(n: &'n Number) -> Result<Self, Self::Error> {
+		match integer_magnitude(n)? {
+			(true, _) => Err(TryIntoIntegerError::Negative),
+			(false, magnitude) => Ok(magnitude),
+		}
+	}
+}
+
+impl<'n> TryFrom<&'n Number> for Integer {
+	type Error = TryIntoIntegerError;
+
+	/// Converts the number into an integer.
+	///
+	/// Beware that the exponent is expanded, meaning that small inputs such
+	/// as `1e1000000000` require a very large amount of memory.
+	fn try_from(n: &'n Number) -> Result<Self, Self::Error> {
+		let (negative, magnitude) = integer_magnitude(n)?;
+		Ok(Integer::from_sign_and_abs(!negative, magnitude))
+	}
+}
+
+impl<'n> From<&'n Number> for Rational {
+	/// Converts the number into its exact rational value.
+	///
+	/// Beware that the exponent is expanded, meaning that small inputs such
+	/// as `1e1000000000` require a very large amount of memory.
+	fn from(n: &'n Number) -> Self {
+		let d = Decimal::new(n);
+		let pow = ten().pow(d.exponent.unsigned_abs());
+		if d.exponent < 0 {
+			Rational::from_sign_and_naturals(!d.negative, significand(&d), pow)
+		} else {
+			Rational::from_sign_and_naturals(
+				!d.negative,
+				significand(&d) * pow,
+				Natural::from(1u32),
+			)
+		}
+	}
+}
+
+impl<'n, B: Buffer> From<&'n Natural> for NumberBuf<B> {
+	fn from(n: &'n Natural) -> Self {
+		let digits: Vec<u8> = n
+			.to_digits_desc(&10u8)
+			.into_iter()
+			.map(|d| b'0' + d)
+			.collect();
+		if digits.is_empty() {
+			unsafe { Self::new_unchecked(B::from_bytes(b"0")) }
+		} else {
+			unsafe { Self::new_unchecked(B::from_vec(digits)) }
+		}
+	}
+}
+
+impl<B: Buffer> From<Natural> for NumberBuf<B> {
+	#[inline(always)]
+	fn from(n: Natural) -> Self {
+		(&n).into()
+	}
+}
+
+impl<'n, B: Buffer> From<&'n Integer> for NumberBuf<B> {
+	fn from(n: &'n Integer) -> Self {
+		let negative = n.sign() == Ordering::Less;
+		let digits = n.unsigned_abs_ref().to_digits_desc(&10u8);
+		let mut bytes = Vec::with_capacity(digits.len() + 1);
+
+		if digits.is_empty() {
+			bytes.push(b'0')
+		} else {
+			if negative {
+				bytes.push(b'-')
+			}
+
+			bytes.extend(digits.into_iter().map(|d| b'0' + d))
+		}
+
+		unsafe { Self::new_unchecked(B::from_vec(bytes)) }
+	}
+}
+
+impl<B: Buffer> From<Integer> for NumberBuf<B> {
+	#[inline(always)]
+	fn from(n: Integer) -> Self {
+		(&n).into()
+	}
+}
+
+impl<'n, B: Buffer> TryFrom<&'n Rational> for NumberBuf<B> {
+	type Error = NonTerminatingDecimal;
+
+	/// Converts the rational into its decimal expansion, provided it is
+	/// finite (the reduced denominator has no prime factors other than `2`
+	/// and `5`).
+	fn try_from(r: &'n Rational) -> Result<Self, Self::Error> {
+		let negative = r.sign() == Ordering::Less;
+		let (numerator, denominator) = r.to_numerator_and_denominator();
+
+		let twos = denominator.trailing_zeros().unwrap();
+		let mut rest = &denominator >> twos;
+		let five = Natural::from(5u32);
+		let mut fives = 0u64;
+		while &rest % &five == 0u32 {
+			rest /= &five;
+			fives += 1
+		}
+
+		if rest != 1u32 {
+			return Err(NonTerminatingDecimal);
+		}
+
+		let scale = twos.max(fives);
+		let scaled = numerator * ten().pow(scale) / denominator;
+
+		let mut d = natural_decimal(negative, &scaled);
+		d.exponent = d.exponent.saturating_sub(scale as i64);
+		Ok(d.into_number_buf())
+	}
+}
+
+impl<B: Buffer> TryFrom<Rational> for NumberBuf<B> {
+	type Error = NonTerminatingDecimal;
+
+	#[inline(always)]
+	fn try_from(r: Rational) -> Result<Self, Self::Error> {
+		(&r).try_into()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::TryIntoIntegerError;
+	use crate::{Number, NumberBuf};
+	use malachite::{Integer, Natural, Rational};
+
+	fn number(s: &str) -> &Number {
+		Number::new(s).unwrap()
+	}
+
+	#[test]
+	fn number_to_malachite() {
+		assert_eq!(
+			Natural::try_from(number("1.5e2")),
+			Ok(Natural::from(150u32))
+		);
+		assert_eq!(
+			Natural::try_from(number("-1")),
+			Err(TryIntoIntegerError::Negative)
+		);
+		assert_eq!(Integer::try_from(number("-12e1")), Ok(Integer::from(-120)));
+		assert_eq!(
+			Integer::try_from(number("1.5")),
+			Err(TryIntoIntegerError::Fractional)
+		);
+		assert_eq!(
+			Rational::from(number("-0.250")),
+			Rational::from_signeds(-1, 4)
+		);
+	}
+
+	#[test]
+	fn malachite_to_number() {
+		let n: NumberBuf = Natural::from(1234u32).into();
+		assert_eq!(n.as_str(), "1234");
+		let n: NumberBuf = Integer::from(-1234).into();
+		assert_eq!(n.as_str(), "-1234");
+		let n: NumberBuf = Rational::from_signeds(-3, 8).try_into().unwrap();
+		assert_eq!(n.as_str(), "-0.375");
+		assert!(NumberBuf::<Vec<u8>>::try_from(Rational::from_signeds(1, 3)).is_err())
+	}
+}
@@ -0,0 +1,286 @@
+//! Number newtypes restricting the set of valid numbers.
+
+/// Defines a borrowed number newtype and its owned buffer counterpart,
+/// holding only numbers satisfying the given `$check` predicate.
+///
+/// The buffer borrows the number using the `$as` method. `$expecting`
+/// describes the valid numbers, in documentation and deserialization errors.
+macro_rules! number_newtype {
+	(
+		$(#[$meta:meta])*
+		$name:ident,
+		$(#[$buf_meta:meta])*
+		$buf:ident,
+		$as:ident,
+		$check:expr,
+		$expecting:literal
+	) => {
+		$(#[$meta])*
+		#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+		#[repr(transparent)]
+		pub struct $name(crate::Number);
+
+		impl $name {
+			/// Checks that the given number is valid for this type.
+			#[inline(always)]
+			pub fn new(n: &crate::Number) -> Option<&Self> {
+				if ($check)(n) {
+					Some(unsafe { Self::new_unchecked(n) })
+				} else {
+					None
+				}
+			}
+
+			/// Wraps the given number without checking it.
+			///
+			/// ## Safety
+			///
+			#[doc = concat!("The number **must** be ", $expecting, ".")]
+			#[inline(always)]
+			pub unsafe fn new_unchecked(n: &crate::Number) -> &Self {
+				&*(n as *const crate::Number as *const Self)
+			}
+
+			/// Returns the underlying number.
+			#[inline(always)]
+			pub fn as_number(&self) -> &crate::Number {
+				&self.0
+			}
+		}
+
+		impl core::ops::Deref for $name {
+			type Target = crate::Number;
+
+			#[inline(always)]
+			fn deref(&self) -> &crate::Number {
+				&self.0
+			}
+		}
+
+		impl AsRef<crate::Number> for $name {
+			#[inline(always)]
+			fn as_ref(&self) -> &crate::Number {
+				&self.0
+			}
+		}
+
+		impl core::borrow::Borrow<crate::Number> for $name {
+			#[inline(always)]
+			fn borrow(&self) -> &crate::Number {
+				&self.0
+			}
+		}
+
+		impl alloc::borrow::ToOwned for $name {
+			type Owned = $buf;
+
+			#[inline(always)]
+			fn to_owned(&self) -> $buf {
+				$buf(self.0.to_owned())
+			}
+		}
+
+		impl<'a> TryFrom<&'a crate::Number> for &'a $name {
+			type Error = &'a crate::Number;
+
+			/// Checks the given number, returning it back if it is not valid.
+			#[inline(always)]
+			fn try_from(n: &'a crate::Number) -> Result<Self, &'a crate::Number> {
+				$name::new(n).ok_or(n)
+			}
+		}
+
+		impl core::fmt::Display for $name {
+			#[inline(always)]
+			fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+				self.0.fmt(f)
+			}
+		}
+
+		impl core::fmt::Debug for $name {
+			#[inline(always)]
+			fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+				self.0.fmt(f)
+			}
+		}
+
+		$(#[$buf_meta])*
+		#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+		pub struct $buf<B = alloc::vec::Vec<u8>>(crate::NumberBuf<B>);
+
+		impl<B: crate::Buffer> $buf<B> {
+			/// Checks that the given number is valid for this type, returning
+			/// it back otherwise.
+			#[inline(always)]
+			pub fn new(n: crate::NumberBuf<B>) -> Result<Self, crate::NumberBuf<B>> {
+				if ($check)(n.as_number()) {
+					Ok(Self(n))
+				} else {
+					Err(n)
+				}
+			}
+
+			/// Wraps the given number without checking it.
+			///
+			/// ## Safety
+			///
+			#[doc = concat!("The number **must** be ", $expecting, ".")]
+			#[inline(always)]
+			pub unsafe fn new_unchecked(n: crate::NumberBuf<B>) -> Self {
+				Self(n)
+			}
+
+			/// Borrows the number.
+			#[inline(always)]
+			pub fn $as(&self) -> &$name {
+				unsafe { $name::new_unchecked(self.0.as_number()) }
+			}
+
+			/// Returns the underlying number buffer.
+			#[inline(always)]
+			pub fn into_number_buf(self) -> crate::NumberBuf<B> {
+				self.0
+			}
+		}
+
+		impl<B: crate::Buffer> core::ops::Deref for $buf<B> {
+			type Target = $name;
+
+			#[inline(always)]
+			fn deref(&self) -> &$name {
+				self.$as()
+			}
+		}
+
+		impl<B: crate::Buffer> AsRef<crate::Number> for $buf<B> {
+			#[inline(always)]
+			fn as_ref(&self) -> &crate::Number {
+				self.0.as_number()
+			}
+		}
+
+		impl<B: crate::Buffer> core::borrow::Borrow<crate::Number> for $buf<B> {
+			#[inline(always)]
+			fn borrow(&self) -> &crate::Number {
+				self.0.as_number()
+			}
+		}
+
+		impl<B: crate::Buffer> core::borrow::Borrow<$name> for $buf<B> {
+			#[inline(always)]
+			fn borrow(&self) -> &$name {
+				self.$as()
+			}
+		}
+
+		impl<B: crate::Buffer> TryFrom<crate::NumberBuf<B>> for $buf<B> {
+			type Error = crate::NumberBuf<B>;
+
+			/// Checks the given number, returning it back if it is not valid.
+			#[inline(always)]
+			fn try_from(n: crate::NumberBuf<B>) -> Result<Self, crate::NumberBuf<B>> {
+				Self::new(n)
+			}
+		}
+
+		impl<B> From<$buf<B>> for crate::NumberBuf<B> {
+			#[inline(always)]
+			fn from(n: $buf<B>) -> Self {
+				n.0
+			}
+		}
+
+		impl<B: crate::Buffer> core::fmt::Display for $buf<B> {
+			#[inline(always)]
+			fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+				self.0.fmt(f)
+			}
+		}
+
+		impl<B: crate::Buffer> core::fmt::Debug for $buf<B> {
+			#[inline(always)]
+			fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+				self.0.fmt(f)
+			}
+		}
+
+		#[cfg(feature = "serde")]
+		impl serde::Serialize for $name {
+			#[inline(always)]
+			fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+				serde::Serialize::serialize(&self.0, serializer)
+			}
+		}
+
+		#[cfg(feature = "serde")]
+		impl<B: crate::Buffer> serde::Serialize for $buf<B> {
+			#[inline(always)]
+			fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+				serde::Serialize::serialize(&self.0, serializer)
+			}
+		}
+
+		#[cfg(feature = "serde")]
+		impl<'de, B: crate::Buffer> serde::Deserialize<'de> for $buf<B> {
+			/// Deserializes a number, failing if it is not valid for this
+			/// type.
+			fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+				let n = <crate::NumberBuf<B> as serde::Deserialize>::deserialize(deserializer)?;
+				Self::new(n).map_err(|n| {
+					serde::de::Error::invalid_value(
+						serde::de::Unexpected::Other(n.as_str()),
+						&$expecting,
+					)
+				})
+			}
+		}
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Number, NumberBuf};
+	use alloc::borrow::{Borrow, ToOwned};
+	use alloc::{boxed::Box, string::ToString};
+
+	number_newtype! {
+		/// Number of at most four bytes.
+		ShortNumber,
+
+		/// Number buffer of at most four bytes.
+		ShortNumberBuf,
+		as_short_number,
+		|n: &Number| n.as_str().len() <= 4,
+		"a number of at most four bytes"
+	}
+
+	#[test]
+	fn round_trip() {
+		let n = ShortNumber::new(Number::new("-1.5").unwrap()).unwrap();
+		assert!(ShortNumber::new(Number::new("-1.25").unwrap()).is_none());
+		assert_eq!(n.to_string(), "-1.5");
+
+		let buf: ShortNumberBuf = n.to_owned();
+		assert_eq!(buf.as_short_number(), n);
+		assert_eq!(Borrow::<Number>::borrow(&buf), n.as_number());
+		assert_eq!(buf.into_number_buf().as_str(), "-1.5");
+
+		let long: NumberBuf<Box<[u8]>> = NumberBuf::new(b"12345".as_slice().into()).unwrap();
+		assert_eq!(ShortNumberBuf::try_from(long.clone()), Err(long));
+
+		let buf =
+			ShortNumberBuf::<Box<[u8]>>::new(NumberBuf::new(b"1e10".as_slice().into()).unwrap())
+				.unwrap();
+		assert_eq!(<&ShortNumber>::try_from(buf.as_number()), Ok(&*buf));
+		assert_eq!(NumberBuf::from(buf).as_str(), "1e10")
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde() {
+		let n: ShortNumberBuf = serde_json::from_str("12.5").unwrap();
+		assert_eq!(serde_json::to_string(&n).unwrap(), "12.5");
+		assert_eq!(serde_json::to_string(n.as_short_number()).unwrap(), "12.5");
+		assert!(serde_json::from_str::<ShortNumberBuf>("12.25").is_err())
+	}
+}
@@ -0,0 +1,79 @@
+//! Non-zero numbers.
+use crate::{DivOptions, Number, NumberBuf};
+
+number_newtype! {
+	/// Non-zero JSON number.
+	///
+	/// Statically guarantees that the value of the number is not zero, so
+	/// it can be used as a divisor without checks.
+	NonZeroNumber,
+
+	/// Non-zero JSON number buffer.
+	NonZeroNumberBuf,
+	as_non_zero_number,
+	|n: &Number| !n.is_zero(),
+	"a non-zero number"
+}
+
+impl Number {
+	/// Divides this number by the given non-zero number, rounding the
+	/// quotient as specified by `options`.
+	///
+	/// Contrarily to [`Number::div_with`], this never panics.
+	#[inline(always)]
+	pub fn div_non_zero(&self, rhs: &NonZeroNumber, options: DivOptions) -> NumberBuf {
+		self.div_with(rhs, options)
+	}
+
+	/// Computes the remainder of the truncated division of this number by
+	/// the given non-zero number.
+	///
+	/// Contrarily to [`Number::rem`], this never panics.
+	#[inline(always)]
+	pub fn rem_non_zero(&self, rhs: &NonZeroNumber) -> NumberBuf {
+		self.rem(rhs)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{NonZeroNumber, NonZeroNumberBuf};
+	use crate::{DivOptions, Number, NumberBuf, RoundingMode};
+
+	#[test]
+	fn new() {
+		assert!(NonZeroNumber::new(Number::new("0.5").unwrap()).is_some());
+		assert!(NonZeroNumber::new(Number::new("-1e-400").unwrap()).is_some());
+		assert!(NonZeroNumber::new(Number::new("0").unwrap()).is_none());
+		assert!(NonZeroNumber::new(Number::new("-0.0e10").unwrap()).is_none());
+
+		let zero: NumberBuf = "0.0".parse().unwrap();
+		assert_eq!(NonZeroNumberBuf::new(zero.clone()), Err(zero));
+
+		let n: NonZeroNumberBuf =
+			NonZeroNumberBuf::try_from("-2".parse::<NumberBuf>().unwrap()).unwrap();
+		assert_eq!(n.as_str(), "-2");
+		assert_eq!(NumberBuf::from(n.clone()).as_str(), "-2");
+		assert_eq!(n.as_non_zero_number().to_owned(), n)
+	}
+
+	#[test]
+	fn division() {
+		let n = Number::new("1").unwrap();
+		let d = NonZeroNumber::new(Number::new("3").unwrap()).unwrap();
+		let options = DivOptions {
+			max_fraction_digits: 3,
+			rounding: RoundingMode::HalfEven,
+		};
+		assert_eq!(n.div_non_zero(d, options).as_str(), "0.333");
+		assert_eq!(Number::new("10").unwrap().rem_non_zero(d).as_str(), "1")
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde() {
+		let n: NonZeroNumberBuf = serde_json::from_str("1.5").unwrap();
+		assert_eq!(serde_json::to_string(&n).unwrap(), "1.5");
+		assert!(serde_json::from_str::<NonZeroNumberBuf>("0.0").is_err())
+	}
+}
@@ -0,0 +1,225 @@
+//! Notation conversions.
+use crate::{decimal::Decimal, Number, NumberBuf};
+use alloc::{borrow::ToOwned, string::String};
+use core::fmt;
+
+impl Number {
+	/// Returns this number in normalized scientific notation `d.ddde<x>`.
+	///
+	/// The significand has exactly one non-zero leading digit and no
+	/// trailing zeros, and the exponent has no `+` sign nor leading zeros.
+	/// Zero is written `0e0`. This is computed by shifting the decimal point
+	/// of the lexical representation, so no digit is lost, and numbers with
+	/// the same value have the same scientific notation. Exponents beyond
+	/// [`Number::MAX_EXACT_EXPONENT`] are computed exactly too.
+	pub fn to_scientific(&self) -> NumberBuf {
+		let d = Decimal::new(self);
+		let bytes = if d.is_saturated() {
+			Decimal::saturated_scientific(self, b'e', None)
+		} else {
+			d.to_scientific(b'e', None)
+		};
+
+		unsafe { NumberBuf::new_unchecked(bytes) }
+	}
+
+	/// Maximum number of digits produced by [`Self::to_plain`].
+	pub const MAX_PLAIN_DIGITS: usize = 100_000;
+
+	/// Returns this number in plain decimal notation, expanding the exponent
+	/// part (`1.5e3` becomes `1500` and `2.5e-4` becomes `0.00025`).
+	///
+	/// If the number has no exponent part, it is returned unchanged.
+	/// Otherwise, the result has no trailing fractional zeros.
+	///
+	/// Returns `None` if the result would have more than
+	/// [`Self::MAX_PLAIN_DIGITS`] digits, since small inputs such as
+	/// `1e1000000000` expand to enormous outputs. Use
+	/// [`Self::to_plain_with_limit`] to choose another bound.
+	pub fn to_plain(&self) -> Option<NumberBuf> {
+		self.to_plain_with_limit(Self::MAX_PLAIN_DIGITS)
+	}
+
+	/// Returns this number in plain decimal notation, provided the result
+	/// has at most `max_digits` digits.
+	///
+	/// Numbers with an exponent beyond [`Number::MAX_EXACT_EXPONENT`] always
+	/// return `None`. See [`Self::to_plain`] for details.
+	pub fn to_plain_with_limit(&self, max_digits: usize) -> Option<NumberBuf> {
+		if !self.has_exponent() {
+			let digits = self.bytes().filter(u8::is_ascii_digit).count();
+			return (digits <= max_digits).then(|| self.to_owned());
+		}
+
+		let d = Decimal::new(self);
+		if d.is_saturated() {
+			return None;
+		}
+
+		let fraction_digits = d.exponent.min(0).unsigned_abs();
+		let integer_digits = d.point().max(1) as u64;
+		if integer_digits.saturating_add(fraction_digits) > max_digits as u64 {
+			return None;
+		}
+
+		let bytes = d.to_fixed(fraction_digits as usize);
+		Some(unsafe { NumberBuf::new_unchecked(bytes) })
+	}
+
+	/// Returns a displayable value rendering this number with the integer
+	/// part digits grouped by thousands using `separator`, and the given
+	/// `decimal_point` mark.
+	///
+	/// The exponent part, if any, is left untouched.
+	///
+	/// ```
+	/// use json_number::Number;
+	///
+	/// let n = Number::new("-1234567.5").unwrap();
+	/// assert_eq!(n.format_grouped(',', '.').to_string(), "-1,234,567.5");
+	/// assert_eq!(n.format_grouped('.', ',').to_string(), "-1.234.567,5");
+	/// ```
+	pub fn format_grouped(&self, separator: char, decimal_point: char) -> Grouped<'_> {
+		Grouped {
+			number: self,
+			separator,
+			decimal_point,
+		}
+	}
+}
+
+/// Number displayed with grouped integer digits.
+///
+/// Returned by [`Number::format_grouped`]. The formatter width, fill,
+/// alignment and sign flags are honored, just like for integers.
+#[derive(Clone, Copy, Debug)]
+pub struct Grouped<'a> {
+	number: &'a Number,
+	separator: char,
+	decimal_point: char,
+}
+
+impl fmt::Display for Grouped<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let s = self.number.as_str();
+
+		let (sign, unsigned) = match s.strip_prefix('-') {
+			Some(unsigned) => ("-", unsigned),
+			None => ("", s),
+		};
+
+		let integer_len = unsigned.bytes().take_while(u8::is_ascii_digit).count();
+		let (integer, rest) = unsigned.split_at(integer_len);
+
+		let mut grouped = String::with_capacity(unsigned.len() + integer_len);
+		for (i, c) in integer.chars().enumerate() {
+			if i > 0 && (integer_len - i) % 3 == 0 {
+				grouped.push(self.separator)
+			}
+
+			grouped.push(c)
+		}
+
+		match rest.strip_prefix('.') {
+			Some(rest) => {
+				grouped.push(self.decimal_point);
+				grouped.push_str(rest)
+			}
+			None => grouped.push_str(rest),
+		}
+
+		f.pad_integral(sign.is_empty(), "", &grouped)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::Number;
+
+	fn n(s: &str) -> &Number {
+		Number::new(s).unwrap()
+	}
+
+	#[test]
+	fn to_plain() {
+		assert_eq!(n("1.5e3").to_plain().unwrap().as_str(), "1500");
+		assert_eq!(n("2.5e-4").to_plain().unwrap().as_str(), "0.00025");
+		assert_eq!(n("-2.50E+1").to_plain().unwrap().as_str(), "-25");
+		assert_eq!(n("1.2345e2").to_plain().unwrap().as_str(), "123.45");
+		assert_eq!(n("0e10").to_plain().unwrap().as_str(), "0");
+		assert_eq!(n("1.50").to_plain().unwrap().as_str(), "1.50");
+		assert_eq!(
+			n("1e99999").to_plain().unwrap().as_str().len(),
+			Number::MAX_PLAIN_DIGITS
+		);
+		assert!(n("1e100000").to_plain().is_none());
+		assert!(n("1e1000000000").to_plain().is_none());
+		assert!(n("1e99999999999999999999").to_plain().is_none());
+		assert!(n("-1e-99999999999999999999").to_plain().is_none());
+		assert!(n("1e99999999999999999999")
+			.to_plain_with_limit(usize::MAX)
+			.is_none());
+
+		assert_eq!(n("1e3").to_plain_with_limit(4).unwrap().as_str(), "1000");
+		assert!(n("1e4").to_plain_with_limit(4).is_none());
+		assert_eq!(n("1e-3").to_plain_with_limit(4).unwrap().as_str(), "0.001");
+		assert!(n("1e-4").to_plain_with_limit(4).is_none());
+		assert!(n("1e1000000000").to_plain_with_limit(1000).is_none());
+		assert!(n("12345").to_plain_with_limit(4).is_none());
+	}
+
+	#[test]
+	fn format_grouped() {
+		let grouped = |s, separator, point| n(s).format_grouped(separator, point).to_string();
+		assert_eq!(grouped("0", ',', '.'), "0");
+		assert_eq!(grouped("123", ',', '.'), "123");
+		assert_eq!(grouped("1234", ',', '.'), "1,234");
+		assert_eq!(grouped("123456", ',', '.'), "123,456");
+		assert_eq!(
+			grouped("-1234567.0001", '\u{202f}', ','),
+			"-1\u{202f}234\u{202f}567,0001"
+		);
+		assert_eq!(grouped("12345.5e3", '\'', '.'), "12'345.5e3");
+		assert_eq!(grouped("12345E-3", ' ', ','), "12 345E-3");
+
+		let n = n("-1234567.5");
+		assert_eq!(
+			format!("{:>14}", n.format_grouped(',', '.')),
+			"  -1,234,567.5"
+		);
+		assert_eq!(
+			format!("{:*<14}", n.format_grouped(',', '.')),
+			"-1,234,567.5**"
+		);
+		assert_eq!(
+			format!("{:014}", n.format_grouped(',', '.')),
+			"-001,234,567.5"
+		)
+	}
+
+	#[test]
+	fn to_scientific() {
+		assert_eq!(n("1500").to_scientific().as_str(), "1.5e3");
+		assert_eq!(n("15e2").to_scientific().as_str(), "1.5e3");
+		assert_eq!(n("0.0150E+5").to_scientific().as_str(), "1.5e3");
+		assert_eq!(n("-0.00025").to_scientific().as_str(), "-2.5e-4");
+		assert_eq!(n("-0.0").to_scientific().as_str(), "0e0");
+		assert_eq!(n("3").to_scientific().as_str(), "3e0");
+		assert_eq!(
+			n("123456789012345678901234567890").to_scientific().as_str(),
+			"1.2345678901234567890123456789e29"
+		);
+		assert_eq!(
+			n("-12.50e99999999999999999999").to_scientific().as_str(),
+			"-1.25e100000000000000000000"
+		);
+		assert_eq!(
+			n("0.05E-9223372036854775807").to_scientific().as_str(),
+			"5e-9223372036854775809"
+		);
+		assert_ne!(
+			n("1e99999999999999999999").to_scientific(),
+			n("1e99999999999999999998").to_scientific()
+		)
+	}
+}
@@ -0,0 +1,1182 @@
+//! Exact arithmetic operators.
+use crate::{decimal::Decimal, Buffer, BufferMut, Number, NumberBuf, RoundingMode};
+use alloc::{borrow::ToOwned, string::ToString, vec, vec::Vec};
+use core::cmp::Ordering;
+use core::fmt;
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Mul, Neg, Rem, Sub, SubAssign};
+
+/// Division options.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DivOptions {
+	/// Maximum number of digits after the decimal point.
+	pub max_fraction_digits: u32,
+
+	/// Rounding mode used when the quotient has more fractional digits.
+	pub rounding: RoundingMode,
+}
+
+/// Limits on the size of arithmetic operation results.
+///
+/// Used by the checked arithmetic methods such as [`Number::checked_add`] to
+/// bound the memory and time spent on operations involving untrusted
+/// numbers.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ArithmeticLimits {
+	/// Maximum number of digits processed by the operation.
+	///
+	/// For additions and subtractions, this is the number of digits
+	/// between the most significant digit and the least significant digit
+	/// of both operands, plus one for the carry. For multiplications, this is
+	/// the sum of the operands' significant digit counts.
+	pub max_digits: u64,
+
+	/// Maximum absolute value of the result's exponent, once written in
+	/// scientific notation with a single leading digit.
+	pub max_exponent: u64,
+}
+
+impl Default for ArithmeticLimits {
+	/// Allows up to `1000` digits and exponents between `-1000` and `1000`.
+	fn default() -> Self {
+		Self {
+			max_digits: 1000,
+			max_exponent: 1000,
+		}
+	}
+}
+
+/// Error returned by checked arithmetic operations when a limit is exceeded.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum LimitExceeded {
+	/// The operation requires processing too many digits.
+	Digits,
+
+	/// The result exponent is too large.
+	Exponent,
+}
+
+impl fmt::Display for LimitExceeded {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Digits => f.write_str("too many digits"),
+			Self::Exponent => f.write_str("exponent too large"),
+		}
+	}
+}
+
+impl core::error::Error for LimitExceeded {}
+
+/// Checks that the given digit count is within the limits.
+fn check_digits(digits: i128, limits: &ArithmeticLimits) -> Result<(), LimitExceeded> {
+	if digits > limits.max_digits as i128 {
+		Err(LimitExceeded::Digits)
+	} else {
+		Ok(())
+	}
+}
+
+/// Checks that the result exponent is within the limits.
+fn check_result<B: Buffer>(
+	result: Decimal,
+	limits: &ArithmeticLimits,
+) -> Result<NumberBuf<B>, LimitExceeded> {
+	if !result.is_zero() && (result.point() - 1).unsigned_abs() > limits.max_exponent {
+		Err(LimitExceeded::Exponent)
+	} else {
+		Ok(result.into_number_buf())
+	}
+}
+
+/// Decomposes the operands of a checked operation, failing if one of them
+/// has a saturated exponent.
+fn check_operands(a: &Number, b: &Number) -> Result<(Decimal, Decimal), LimitExceeded> {
+	let (a, b) = (Decimal::new(a), Decimal::new(b));
+	if a.is_saturated() || b.is_saturated() {
+		Err(LimitExceeded::Exponent)
+	} else {
+		Ok((a, b))
+	}
+}
+
+/// Returns the number of digits processed when adding `a` and `b`.
+fn addition_width(a: &Decimal, b: &Decimal) -> i128 {
+	match (a.is_zero(), b.is_zero()) {
+		(true, _) => b.digits.len() as i128,
+		(_, true) => a.digits.len() as i128,
+		_ => {
+			let high = a.point().max(b.point()) as i128;
+			let low = a.exponent.min(b.exponent) as i128;
+			high - low + 1
+		}
+	}
+}
+
+/// Maximum number of zeros inserted by [`Number::scaled_by_pow10`] when
+/// moving the decimal point.
+const MAX_SCALE_PADDING: u64 = 21;
+
+impl Number {
+	/// Maximum absolute value of the exponent of a number handled by the
+	/// arithmetic operations and comparisons, once the number is written as
+	/// an integer without trailing zeros times a power of ten (`2^62`).
+	///
+	/// Larger exponents, such as the one of `1e99999999999999999999`, are
+	/// valid but cannot be computed with. Numbers with such exponents are
+	/// still compared with other numbers as long as the result does not
+	/// depend on the exact value of the exponent, but arithmetic
+	/// operations on them panic.
+	pub const MAX_EXACT_EXPONENT: u64 = 1 << 62;
+
+	/// Multiplies this number by `10^n`, exactly.
+	///
+	/// The result preserves all the digits of the lexical representation.
+	/// If the number has an exponent part, `n` is added to the exponent.
+	/// Otherwise the decimal point is moved, unless that would require
+	/// inserting more than 21 zeros, in which case an exponent part is
+	/// added.
+	pub fn scaled_by_pow10(&self, n: i32) -> NumberBuf {
+		let bytes = self.as_bytes();
+		let (negative, unsigned) = match bytes {
+			[b'-', rest @ ..] => (true, rest),
+			_ => (false, bytes),
+		};
+
+		let mut result = Vec::with_capacity(bytes.len() + 8);
+		if negative {
+			result.push(b'-')
+		}
+
+		match unsigned.iter().position(|b| matches!(b, b'e' | b'E')) {
+			Some(i) => {
+				let (mantissa, exponent) = unsigned.split_at(i);
+				let marker = exponent[0];
+				let (exponent_negative, exponent_digits) = match &exponent[1..] {
+					[b'-', rest @ ..] => (true, rest),
+					[b'+', rest @ ..] => (false, rest),
+					digits => (false, digits),
+				};
+
+				let shift = n.to_string();
+				let shift = match shift.as_bytes() {
+					[b'-', rest @ ..] => Decimal::from_ascii_integer(true, rest),
+					digits => Decimal::from_ascii_integer(false, digits),
+				};
+
+				let e = Decimal::from_ascii_integer(exponent_negative, exponent_digits).add(&shift);
+				result.extend_from_slice(mantissa);
+				result.push(marker);
+				push_integer(&mut result, &e)
+			}
+			None => {
+				let point = unsigned
+					.iter()
+					.position(|b| *b == b'.')
+					.unwrap_or(unsigned.len());
+				let digits: Vec<u8> = unsigned.iter().copied().filter(|b| *b != b'.').collect();
+				let new_point = point as i64 + n as i64;
+				let len = digits.len() as i64;
+
+				if new_point >= len {
+					if (new_point - len) as u64 > MAX_SCALE_PADDING {
+						return self.with_exponent(n);
+					}
+
+					let integer = strip_leading_zeros(&digits);
+					result.extend_from_slice(integer);
+					if integer != b"0" {
+						result.resize(result.len() + (new_point - len) as usize, b'0')
+					}
+				} else if new_point <= 0 {
+					if (-new_point) as u64 > MAX_SCALE_PADDING {
+						return self.with_exponent(n);
+					}
+
+					result.extend_from_slice(b"0.");
+					result.resize(result.len() + (-new_point) as usize, b'0');
+					result.extend_from_slice(&digits)
+				} else {
+					let (integer, fraction) = digits.split_at(new_point as usize);
+					result.extend_from_slice(strip_leading_zeros(integer));
+					result.push(b'.');
+					result.extend_from_slice(fraction)
+				}
+			}
+		}
+
+		unsafe { NumberBuf::new_unchecked(result) }
+	}
+
+	/// Computes the exact remainder of the division of this number by
+	/// `rhs`.
+	///
+	/// Just like the `%` operator on primitive numbers, the quotient is
+	/// truncated toward zero, so the remainder has the sign of `self`.
+	///
+	/// # Panics
+	///
+	/// Panics if `rhs` is zero, if an operand has an exponent greater than
+	/// [`Number::MAX_EXACT_EXPONENT`] in absolute value, or if the exponent
+	/// gap between the operands exceeds `u32::MAX`. Below that, the operands
+	/// are aligned in memory: use [`Number::checked_add`] and its siblings
+	/// to bound the cost of operations on untrusted numbers.
+	pub fn rem(&self, rhs: &Number) -> NumberBuf {
+		Decimal::new(self).rem(&Decimal::new(rhs)).into_number_buf()
+	}
+
+	/// Appends the exponent part `e<n>` to this number, which must not
+	/// already have an exponent part.
+	fn with_exponent(&self, n: i32) -> NumberBuf {
+		let mut result = self.as_bytes().to_vec();
+		result.push(b'e');
+		result.extend_from_slice(n.to_string().as_bytes());
+		unsafe { NumberBuf::new_unchecked(result) }
+	}
+
+	/// Adds `rhs` to this number, provided the operation stays within the
+	/// given `limits`.
+	///
+	/// Limits are checked before performing the operation. Operands with an
+	/// exponent greater than [`Number::MAX_EXACT_EXPONENT`] in absolute
+	/// value exceed the exponent limit.
+	pub fn checked_add(
+		&self,
+		rhs: &Number,
+		limits: &ArithmeticLimits,
+	) -> Result<NumberBuf, LimitExceeded> {
+		let (a, b) = check_operands(self, rhs)?;
+		check_digits(addition_width(&a, &b), limits)?;
+		check_result(a.add(&b), limits)
+	}
+
+	/// Subtracts `rhs` from this number, provided the operation stays within
+	/// the given `limits`.
+	///
+	/// Limits are checked before performing the operation. Operands with an
+	/// exponent greater than [`Number::MAX_EXACT_EXPONENT`] in absolute
+	/// value exceed the exponent limit.
+	pub fn checked_sub(
+		&self,
+		rhs: &Number,
+		limits: &ArithmeticLimits,
+	) -> Result<NumberBuf, LimitExceeded> {
+		let (a, b) = check_operands(self, rhs)?;
+		check_digits(addition_width(&a, &b), limits)?;
+		check_result(a.sub(&b), limits)
+	}
+
+	/// Multiplies this number by `rhs`, provided the operation stays within
+	/// the given `limits`.
+	///
+	/// Limits are checked before performing the operation. Operands with an
+	/// exponent greater than [`Number::MAX_EXACT_EXPONENT`] in absolute
+	/// value exceed the exponent limit.
+	pub fn checked_mul(
+		&self,
+		rhs: &Number,
+		limits: &ArithmeticLimits,
+	) -> Result<NumberBuf, LimitExceeded> {
+		let (a, b) = check_operands(self, rhs)?;
+		check_digits(a.digits.len() as i128 + b.digits.len() as i128, limits)?;
+		check_result(a.mul(&b), limits)
+	}
+
+	/// Returns the absolute value of this number.
+	///
+	/// This removes the leading `-` sign, if any, so `-0` becomes `0`.
+	pub fn abs(&self) -> NumberBuf {
+		match self.as_bytes() {
+			[b'-', rest @ ..] => unsafe { NumberBuf::new_unchecked(rest.to_vec()) },
+			bytes => unsafe { NumberBuf::new_unchecked(bytes.to_vec()) },
+		}
+	}
+
+	/// Returns the opposite of this number.
+	///
+	/// This toggles the leading `-` sign, preserving the rest of the lexical
+	/// representation. Just like with floats, `0` becomes `-0` and
+	/// vice versa.
+	pub fn neg(&self) -> NumberBuf {
+		let mut result = self.to_owned();
+		result.negate();
+		result
+	}
+
+	/// Divides this number by `rhs`.
+	///
+	/// Since the exact quotient may have an infinite decimal expansion, it is
+	/// rounded to `options.max_fraction_digits` fractional digits using
+	/// `options.rounding`.
+	///
+	/// # Panics
+	///
+	/// Panics if `rhs` is zero, if an operand has an exponent greater than
+	/// [`Number::MAX_EXACT_EXPONENT`] in absolute value, or if the exponent
+	/// of `self` exceeds the one of `rhs` by more than `u32::MAX` once
+	/// `options.max_fraction_digits` is added. Below that, the quotient has
+	/// as many digits as this difference (`1e1000000000 ÷ 3` has a billion
+	/// digits), and is computed in memory.
+	pub fn div_with(&self, rhs: &Number, options: DivOptions) -> NumberBuf {
+		Decimal::new(self)
+			.div(
+				&Decimal::new(rhs),
+				options.max_fraction_digits,
+				options.rounding,
+			)
+			.into_number_buf()
+	}
+
+	/// Checks that this number is within `epsilon` of `other`, that is
+	/// `|self - other| <= epsilon`.
+	///
+	/// The comparison is exact: no float conversion is involved. Always
+	/// returns `false` if `epsilon` is negative.
+	///
+	/// The difference is never computed beyond the precision of `epsilon`,
+	/// so the cost of this operation does not depend on the exponent gap
+	/// between the numbers. Numbers with an exponent greater than
+	/// [`Number::MAX_EXACT_EXPONENT`] in absolute value are only handled
+	/// when they are negligible or far apart: this returns `false` whenever
+	/// the result depends on the exact value of such an exponent.
+	pub fn approx_eq(&self, other: &Number, epsilon: &Number) -> bool {
+		let epsilon = Decimal::new(epsilon);
+		if epsilon.negative {
+			return false;
+		}
+
+		let (a, b) = (Decimal::new(self), Decimal::new(other));
+		match a.partial_cmp_signed(&b) {
+			Some(Ordering::Equal) => return true,
+			None => return false,
+			Some(_) => (),
+		}
+
+		if epsilon.is_zero() {
+			return false;
+		}
+
+		if a.is_zero() || b.is_zero() {
+			let nonzero = if a.is_zero() { &b } else { &a };
+			return matches!(nonzero.partial_cmp_abs(&epsilon), Some(o) if o != Ordering::Greater);
+		}
+
+		// Past this magnitude, numbers with different signs or orders of
+		// magnitude are too far apart.
+		let point = a.point().max(b.point());
+		if point > epsilon.point().saturating_add(1)
+			&& (a.negative != b.negative || a.point().abs_diff(b.point()) > 1)
+		{
+			return false;
+		}
+
+		if epsilon.is_saturated() {
+			// Exact numbers are lower than `10^MAX_EXACT_EXPONENT`, hence
+			// closer than a saturated `epsilon` above it.
+			return epsilon.exponent == i64::MAX && point < Self::MAX_EXACT_EXPONENT as i64;
+		}
+
+		// Saturated numbers below the last digit of `epsilon` and of the
+		// other number only matter through their digits and sign. They are
+		// replaced by exact numbers right below those digits.
+		let below = |d: &Decimal, other: &Decimal| {
+			if !d.is_saturated() {
+				return Some(d.clone());
+			}
+
+			let mut limit = epsilon.exponent;
+			if !other.is_saturated() {
+				limit = limit.min(other.exponent)
+			}
+
+			let len = d.digits.len() as i64;
+			if d.exponent == i64::MAX || len - Self::MAX_EXACT_EXPONENT as i64 > limit {
+				return None;
+			}
+
+			Some(Decimal {
+				negative: d.negative,
+				digits: d.digits.clone(),
+				exponent: limit - 1 - len,
+			})
+		};
+
+		let (Some(a), Some(b)) = (below(&a, &b), below(&b, &a)) else {
+			return false;
+		};
+
+		// Split the numbers at the last digit of `epsilon`, into a multiple
+		// of `10^exponent` and a tail in `[0, 10^exponent)`. The difference
+		// of the multiples is at most one unit away from the difference.
+		let exponent = epsilon.exponent;
+		let diff = a
+			.round(exponent, RoundingMode::Floor)
+			.sub(&b.round(exponent, RoundingMode::Floor));
+
+		match diff.cmp_abs(&epsilon) {
+			Ordering::Less => true,
+			Ordering::Greater => false,
+			Ordering::Equal => {
+				let tails = cmp_tails(&a, &b, exponent);
+				if diff.negative {
+					tails != Ordering::Less
+				} else {
+					tails != Ordering::Greater
+				}
+			}
+		}
+	}
+
+	/// Checks that the value of this number is an integer multiple of the
+	/// value of `divisor`, using exact decimal arithmetic (`4.5` is a
+	/// multiple of `0.1`).
+	///
+	/// Just like [`u64::is_multiple_of`], zero is the only multiple of zero.
+	///
+	/// The cost of this operation only depends on the number of significant
+	/// digits of the operands, not on their exponents. Returns `false` if
+	/// the result depends on the exact value of an exponent greater than
+	/// [`Number::MAX_EXACT_EXPONENT`] in absolute value.
+	pub fn is_multiple_of(&self, divisor: &Number) -> bool {
+		let (n, d) = (Decimal::new(self), Decimal::new(divisor));
+		if n.is_zero() {
+			return true;
+		}
+
+		if d.is_zero() || n.exponent < d.exponent {
+			// The last digit of `n` is not zero, so it cannot be a multiple
+			// of `d` times a power of ten.
+			return false;
+		}
+
+		// The exponent gap is unknown if an exponent is saturated, but large
+		// enough if only the one of `n` is, and `d` is not too large.
+		let max_shift = 4 * d.digits.len() as i64;
+		if d.is_saturated()
+			|| (n.is_saturated() && d.exponent > Self::MAX_EXACT_EXPONENT as i64 - max_shift)
+		{
+			return false;
+		}
+
+		// With `d = 2^x × 5^y × c`, where `c` is coprime with ten, `n` is a
+		// multiple of `d` if `c` divides the digits of `n` and `2^x × 5^y`
+		// divides the digits of `n` shifted by the exponent difference.
+		// Since `x` and `y` are lower than `4 × len(d)`, larger shifts do not
+		// change the result.
+		let shift = n.exponent.saturating_sub(d.exponent).min(max_shift);
+
+		let a = Decimal {
+			negative: false,
+			digits: n.digits,
+			exponent: shift,
+		};
+
+		let b = Decimal {
+			negative: false,
+			digits: d.digits,
+			exponent: 0,
+		};
+
+		a.rem(&b).is_zero()
+	}
+
+	/// Checks that this number has the same value as `other`, regardless of
+	/// their lexical representations.
+	///
+	/// The values are compared without computing their difference, so the
+	/// cost does not depend on the exponents of the numbers. Numbers whose
+	/// order depends on the exact value of an exponent greater than
+	/// [`Number::MAX_EXACT_EXPONENT`] in absolute value are not equal.
+	#[cfg(any(
+		feature = "serde",
+		feature = "serde_json",
+		feature = "toml",
+		feature = "wasm",
+		feature = "async-graphql"
+	))]
+	pub(crate) fn value_eq(&self, other: &Number) -> bool {
+		Decimal::new(self).partial_cmp_signed(&Decimal::new(other)) == Some(Ordering::Equal)
+	}
+}
+
+/// Compares the tails of `a` and `b` in `[0, 10^exponent)`, that is what
+/// remains of the numbers after rounding them down to a multiple of
+/// `10^exponent`.
+fn cmp_tails(a: &Decimal, b: &Decimal, exponent: i64) -> Ordering {
+	/// Digits of the given number below `10^exponent`, and whether the tail
+	/// is the complement of those digits to `10^exponent` (for negative
+	/// numbers).
+	fn digits_below(d: &Decimal, exponent: i64) -> (Decimal, bool) {
+		let mut below = d.sub(&d.round(exponent, RoundingMode::Trunc));
+		below.negative = false;
+		let complement = d.negative && !below.is_zero();
+		(below, complement)
+	}
+
+	/// Compares `a + b` with `10^exponent`, where `a` and `b` are lower than
+	/// `10^exponent`.
+	fn cmp_sum(a: &Decimal, b: &Decimal, exponent: i64) -> Ordering {
+		let (high, low) = if a.point() >= b.point() {
+			(a, b)
+		} else {
+			(b, a)
+		};
+		if low.is_zero() || low.point() <= high.exponent {
+			// No carry: `high + low < high + 10^high.exponent <= 10^exponent`.
+			return Ordering::Less;
+		}
+
+		let power = Decimal {
+			negative: false,
+			digits: vec![1],
+			exponent,
+		};
+
+		high.add(low).cmp_signed(&power)
+	}
+
+	let (a, a_complement) = digits_below(a, exponent);
+	let (b, b_complement) = digits_below(b, exponent);
+	match (a_complement, b_complement) {
+		(false, false) => a.cmp_abs(&b),
+		(true, true) => b.cmp_abs(&a),
+		(false, true) => cmp_sum(&a, &b, exponent),
+		(true, false) => cmp_sum(&a, &b, exponent).reverse(),
+	}
+}
+
+macro_rules! impl_binary_op {
+	($($tr:ident: $method:ident),*) => {
+		$(
+			impl<'a, 'b> $tr<&'b Number> for &'a Number {
+				type Output = NumberBuf;
+
+				#[inline(always)]
+				fn $method(self, rhs: &'b Number) -> NumberBuf {
+					Decimal::new(self).$method(&Decimal::new(rhs)).into_number_buf()
+				}
+			}
+
+			impl<'a, 'b, B: Buffer> $tr<&'b NumberBuf<B>> for &'a NumberBuf<B> {
+				type Output = NumberBuf<B>;
+
+				#[inline(always)]
+				fn $method(self, rhs: &'b NumberBuf<B>) -> NumberBuf<B> {
+					Decimal::new(self.as_number())
+						.$method(&Decimal::new(rhs.as_number()))
+						.into_number_buf()
+				}
+			}
+
+			impl<'b, B: Buffer> $tr<&'b Number> for NumberBuf<B> {
+				type Output = NumberBuf<B>;
+
+				#[inline(always)]
+				fn $method(self, rhs: &'b Number) -> NumberBuf<B> {
+					Decimal::new(self.as_number())
+						.$method(&Decimal::new(rhs))
+						.into_number_buf()
+				}
+			}
+
+			impl<B: Buffer> $tr for NumberBuf<B> {
+				type Output = NumberBuf<B>;
+
+				#[inline(always)]
+				fn $method(self, rhs: NumberBuf<B>) -> NumberBuf<B> {
+					self.$method(rhs.as_number())
+				}
+			}
+		)*
+	};
+}
+
+impl_binary_op!(Add: add, Sub: sub, Mul: mul, Rem: rem);
+
+macro_rules! impl_fold {
+	($($tr:ident: $method:ident, $init:expr, $op:ident),*) => {
+		$(
+			impl<B: Buffer> $tr for NumberBuf<B> {
+				fn $method<I: Iterator<Item = Self>>(iter: I) -> Self {
+					iter.fold($init, |acc, n| acc.$op(&Decimal::new(n.as_number())))
+						.into_number_buf()
+				}
+			}
+
+			impl<'a, B: Buffer> $tr<&'a NumberBuf<B>> for NumberBuf<B> {
+				fn $method<I: Iterator<Item = &'a NumberBuf<B>>>(iter: I) -> Self {
+					iter.fold($init, |acc, n| acc.$op(&Decimal::new(n.as_number())))
+						.into_number_buf()
+				}
+			}
+
+			impl<'a, B: Buffer> $tr<&'a Number> for NumberBuf<B> {
+				fn $method<I: Iterator<Item = &'a Number>>(iter: I) -> Self {
+					iter.fold($init, |acc, n| acc.$op(&Decimal::new(n)))
+						.into_number_buf()
+				}
+			}
+		)*
+	};
+}
+
+impl_fold!(Sum: sum, Decimal::zero(), add, Product: product, Decimal::one(), mul);
+
+/// Removes the leading zeros of an integer part, keeping at least one digit.
+fn strip_leading_zeros(digits: &[u8]) -> &[u8] {
+	let leading = digits.iter().take_while(|d| **d == b'0').count();
+	&digits[leading.min(digits.len() - 1)..]
+}
+
+/// Writes the given integer decimal.
+fn push_integer(bytes: &mut Vec<u8>, d: &Decimal) {
+	if d.is_zero() {
+		bytes.push(b'0')
+	} else {
+		if d.negative {
+			bytes.push(b'-')
+		}
+
+		bytes.extend(d.digits.iter().map(|d| b'0' + d));
+		bytes.resize(bytes.len() + d.exponent as usize, b'0')
+	}
+}
+
+impl<B: BufferMut> NumberBuf<B> {
+	/// Negates this number in place.
+	///
+	/// This toggles the leading `-` sign, preserving the rest of the lexical
+	/// representation. Just like with floats, `0` becomes `-0` and
+	/// vice versa. The underlying buffer is edited in place.
+	pub fn negate(&mut self) {
+		let len = self.len();
+		if self.as_bytes()[0] == b'-' {
+			self.data.as_mut_slice().copy_within(1.., 0);
+			self.data.truncate(len - 1)
+		} else {
+			self.data.push(b'-');
+			let bytes = self.data.as_mut_slice();
+			bytes.copy_within(..len, 1);
+			bytes[0] = b'-'
+		}
+	}
+
+	/// Replaces the content of the underlying buffer with the given valid
+	/// number `bytes`.
+	pub(crate) fn set_bytes(&mut self, bytes: &[u8]) {
+		self.data.truncate(0);
+		self.data.extend_from_slice(bytes)
+	}
+}
+
+/// Returns the negation of the given number buffer, rebuilding it.
+fn negated<B: Buffer>(n: &NumberBuf<B>) -> NumberBuf<B> {
+	let bytes = n.as_bytes();
+	let negated = match bytes {
+		[b'-', rest @ ..] => B::from_bytes(rest),
+		_ => {
+			let mut negated = Vec::with_capacity(bytes.len() + 1);
+			negated.push(b'-');
+			negated.extend_from_slice(bytes);
+			B::from_vec(negated)
+		}
+	};
+
+	unsafe { NumberBuf::new_unchecked(negated) }
+}
+
+impl<B: BufferMut> NumberBuf<B> {
+	/// Adds one to this number, in place.
+	///
+	/// This is equivalent to `*self += 1`.
+	#[inline(always)]
+	pub fn increment(&mut self) {
+		*self += 1
+	}
+
+	/// Subtracts one from this number, in place.
+	///
+	/// This is equivalent to `*self -= 1`.
+	#[inline(always)]
+	pub fn decrement(&mut self) {
+		*self -= 1
+	}
+
+	/// Adds the integer `delta` to this number, in place.
+	fn add_integer(&mut self, delta: i128) {
+		if delta == 0 {
+			return;
+		}
+
+		let bytes = self.as_bytes();
+
+		// Fast path for integers fitting in an `i128`.
+		if bytes.len() < 39 && !bytes.iter().any(|b| matches!(b, b'.' | b'e' | b'E')) {
+			if let Some(value) = self.as_str().parse::<i128>().unwrap().checked_add(delta) {
+				let mut buffer = [0u8; 40];
+				let mut i = buffer.len();
+				let mut magnitude = value.unsigned_abs();
+				loop {
+					i -= 1;
+					buffer[i] = b'0' + (magnitude % 10) as u8;
+					magnitude /= 10;
+					if magnitude == 0 {
+						break;
+					}
+				}
+
+				if value < 0 {
+					i -= 1;
+					buffer[i] = b'-'
+				}
+
+				self.set_bytes(&buffer[i..]);
+				return;
+			}
+		}
+
+		let result = Decimal::new(self.as_number()).add(&Decimal::from_i128(delta));
+		let result = if self.has_exponent() {
+			result.into_number_buf::<Vec<u8>>().into_buffer()
+		} else {
+			let fraction_digits = bytes
+				.iter()
+				.position(|b| *b == b'.')
+				.map(|i| bytes.len() - i - 1)
+				.unwrap_or(0);
+			result.to_fixed(fraction_digits)
+		};
+
+		self.set_bytes(&result)
+	}
+}
+
+impl<B: BufferMut> AddAssign<i64> for NumberBuf<B> {
+	/// Adds the integer `delta` to this number, in place.
+	///
+	/// If the number has no exponent part, its fractional digits are
+	/// preserved (`1.50 + 1 = 2.50`). The underlying buffer is reused, and
+	/// hence not reallocated unless the result is longer than its capacity.
+	#[inline(always)]
+	fn add_assign(&mut self, delta: i64) {
+		self.add_integer(delta as i128)
+	}
+}
+
+impl<B: BufferMut> SubAssign<i64> for NumberBuf<B> {
+	/// Subtracts the integer `delta` from this number, in place.
+	///
+	/// Just like with `+=`, fractional digits are preserved if the number
+	/// has no exponent part.
+	#[inline(always)]
+	fn sub_assign(&mut self, delta: i64) {
+		self.add_integer(-(delta as i128))
+	}
+}
+
+impl Neg for &Number {
+	type Output = NumberBuf;
+
+	#[inline(always)]
+	fn neg(self) -> NumberBuf {
+		Number::neg(self)
+	}
+}
+
+impl<B: Buffer> Neg for NumberBuf<B> {
+	type Output = NumberBuf<B>;
+
+	#[inline(always)]
+	fn neg(self) -> NumberBuf<B> {
+		negated(&self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{ArithmeticLimits, DivOptions, LimitExceeded, Number, NumberBuf, RoundingMode};
+
+	fn n(s: &str) -> &Number {
+		Number::new(s).unwrap()
+	}
+
+	#[test]
+	fn add() {
+		assert_eq!((n("0.1") + n("0.2")).as_str(), "0.3");
+		assert_eq!((n("1.10") + n("-1.1")).as_str(), "0");
+		assert_eq!((n("-5") + n("2.5")).as_str(), "-2.5");
+		assert_eq!((n("999") + n("1")).as_str(), "1000");
+		assert_eq!(
+			(n("1e30") + n("1e-30")).as_str(),
+			"1.000000000000000000000000000000000000000000000000000000000001e30"
+		);
+	}
+
+	#[test]
+	fn sub() {
+		assert_eq!((n("0.3") - n("0.1")).as_str(), "0.2");
+		assert_eq!((n("1") - n("1.5")).as_str(), "-0.5");
+		assert_eq!((n("-1") - n("-1")).as_str(), "0");
+		assert_eq!((n("100") - n("0.01")).as_str(), "99.99");
+	}
+
+	#[test]
+	fn mul() {
+		assert_eq!((n("1.5") * n("-2")).as_str(), "-3");
+		assert_eq!((n("0.1") * n("0.1")).as_str(), "0.01");
+		assert_eq!((n("-0") * n("-5")).as_str(), "0");
+		assert_eq!((n("1e200") * n("1e200")).as_str(), "1e400");
+		assert_eq!(
+			(n("123456789") * n("987654321")).as_str(),
+			"121932631112635269"
+		);
+	}
+
+	#[test]
+	fn checked() {
+		let limits = ArithmeticLimits {
+			max_digits: 10,
+			max_exponent: 20,
+		};
+
+		assert_eq!(
+			n("1.5").checked_add(n("2.25"), &limits).unwrap().as_str(),
+			"3.75"
+		);
+		assert_eq!(
+			n("1e5").checked_add(n("1e-5"), &limits),
+			Err(LimitExceeded::Digits)
+		);
+		assert_eq!(
+			n("1e100").checked_sub(n("0"), &limits),
+			Err(LimitExceeded::Exponent)
+		);
+		assert_eq!(
+			n("1e100")
+				.checked_sub(n("1e100"), &limits)
+				.unwrap()
+				.as_str(),
+			"0"
+		);
+		assert_eq!(
+			n("123456").checked_mul(n("123456"), &limits),
+			Err(LimitExceeded::Digits)
+		);
+		assert_eq!(
+			n("1e15").checked_mul(n("1e15"), &limits),
+			Err(LimitExceeded::Exponent)
+		);
+		assert_eq!(
+			n("1e10").checked_mul(n("-1e10"), &limits).unwrap().as_str(),
+			"-100000000000000000000"
+		);
+		assert_eq!(
+			n("1e99999999999999999999").checked_sub(n("1e99999999999999999999"), &limits),
+			Err(LimitExceeded::Exponent)
+		);
+		assert_eq!(
+			n("1e-99999999999999999999").checked_mul(n("0"), &limits),
+			Err(LimitExceeded::Exponent)
+		);
+		assert_eq!(
+			n("1e5000000000").checked_add(n("1"), &ArithmeticLimits::default()),
+			Err(LimitExceeded::Digits)
+		);
+	}
+
+	#[test]
+	fn scaled_by_pow10() {
+		let scaled = |s: &str, n: i32| self::n(s).scaled_by_pow10(n).as_str().to_owned();
+		assert_eq!(scaled("19.99", 2), "1999");
+		assert_eq!(scaled("1999", -2), "19.99");
+		assert_eq!(scaled("1.50", 1), "15.0");
+		assert_eq!(scaled("1.5", 3), "1500");
+		assert_eq!(scaled("-0.05", 2), "-5");
+		assert_eq!(scaled("0.05", 1), "0.5");
+		assert_eq!(scaled("12", -3), "0.012");
+		assert_eq!(scaled("-0", 3), "-0");
+		assert_eq!(scaled("7", 0), "7");
+		assert_eq!(scaled("1", 30), "1e30");
+		assert_eq!(scaled("1.5", -30), "1.5e-30");
+		assert_eq!(scaled("1.5e3", 2), "1.5e5");
+		assert_eq!(scaled("1.5E+3", -5), "1.5E-2");
+		assert_eq!(scaled("-2e-4", 4), "-2e0");
+		assert_eq!(
+			scaled("1e99999999999999999999", 1),
+			"1e100000000000000000000"
+		);
+	}
+
+	#[test]
+	fn approx_eq() {
+		assert!(n("0.1").approx_eq(n("0.1"), n("0")));
+		assert!(n("1e-1").approx_eq(n("0.10"), n("-0")));
+		assert!(n("1.05").approx_eq(n("1"), n("0.05")));
+		assert!(!n("1.0500001").approx_eq(n("1"), n("0.05")));
+		assert!(n("-1").approx_eq(n("-1.04"), n("5e-2")));
+		assert!(n("1e20").approx_eq(n("100000000000000000001"), n("1")));
+		assert!(!n("1").approx_eq(n("1"), n("-1e-9")));
+		assert!(!n("1").approx_eq(n("1e99999999999999999999"), n("1")));
+		assert!(!n("-1e999999999").approx_eq(n("1e999999999"), n("1")));
+		assert!(n("1").approx_eq(n("1.0000000000000000001"), n("1e-10")));
+		assert!(n("1").approx_eq(n("1e-99999999999999999999"), n("1")));
+		assert!(!n("2").approx_eq(n("-1e-99999999999999999999"), n("2")));
+		assert!(!n("1e9223372036854775807").approx_eq(n("1e9223372036854775808"), n("1")));
+		assert!(n("1").approx_eq(n("2"), n("1e99999999999999999999")));
+		assert!(!n("1").approx_eq(n("2"), n("1e-99999999999999999999")));
+		assert!(n("1e-99999999999999999999").approx_eq(n("-1e-99999999999999999999"), n("1")));
+		assert!(n("1.999").approx_eq(n("-1e-99999999999999999999"), n("2")));
+		assert!(n("0").approx_eq(n("1e-99999999999999999999"), n("1e-9")));
+		assert!(n("1e100").approx_eq(n("1e100"), n("1e-99999999999999999999")));
+		assert!(!n("1e100").approx_eq(n("2e100"), n("1e-99999999999999999999")));
+
+		// Tails below the last digit of epsilon.
+		assert!(n("3.25").approx_eq(n("1.5"), n("2")));
+		assert!(!n("3.55").approx_eq(n("1.5"), n("2")));
+		assert!(n("3.5").approx_eq(n("1.5"), n("2")));
+		assert!(n("-3.25").approx_eq(n("-1.5"), n("2")));
+		assert!(!n("-3.55").approx_eq(n("-1.5"), n("2")));
+		assert!(n("1.5").approx_eq(n("3.5"), n("2")));
+		assert!(!n("1.49").approx_eq(n("3.5"), n("2")));
+		let big = "10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001";
+		assert!(n(big).approx_eq(n("1e100"), n("1")));
+		assert!(!n(big).approx_eq(n("1e100"), n("0.5")))
+	}
+
+	#[cfg(any(
+		feature = "serde",
+		feature = "serde_json",
+		feature = "toml",
+		feature = "wasm",
+		feature = "async-graphql"
+	))]
+	#[test]
+	fn value_eq() {
+		assert!(n("1.50e1").value_eq(n("15")));
+		assert!(n("-0.0").value_eq(n("0e10")));
+		assert!(!n("1").value_eq(n("-1")));
+		assert!(!n("1").value_eq(n("1e99999999999999999999")));
+		assert!(!n("1e-99999999999999999999").value_eq(n("1e99999999999999999999")));
+		assert!(!n("1e9223372036854775807").value_eq(n("1e9223372036854775808")))
+	}
+
+	#[test]
+	fn is_multiple_of() {
+		fn multiple(a: &str, b: &str) -> bool {
+			Number::new(a)
+				.unwrap()
+				.is_multiple_of(Number::new(b).unwrap())
+		}
+
+		assert!(multiple("4.5", "0.1"));
+		assert!(multiple("4.5", "1.5"));
+		assert!(multiple("-4.5", "1.5"));
+		assert!(multiple("4.5", "-1.5"));
+		assert!(multiple("0.0075", "0.0001"));
+		assert!(multiple("1e10", "2"));
+		assert!(multiple("1e1000000000", "0.5e3"));
+		assert!(multiple("1e1000000000", "1024"));
+		assert!(multiple("1e99999999999999999999", "2"));
+		assert!(!multiple(
+			"1e99999999999999999999",
+			"1e99999999999999999998"
+		));
+		assert!(!multiple("1e-99999999999999999999", "1"));
+		assert!(multiple("0", "7"));
+		assert!(multiple("0", "0"));
+		assert!(!multiple("4.5", "0.2"));
+		assert!(!multiple("0.0075", "0.001"));
+		assert!(!multiple("1e1000000000", "3"));
+		assert!(!multiple("7", "0"));
+		assert!(!multiple("10", "1e2"));
+		assert!(multiple("19.99", "0.01"));
+		assert!(multiple("1e2", "10"));
+		assert!(!multiple("1e2", "8.1"))
+	}
+
+	#[test]
+	fn rem() {
+		assert_eq!(n("4.5").rem(n("0.1")).as_str(), "0");
+		assert_eq!(n("0.3").rem(n("0.1")).as_str(), "0");
+		assert_eq!(n("7").rem(n("2.5")).as_str(), "2");
+		assert_eq!(n("-7").rem(n("2.5")).as_str(), "-2");
+		assert_eq!(n("7").rem(n("-2.5")).as_str(), "2");
+		assert_eq!(n("1.5").rem(n("10")).as_str(), "1.5");
+		assert_eq!(n("1e3").rem(n("7")).as_str(), "6");
+		assert_eq!((n("10.25") % n("1e-1")).as_str(), "0.05");
+	}
+
+	#[test]
+	#[should_panic(expected = "exponent gap too large")]
+	fn add_exponent_gap() {
+		let _ = n("1e5000000000") + n("1");
+	}
+
+	#[test]
+	#[should_panic(expected = "exponent gap too large")]
+	fn rem_exponent_gap() {
+		let _ = n("1e5000000000") % n("3e-1");
+	}
+
+	#[test]
+	#[should_panic]
+	fn rem_by_zero() {
+		n("1").rem(n("0"));
+	}
+
+	#[test]
+	fn sum() {
+		let numbers: Vec<NumberBuf> = ["19.99", "0.01", "-5", "1e2"]
+			.iter()
+			.map(|s| s.parse().unwrap())
+			.collect();
+		assert_eq!(numbers.iter().sum::<NumberBuf>().as_str(), "115");
+		assert_eq!(
+			numbers
+				.iter()
+				.map(NumberBuf::as_number)
+				.sum::<NumberBuf>()
+				.as_str(),
+			"115"
+		);
+		assert_eq!(numbers.into_iter().sum::<NumberBuf>().as_str(), "115");
+		assert_eq!(
+			core::iter::empty::<&Number>().sum::<NumberBuf>().as_str(),
+			"0"
+		);
+	}
+
+	#[test]
+	fn product() {
+		let numbers = [n("1.5"), n("-2"), n("0.1")];
+		assert_eq!(
+			numbers.iter().copied().product::<NumberBuf>().as_str(),
+			"-0.3"
+		);
+		assert_eq!(
+			core::iter::empty::<&Number>()
+				.product::<NumberBuf>()
+				.as_str(),
+			"1"
+		);
+	}
+
+	#[test]
+	fn increment() {
+		let mut counter: NumberBuf = "41".parse().unwrap();
+		let capacity = counter.buffer().capacity();
+		counter.increment();
+		assert_eq!(counter.as_str(), "42");
+		assert_eq!(counter.buffer().capacity(), capacity);
+		counter.decrement();
+		counter.decrement();
+		assert_eq!(counter.as_str(), "40");
+		counter -= 41;
+		assert_eq!(counter.as_str(), "-1");
+		counter += i64::MAX;
+		assert_eq!(counter.as_str(), "9223372036854775806");
+
+		let mut big: NumberBuf = "99999999999999999999999999999999999999999".parse().unwrap();
+		big.increment();
+		assert_eq!(big.as_str(), "100000000000000000000000000000000000000000");
+
+		let mut amount: NumberBuf = "1.50".parse().unwrap();
+		amount += 1;
+		assert_eq!(amount.as_str(), "2.50");
+		amount -= 3;
+		assert_eq!(amount.as_str(), "-0.50");
+
+		let mut scientific: NumberBuf = "1e3".parse().unwrap();
+		scientific.increment();
+		assert_eq!(scientific.as_str(), "1001");
+	}
+
+	#[test]
+	fn abs() {
+		assert_eq!(n("1.50").abs().as_str(), "1.50");
+		assert_eq!(n("-1.50").abs().as_str(), "1.50");
+		assert_eq!(n("-0").abs().as_str(), "0");
+	}
+
+	#[test]
+	fn neg() {
+		assert_eq!(n("1.50").neg().as_str(), "-1.50");
+		assert_eq!(n("-1e5").neg().as_str(), "1e5");
+		assert_eq!(n("0").neg().as_str(), "-0");
+		assert_eq!(n("-0.0").neg().as_str(), "0.0");
+
+		let mut a: NumberBuf = "12".parse().unwrap();
+		a.negate();
+		assert_eq!(a.as_str(), "-12");
+		assert_eq!((-a).as_str(), "12");
+		assert_eq!((-n("3")).as_str(), "-3");
+
+		let mut b: NumberBuf<Box<[u8]>> = "-7".parse().unwrap();
+		b = -b;
+		assert_eq!(b.as_str(), "7");
+	}
+
+	#[cfg(feature = "smallnumberbuf")]
+	#[test]
+	fn in_place_small() {
+		let mut a: crate::SmallNumberBuf = "-99".parse().unwrap();
+		a.increment();
+		assert_eq!(a.as_str(), "-98");
+		a.negate();
+		assert_eq!(a.as_str(), "98");
+		a += 2;
+		assert_eq!(a.as_str(), "100");
+		assert!(!a.buffer().spilled());
+	}
+
+	fn div(a: &str, b: &str, max_fraction_digits: u32, rounding: RoundingMode) -> String {
+		let options = DivOptions {
+			max_fraction_digits,
+			rounding,
+		};
+
+		n(a).div_with(n(b), options).as_str().to_owned()
+	}
+
+	#[test]
+	fn div_with() {
+		use RoundingMode::*;
+		assert_eq!(div("1", "4", 2, HalfEven), "0.25");
+		assert_eq!(div("1", "3", 5, HalfEven), "0.33333");
+		assert_eq!(div("2", "3", 5, HalfEven), "0.66667");
+		assert_eq!(div("2", "3", 5, Trunc), "0.66666");
+		assert_eq!(div("-2", "3", 2, Floor), "-0.67");
+		assert_eq!(div("-2", "3", 2, Ceil), "-0.66");
+		assert_eq!(div("1", "8", 2, HalfEven), "0.12");
+		assert_eq!(div("3", "8", 2, HalfEven), "0.38");
+		assert_eq!(div("1", "8", 2, HalfUp), "0.13");
+		assert_eq!(div("-1", "8", 2, HalfUp), "-0.13");
+		assert_eq!(div("1.5e3", "-0.5", 0, HalfEven), "-3000");
+		assert_eq!(div("1", "1e10", 2, HalfEven), "0");
+		assert_eq!(div("1", "1e10", 2, Ceil), "0.01");
+		assert_eq!(div("1", "1e5000000000", 2, Ceil), "0.01");
+		assert_eq!(div("-1e-5000000000", "3", 2, Floor), "-0.01");
+		assert_eq!(div("-1", "1e10", 2, Floor), "-0.01");
+		assert_eq!(div("0", "7", 2, Ceil), "0");
+	}
+
+	#[test]
+	#[should_panic]
+	fn div_by_zero() {
+		div("1", "0.0", 2, RoundingMode::HalfEven);
+	}
+
+	#[test]
+	#[should_panic(expected = "exponent gap too large")]
+	fn div_exponent_gap() {
+		div("1e5000000000", "3", 2, RoundingMode::HalfEven);
+	}
+
+	#[test]
+	fn number_buf_operators() {
+		let a: NumberBuf = "19.99".parse().unwrap();
+		let b: NumberBuf = "5.01".parse().unwrap();
+		assert_eq!((&a + &b).as_str(), "25");
+		assert_eq!((a.clone() - b.as_number()).as_str(), "14.98");
+		assert_eq!((a * b).as_str(), "100.1499");
+	}
+}
@@ -0,0 +1,190 @@
+//! Compact binary encoding.
+//!
+//! A packed number starts with a LEB128 varint header holding the
+//! zigzag-encoded decimal exponent shifted left by one bit, with the sign in
+//! the lowest bit. It is followed by the significant digits in packed BCD,
+//! two digits per byte with the most significant first. An odd number of
+//! digits is padded with a final `0xf` nibble.
+use crate::{decimal::Decimal, Buffer, Number, NumberBuf};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Maximum length of the header varint, enough to hold 65 bits.
+const MAX_HEADER_LEN: usize = 10;
+
+/// Padding nibble.
+const PADDING: u8 = 0xf;
+
+/// Packed number decoding error.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PackedNumberError {
+	/// The header is truncated or out of range.
+	InvalidHeader,
+
+	/// A nibble is not a decimal digit, or the padding is misplaced.
+	InvalidDigit,
+}
+
+impl fmt::Display for PackedNumberError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::InvalidHeader => f.write_str("invalid packed number header"),
+			Self::InvalidDigit => f.write_str("invalid packed number digit"),
+		}
+	}
+}
+
+impl core::error::Error for PackedNumberError {}
+
+impl Number {
+	/// Encodes the value of this number in a compact binary form, decoded by
+	/// [`NumberBuf::from_packed`].
+	///
+	/// The encoding takes about half the size of the digits, plus a
+	/// header of usually one or two bytes. Only the value is preserved, not
+	/// its lexical representation: `1.50`, `15e-1` and `0.15E1` are all
+	/// encoded the same way.
+	pub fn to_packed(&self) -> Vec<u8> {
+		let d = Decimal::new(self);
+		let mut bytes = Vec::with_capacity(2 + d.digits.len().div_ceil(2));
+
+		let exponent = ((d.exponent << 1) ^ (d.exponent >> 63)) as u64;
+		let mut header = ((exponent as u128) << 1) | d.negative as u128;
+		loop {
+			let byte = (header & 0x7f) as u8;
+			header >>= 7;
+			if header == 0 {
+				bytes.push(byte);
+				break;
+			}
+
+			bytes.push(byte | 0x80)
+		}
+
+		for pair in d.digits.chunks(2) {
+			let low = pair.get(1).copied().unwrap_or(PADDING);
+			bytes.push((pair[0] << 4) | low)
+		}
+
+		bytes
+	}
+}
+
+impl<B: Buffer> NumberBuf<B> {
+	/// Decodes a number encoded with [`Number::to_packed`].
+	///
+	/// The result has no trailing fractional zeros, and is written in
+	/// scientific notation when the decimal point is more than 21 digits
+	/// after the first significant digit or more than 6 digits before it.
+	pub fn from_packed(bytes: &[u8]) -> Result<Self, PackedNumberError> {
+		let mut header: u128 = 0;
+		let mut len = 0;
+		loop {
+			let byte = *bytes.get(len).ok_or(PackedNumberError::InvalidHeader)?;
+			if len == MAX_HEADER_LEN {
+				return Err(PackedNumberError::InvalidHeader);
+			}
+
+			header |= ((byte & 0x7f) as u128) << (7 * len);
+			len += 1;
+
+			if byte & 0x80 == 0 {
+				break;
+			}
+		}
+
+		let exponent = u64::try_from(header >> 1).map_err(|_| PackedNumberError::InvalidHeader)?;
+		let mut d = Decimal {
+			negative: header & 1 == 1,
+			digits: Vec::with_capacity((bytes.len() - len) * 2),
+			exponent: (exponent >> 1) as i64 ^ -((exponent & 1) as i64),
+		};
+
+		let packed = &bytes[len..];
+		for (i, byte) in packed.iter().enumerate() {
+			let (high, low) = (byte >> 4, byte & 0xf);
+			if high > 9 {
+				return Err(PackedNumberError::InvalidDigit);
+			}
+
+			d.digits.push(high);
+
+			if low == PADDING && i == packed.len() - 1 {
+				break;
+			}
+
+			if low > 9 {
+				return Err(PackedNumberError::InvalidDigit);
+			}
+
+			d.digits.push(low)
+		}
+
+		d.normalize();
+		Ok(d.into_number_buf())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::PackedNumberError;
+	use crate::{Number, NumberBuf};
+
+	fn packed(s: &str) -> Vec<u8> {
+		Number::new(s).unwrap().to_packed()
+	}
+
+	fn unpacked(bytes: &[u8]) -> Result<NumberBuf, PackedNumberError> {
+		NumberBuf::from_packed(bytes)
+	}
+
+	#[test]
+	fn encoding() {
+		assert_eq!(packed("0"), [0x00]);
+		assert_eq!(packed("-0.0"), [0x00]);
+		assert_eq!(packed("12345"), [0x00, 0x12, 0x34, 0x5f]);
+		assert_eq!(packed("-1.5"), [0x03, 0x15]);
+		assert_eq!(packed("1.50"), packed("15e-1"));
+		assert_eq!(packed("1e64"), [0x80, 0x02, 0x1f])
+	}
+
+	#[test]
+	fn round_trip() {
+		for (input, expected) in [
+			("0", "0"),
+			("-0", "0"),
+			("12345", "12345"),
+			("-1.50", "-1.5"),
+			("0.000001", "0.000001"),
+			("12.5e-10", "1.25e-9"),
+			("1e100", "1e100"),
+			(
+				"-123456789012345678901234567890",
+				"-1.2345678901234567890123456789e29",
+			),
+		] {
+			let n = Number::new(input).unwrap();
+			let m = unpacked(&n.to_packed()).unwrap();
+			assert_eq!(m.as_str(), expected)
+		}
+	}
+
+	#[test]
+	fn errors() {
+		assert_eq!(unpacked(&[]), Err(PackedNumberError::InvalidHeader));
+		assert_eq!(unpacked(&[0x80]), Err(PackedNumberError::InvalidHeader));
+		assert_eq!(unpacked(&[0xff; 20]), Err(PackedNumberError::InvalidHeader));
+		assert_eq!(
+			unpacked(&[0x00, 0x1a]),
+			Err(PackedNumberError::InvalidDigit)
+		);
+		assert_eq!(
+			unpacked(&[0x00, 0xf1]),
+			Err(PackedNumberError::InvalidDigit)
+		);
+		assert_eq!(
+			unpacked(&[0x00, 0x1f, 0x23]),
+			Err(PackedNumberError::InvalidDigit)
+		)
+	}
+}
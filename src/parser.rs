@@ -0,0 +1,553 @@
+//! Number validation state machine.
+use crate::{Buffer, InvalidNumber, Number, NumberBuf};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+/// Detailed number parsing error.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ParseError {
+	/// Byte offset of the first invalid byte, or length of the input if it
+	/// ended prematurely.
+	pub offset: usize,
+
+	/// Error kind.
+	pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"invalid JSON number: {} at offset {}",
+			self.kind, self.offset
+		)
+	}
+}
+
+impl core::error::Error for ParseError {}
+
+/// Number parsing error kind.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParseErrorKind {
+	/// The input is empty.
+	Empty,
+
+	/// The integer part starts with a `0` followed by other digits.
+	LeadingZero,
+
+	/// The `-` sign is not followed by any digit.
+	MissingIntegerDigits,
+
+	/// The decimal point is not followed by any digit.
+	MissingFractionDigits,
+
+	/// The exponent marker (`e` or `E`) or sign is not followed by any digit.
+	MissingExponentDigits,
+
+	/// Unexpected byte.
+	UnexpectedByte(u8),
+}
+
+impl fmt::Display for ParseErrorKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Empty => f.write_str("empty input"),
+			Self::LeadingZero => f.write_str("leading zero"),
+			Self::MissingIntegerDigits => f.write_str("missing integer digits"),
+			Self::MissingFractionDigits => f.write_str("missing fraction digits"),
+			Self::MissingExponentDigits => f.write_str("missing exponent digits"),
+			Self::UnexpectedByte(b) => write!(f, "unexpected byte `{}`", b.escape_ascii()),
+		}
+	}
+}
+
+impl Number {
+	/// Creates a new number by parsing the given input `data`, returning a
+	/// detailed error on failure.
+	///
+	/// This is equivalent to [`Number::new`], but the error describes why
+	/// and where the parsing failed, which is useful to produce diagnostics.
+	pub fn new_detailed<B: AsRef<[u8]> + ?Sized>(data: &B) -> Result<&Number, ParseError> {
+		let s = data.as_ref();
+
+		let mut state = State::Init;
+		let mut i = 0;
+		while i < s.len() {
+			match state.next(s[i]) {
+				Some(next) => state = next,
+				None => {
+					return Err(ParseError {
+						offset: i,
+						kind: state.error(Some(s[i])),
+					})
+				}
+			}
+
+			i += 1;
+			if state.is_digit_run() {
+				i += digit_run(&s[i..])
+			}
+		}
+
+		if state.is_final() {
+			Ok(unsafe { Number::new_unchecked(s) })
+		} else {
+			Err(ParseError {
+				offset: s.len(),
+				kind: state.error(None),
+			})
+		}
+	}
+}
+
+/// Incremental number parser.
+///
+/// Bytes are fed one at a time with [`push`](Self::push) (or by chunks with
+/// [`push_bytes`](Self::push_bytes)), allowing numbers split across
+/// multiple input buffers to be validated without first concatenating them.
+/// Once the first byte that is not part of the number is found, the number
+/// is retrieved with [`finish`](Self::finish).
+///
+/// ```
+/// use json_number::{NumberBuf, NumberParser, Step};
+///
+/// let mut parser = NumberParser::new();
+/// assert_eq!(parser.push_bytes(b"12."), 3);
+/// assert_eq!(parser.push_bytes(b"5e3, "), 3);
+/// assert_eq!(parser.push(b','), Step::Rejected);
+///
+/// let n: NumberBuf = parser.finish().unwrap();
+/// assert_eq!(n.as_str(), "12.5e3")
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct NumberParser {
+	state: State,
+	data: Vec<u8>,
+}
+
+/// Outcome of a [`NumberParser::push`] call.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Step {
+	/// The byte is part of the number, but the number is not yet complete
+	/// (for instance after a `-` sign or a decimal point).
+	Incomplete,
+
+	/// The byte is part of the number, and the bytes pushed so far form a
+	/// valid number.
+	Complete,
+
+	/// The byte is not part of the number and has not been consumed.
+	Rejected,
+}
+
+impl NumberParser {
+	/// Creates a new parser.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Pushes the next byte of the number.
+	pub fn push(&mut self, b: u8) -> Step {
+		match self.state.next(b) {
+			Some(next) => {
+				self.state = next;
+				self.data.push(b);
+				if next.is_final() {
+					Step::Complete
+				} else {
+					Step::Incomplete
+				}
+			}
+			None => Step::Rejected,
+		}
+	}
+
+	/// Pushes the given bytes until one of them is rejected.
+	///
+	/// Returns the number of consumed bytes. If it is lower than
+	/// `bytes.len()`, the byte at this index is not part of the number.
+	pub fn push_bytes(&mut self, bytes: &[u8]) -> usize {
+		for (i, b) in bytes.iter().enumerate() {
+			if self.push(*b) == Step::Rejected {
+				return i;
+			}
+		}
+
+		bytes.len()
+	}
+
+	/// Checks if the bytes pushed so far form a valid number.
+	pub fn is_complete(&self) -> bool {
+		self.state.is_final()
+	}
+
+	/// Returns the bytes pushed so far.
+	pub fn as_bytes(&self) -> &[u8] {
+		&self.data
+	}
+
+	/// Returns the parsed number.
+	///
+	/// Fails if the bytes pushed so far do not form a valid number, returning
+	/// those bytes.
+	pub fn finish<B: Buffer>(self) -> Result<NumberBuf<B>, InvalidNumber<Vec<u8>>> {
+		if self.state.is_final() {
+			Ok(unsafe { NumberBuf::new_unchecked(B::from_vec(self.data)) })
+		} else {
+			Err(InvalidNumber(self.data))
+		}
+	}
+}
+
+impl<B: Buffer> NumberBuf<B> {
+	/// Creates a number buffer from the given bytes, validating them while
+	/// they are collected.
+	///
+	/// On failure, the error holds the bytes collected up to (and including)
+	/// the first invalid byte.
+	pub fn from_iter_checked(
+		bytes: impl IntoIterator<Item = u8>,
+	) -> Result<Self, InvalidNumber<Vec<u8>>> {
+		let mut parser = NumberParser::new();
+
+		for b in bytes {
+			if parser.push(b) == Step::Rejected {
+				let mut data = parser.data;
+				data.push(b);
+				return Err(InvalidNumber(data));
+			}
+		}
+
+		parser.finish()
+	}
+
+	/// Creates a number buffer from the given characters, validating them
+	/// while they are collected.
+	///
+	/// On failure, the error holds the characters collected up to (and
+	/// including) the first invalid character.
+	pub fn from_chars_checked(
+		chars: impl IntoIterator<Item = char>,
+	) -> Result<Self, InvalidNumber<String>> {
+		let mut parser = NumberParser::new();
+
+		for c in chars {
+			if !c.is_ascii() || parser.push(c as u8) == Step::Rejected {
+				// Only ASCII bytes have been pushed so far.
+				let mut data = unsafe { String::from_utf8_unchecked(parser.data) };
+				data.push(c);
+				return Err(InvalidNumber(data));
+			}
+		}
+
+		parser.finish().map_err(|e| {
+			// Only ASCII bytes have been pushed.
+			InvalidNumber(unsafe { String::from_utf8_unchecked(e.0) })
+		})
+	}
+
+	/// Reads a number from the given `reader`.
+	///
+	/// Bytes are read until the first byte that is not part of the number,
+	/// which is left unconsumed in the reader.
+	///
+	/// Fails with an [`io::ErrorKind::InvalidData`] error if the bytes read
+	/// do not form a valid number.
+	///
+	/// [`io::ErrorKind::InvalidData`]: std::io::ErrorKind::InvalidData
+	#[cfg(feature = "std")]
+	pub fn read_from(mut reader: impl std::io::BufRead) -> std::io::Result<Self> {
+		let mut parser = NumberParser::new();
+
+		loop {
+			let bytes = reader.fill_buf()?;
+			let len = bytes.len();
+			let consumed = parser.push_bytes(bytes);
+			reader.consume(consumed);
+
+			if consumed < len || len == 0 {
+				break;
+			}
+		}
+
+		parser.finish().map_err(|e| {
+			std::io::Error::new(
+				std::io::ErrorKind::InvalidData,
+				InvalidNumber(String::from_utf8_lossy(&e.0).into_owned()),
+			)
+		})
+	}
+}
+
+/// Number validation automaton state.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub(crate) enum State {
+	#[default]
+	Init,
+	FirstDigit,
+	Zero,
+	NonZero,
+	FractionalFirst,
+	FractionalRest,
+	ExponentSign,
+	ExponentFirst,
+	ExponentRest,
+}
+
+impl State {
+	/// Returns the state reached after reading `b`, if any.
+	#[inline(always)]
+	pub const fn next(self, b: u8) -> Option<Self> {
+		match self {
+			Self::Init => match b {
+				b'-' => Some(Self::FirstDigit),
+				b'0' => Some(Self::Zero),
+				b'1'..=b'9' => Some(Self::NonZero),
+				_ => None,
+			},
+			Self::FirstDigit => match b {
+				b'0' => Some(Self::Zero),
+				b'1'..=b'9' => Some(Self::NonZero),
+				_ => None,
+			},
+			Self::Zero => match b {
+				b'.' => Some(Self::FractionalFirst),
+				b'e' | b'E' => Some(Self::ExponentSign),
+				_ => None,
+			},
+			Self::NonZero => match b {
+				b'0'..=b'9' => Some(Self::NonZero),
+				b'.' => Some(Self::FractionalFirst),
+				b'e' | b'E' => Some(Self::ExponentSign),
+				_ => None,
+			},
+			Self::FractionalFirst => match b {
+				b'0'..=b'9' => Some(Self::FractionalRest),
+				_ => None,
+			},
+			Self::FractionalRest => match b {
+				b'0'..=b'9' => Some(Self::FractionalRest),
+				b'e' | b'E' => Some(Self::ExponentSign),
+				_ => None,
+			},
+			Self::ExponentSign => match b {
+				b'+' | b'-' => Some(Self::ExponentFirst),
+				b'0'..=b'9' => Some(Self::ExponentRest),
+				_ => None,
+			},
+			Self::ExponentFirst => match b {
+				b'0'..=b'9' => Some(Self::ExponentRest),
+				_ => None,
+			},
+			Self::ExponentRest => match b {
+				b'0'..=b'9' => Some(Self::ExponentRest),
+				_ => None,
+			},
+		}
+	}
+
+	/// Returns the kind of error caused by reading the given byte in this
+	/// state (or reaching the end of the input if `b` is `None`).
+	fn error(self, b: Option<u8>) -> ParseErrorKind {
+		match (self, b) {
+			(Self::Init, None) => ParseErrorKind::Empty,
+			(Self::FirstDigit, None) => ParseErrorKind::MissingIntegerDigits,
+			(Self::Zero, Some(b'0'..=b'9')) => ParseErrorKind::LeadingZero,
+			(Self::FractionalFirst, _) => ParseErrorKind::MissingFractionDigits,
+			(Self::ExponentSign | Self::ExponentFirst, _) => ParseErrorKind::MissingExponentDigits,
+			(_, Some(b)) => ParseErrorKind::UnexpectedByte(b),
+			(_, None) => unreachable!(),
+		}
+	}
+
+	/// Checks if this state loops on digits.
+	///
+	/// In such a state, a run of digits can be skipped at once using
+	/// [`digit_run`].
+	#[inline(always)]
+	pub fn is_digit_run(self) -> bool {
+		matches!(
+			self,
+			Self::NonZero | Self::FractionalRest | Self::ExponentRest
+		)
+	}
+
+	/// Checks if the input read so far is a valid number.
+	#[inline(always)]
+	pub const fn is_final(self) -> bool {
+		matches!(
+			self,
+			Self::Zero | Self::NonZero | Self::FractionalRest | Self::ExponentRest
+		)
+	}
+}
+
+/// Returns the length of the run of ASCII digits at the start of `bytes`.
+#[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+#[inline(always)]
+pub(crate) fn digit_run(bytes: &[u8]) -> usize {
+	bytes.iter().take_while(|b| b.is_ascii_digit()).count()
+}
+
+/// Returns the length of the run of ASCII digits at the start of `bytes`.
+///
+/// Digits are checked 16 bytes at a time using SSE2 instructions, which are
+/// always available on `x86_64`.
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+#[inline(always)]
+pub(crate) fn digit_run(bytes: &[u8]) -> usize {
+	use core::arch::x86_64::{
+		__m128i, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_max_epu8, _mm_movemask_epi8, _mm_set1_epi8,
+		_mm_sub_epi8,
+	};
+
+	let mut len = 0;
+	let mut chunks = bytes.chunks_exact(16);
+
+	for chunk in &mut chunks {
+		// SAFETY: SSE2 is part of the `x86_64` baseline, and `chunk` holds
+		//         exactly 16 bytes.
+		let mask = unsafe {
+			let v = _mm_loadu_si128(chunk.as_ptr() as *const __m128i);
+			let nine = _mm_set1_epi8(9);
+			// Digits are the bytes `b` such that `b - b'0'` (wrapping) is at
+			// most 9.
+			let offset = _mm_sub_epi8(v, _mm_set1_epi8(b'0' as i8));
+			let digits = _mm_cmpeq_epi8(_mm_max_epu8(offset, nine), nine);
+			_mm_movemask_epi8(digits) as u32
+		};
+
+		if mask != 0xffff {
+			return len + mask.trailing_ones() as usize;
+		}
+
+		len += 16
+	}
+
+	len + chunks
+		.remainder()
+		.iter()
+		.take_while(|b| b.is_ascii_digit())
+		.count()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{NumberParser, Step};
+	use crate::NumberBuf;
+
+	#[test]
+	fn push() {
+		let mut parser = NumberParser::new();
+		assert_eq!(parser.push(b'-'), Step::Incomplete);
+		assert_eq!(parser.push(b'1'), Step::Complete);
+		assert_eq!(parser.push(b'.'), Step::Incomplete);
+		assert_eq!(parser.push(b'.'), Step::Rejected);
+		assert!(!parser.is_complete());
+		assert_eq!(parser.push(b'5'), Step::Complete);
+		assert_eq!(parser.push(b'E'), Step::Incomplete);
+		assert_eq!(parser.push(b'-'), Step::Incomplete);
+		assert_eq!(parser.push(b'2'), Step::Complete);
+		assert_eq!(parser.push(b' '), Step::Rejected);
+
+		let n: NumberBuf = parser.finish().unwrap();
+		assert_eq!(n.as_str(), "-1.5E-2")
+	}
+
+	#[test]
+	fn chunks() {
+		let mut parser = NumberParser::new();
+		for chunk in ["1", "23", "4.", "0", "e", "+1"] {
+			assert_eq!(parser.push_bytes(chunk.as_bytes()), chunk.len())
+		}
+
+		assert_eq!(parser.push_bytes(b"0]"), 1);
+		let n: NumberBuf = parser.finish().unwrap();
+		assert_eq!(n.as_str(), "1234.0e+10")
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn read_from() {
+		use std::io::{BufRead, BufReader};
+
+		let mut reader = BufReader::with_capacity(2, &b"-12.5e3\n42"[..]);
+		let n: NumberBuf = NumberBuf::read_from(&mut reader).unwrap();
+		assert_eq!(n.as_str(), "-12.5e3");
+		assert_eq!(reader.fill_buf().unwrap(), b"\n");
+		reader.consume(1);
+		let n: NumberBuf = NumberBuf::read_from(&mut reader).unwrap();
+		assert_eq!(n.as_str(), "42");
+
+		let e = NumberBuf::<Vec<u8>>::read_from(&b"1.x"[..]).unwrap_err();
+		assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+		assert!(NumberBuf::<Vec<u8>>::read_from(&b""[..]).is_err());
+	}
+
+	#[test]
+	fn digit_run() {
+		let digits = "1234567890".repeat(10);
+		for len in 0..digits.len() {
+			for suffix in ["", ".", "e", "/", ":", "\u{80}", "\u{ff}"] {
+				let s = format!("{}{suffix}", &digits[..len]);
+				assert_eq!(super::digit_run(s.as_bytes()), len)
+			}
+		}
+
+		let long = format!("-{digits}.{digits}e{digits}");
+		assert!(crate::Number::new(&long).is_ok());
+		assert!(crate::Number::new(&format!("{long}x")).is_err());
+		assert!(crate::Number::new(&format!("{digits}x{digits}")).is_err());
+	}
+
+	#[test]
+	fn new_detailed() {
+		use super::{ParseError, ParseErrorKind::*};
+		use crate::Number;
+
+		let error = |s: &str| Number::new_detailed(s).unwrap_err();
+		let e = |offset, kind| ParseError { offset, kind };
+
+		assert!(Number::new_detailed("-12.5e+3").is_ok());
+		assert_eq!(error(""), e(0, Empty));
+		assert_eq!(error("-"), e(1, MissingIntegerDigits));
+		assert_eq!(error("-a"), e(1, UnexpectedByte(b'a')));
+		assert_eq!(error("007"), e(1, LeadingZero));
+		assert_eq!(error("-01"), e(2, LeadingZero));
+		assert_eq!(error("1."), e(2, MissingFractionDigits));
+		assert_eq!(error("1.e3"), e(2, MissingFractionDigits));
+		assert_eq!(error("1e"), e(2, MissingExponentDigits));
+		assert_eq!(error("1e+"), e(3, MissingExponentDigits));
+		assert_eq!(error("1E-x"), e(3, MissingExponentDigits));
+		assert_eq!(error("12345x"), e(5, UnexpectedByte(b'x')));
+		assert_eq!(error("+1"), e(0, UnexpectedByte(b'+')));
+		assert_eq!(
+			error("1.5 ").to_string(),
+			"invalid JSON number: unexpected byte ` ` at offset 3"
+		);
+	}
+
+	#[test]
+	fn from_iter_checked() {
+		let n: NumberBuf = NumberBuf::from_iter_checked(b"12.5e3".iter().copied()).unwrap();
+		assert_eq!(n.as_str(), "12.5e3");
+		let e = NumberBuf::<Vec<u8>>::from_iter_checked(b"1.x5".iter().copied()).unwrap_err();
+		assert_eq!(e.0, b"1.x");
+		assert!(NumberBuf::<Vec<u8>>::from_iter_checked(*b"1e").is_err());
+
+		let n: NumberBuf = NumberBuf::from_chars_checked("-0.25".chars()).unwrap();
+		assert_eq!(n.as_str(), "-0.25");
+		let e = NumberBuf::<Vec<u8>>::from_chars_checked("12é".chars()).unwrap_err();
+		assert_eq!(e.0, "12é");
+		assert!(NumberBuf::<Vec<u8>>::from_chars_checked("-".chars()).is_err());
+	}
+
+	#[test]
+	fn incomplete() {
+		let mut parser = NumberParser::new();
+		assert_eq!(parser.push_bytes(b"1e}"), 2);
+		assert_eq!(parser.finish::<Vec<u8>>().unwrap_err().0, b"1e");
+		assert!(NumberParser::new().finish::<Vec<u8>>().is_err());
+		assert_eq!(NumberParser::new().push(b'0'), Step::Complete);
+	}
+}
@@ -0,0 +1,294 @@
+//! Postgres `NUMERIC` binary representation.
+use crate::{decimal::Decimal, Buffer, Number, NumberBuf};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Sign of positive numbers.
+#[cfg(feature = "sqlx-postgres")]
+const SIGN_POSITIVE: u16 = 0x0000;
+
+/// Sign of negative numbers.
+#[cfg(feature = "sqlx-postgres")]
+const SIGN_NEGATIVE: u16 = 0x4000;
+
+/// Maximum display scale.
+const MAX_SCALE: u16 = 0x3fff;
+
+/// `NUMERIC` conversion error.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum PgNumericError {
+	/// The number weight or scale exceeds the `NUMERIC` limits.
+	OutOfRange,
+
+	/// The `NUMERIC` value is `NaN` or infinite.
+	NonFinite,
+
+	/// The `NUMERIC` value is malformed.
+	Invalid,
+}
+
+impl fmt::Display for PgNumericError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::OutOfRange => f.write_str("number is out of the NUMERIC range"),
+			Self::NonFinite => f.write_str("non-finite NUMERIC value"),
+			Self::Invalid => f.write_str("invalid NUMERIC value"),
+		}
+	}
+}
+
+impl core::error::Error for PgNumericError {}
+
+/// Postgres `NUMERIC` value, made of base-10000 digits.
+///
+/// The value represented is `Σ digits[i] × 10000^(weight - i)`, negated if
+/// `negative` is `true`, displayed with `scale` digits after the decimal
+/// point.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct PgNumeric {
+	pub negative: bool,
+	pub weight: i16,
+	pub scale: u16,
+	pub digits: Vec<i16>,
+}
+
+/// Returns the number of digits after the decimal point of the plain
+/// notation of the given number, as written: `1.10` has a scale of `2`,
+/// and `1.5e-3` a scale of `4`.
+fn lexical_scale(n: &Number) -> i64 {
+	let bytes = n.as_bytes();
+	let (mantissa, exponent) = match bytes.iter().position(|b| matches!(b, b'e' | b'E')) {
+		Some(i) => (&bytes[..i], &bytes[i + 1..]),
+		None => (bytes, &[][..]),
+	};
+
+	let fraction_len = match mantissa.iter().position(|b| *b == b'.') {
+		Some(i) => (mantissa.len() - i - 1) as i64,
+		None => 0,
+	};
+
+	let (exponent_negative, exponent_digits) = match exponent {
+		[b'-', rest @ ..] => (true, rest),
+		[b'+', rest @ ..] => (false, rest),
+		_ => (false, exponent),
+	};
+
+	let exponent = exponent_digits.iter().fold(0i64, |e, d| {
+		e.saturating_mul(10).saturating_add((d - b'0') as i64)
+	});
+
+	if exponent_negative {
+		fraction_len.saturating_add(exponent)
+	} else {
+		fraction_len.saturating_sub(exponent)
+	}
+}
+
+impl PgNumeric {
+	/// Converts the given number, preserving its scale.
+	pub fn new(n: &Number) -> Result<Self, PgNumericError> {
+		let scale = lexical_scale(n).max(0);
+		if scale > MAX_SCALE as i64 {
+			return Err(PgNumericError::OutOfRange);
+		}
+
+		let d = Decimal::new(n);
+		if d.is_zero() {
+			return Ok(Self {
+				negative: false,
+				weight: 0,
+				scale: scale as u16,
+				digits: Vec::new(),
+			});
+		}
+
+		// Power of ten of the first and last digits.
+		let first = d.point() - 1;
+		let last = d.exponent;
+
+		let weight = first.div_euclid(4);
+		let weight = i16::try_from(weight).map_err(|_| PgNumericError::OutOfRange)?;
+		let len = (weight as i64 - last.div_euclid(4) + 1) as usize;
+
+		let mut digits = alloc::vec![0i16; len];
+		for (i, digit) in d.digits.iter().enumerate() {
+			let power = first - i as i64;
+			let group = (weight as i64 - power.div_euclid(4)) as usize;
+			digits[group] += *digit as i16 * 10i16.pow(power.rem_euclid(4) as u32)
+		}
+
+		Ok(Self {
+			negative: d.negative,
+			weight,
+			scale: scale as u16,
+			digits,
+		})
+	}
+
+	/// Converts this value into a number in plain decimal notation, with
+	/// `scale` digits after the decimal point.
+	pub fn to_number_buf<B: Buffer>(&self) -> Result<NumberBuf<B>, PgNumericError> {
+		let mut decimal_digits = Vec::with_capacity(self.digits.len() * 4);
+		for digit in &self.digits {
+			if !(0..10000).contains(digit) {
+				return Err(PgNumericError::Invalid);
+			}
+
+			decimal_digits.extend_from_slice(&[
+				(digit / 1000) as u8,
+				(digit / 100 % 10) as u8,
+				(digit / 10 % 10) as u8,
+				(digit % 10) as u8,
+			])
+		}
+
+		let mut d = Decimal {
+			negative: self.negative,
+			digits: decimal_digits,
+			exponent: 4 * (self.weight as i64 - self.digits.len() as i64 + 1),
+		};
+
+		d.normalize();
+
+		// Digits beyond the scale are not expected, but preserved.
+		let scale = (self.scale as i64).max(-d.exponent) as usize;
+		Ok(unsafe { NumberBuf::new_unchecked(B::from_vec(d.to_fixed(scale))) })
+	}
+
+	/// Encodes this value in the binary wire format.
+	#[cfg(feature = "sqlx-postgres")]
+	pub fn encode(&self, buf: &mut Vec<u8>) -> Result<(), PgNumericError> {
+		let len = i16::try_from(self.digits.len()).map_err(|_| PgNumericError::OutOfRange)?;
+		let sign = if self.negative {
+			SIGN_NEGATIVE
+		} else {
+			SIGN_POSITIVE
+		};
+
+		buf.extend_from_slice(&len.to_be_bytes());
+		buf.extend_from_slice(&self.weight.to_be_bytes());
+		buf.extend_from_slice(&sign.to_be_bytes());
+		buf.extend_from_slice(&self.scale.to_be_bytes());
+		for digit in &self.digits {
+			buf.extend_from_slice(&digit.to_be_bytes())
+		}
+
+		Ok(())
+	}
+
+	/// Decodes a value in the binary wire format.
+	#[cfg(feature = "sqlx-postgres")]
+	pub fn decode(bytes: &[u8]) -> Result<Self, PgNumericError> {
+		let u16_at = |i: usize| {
+			bytes
+				.get(i..i + 2)
+				.map(|b| u16::from_be_bytes([b[0], b[1]]))
+				.ok_or(PgNumericError::Invalid)
+		};
+
+		let len = u16_at(0)? as usize;
+		let weight = u16_at(2)? as i16;
+		let negative = match u16_at(4)? {
+			SIGN_POSITIVE => false,
+			SIGN_NEGATIVE => true,
+			_ => return Err(PgNumericError::NonFinite),
+		};
+		let scale = u16_at(6)?;
+
+		if bytes.len() != 8 + 2 * len {
+			return Err(PgNumericError::Invalid);
+		}
+
+		let digits = (0..len)
+			.map(|i| u16_at(8 + 2 * i).map(|d| d as i16))
+			.collect::<Result<_, _>>()?;
+
+		Ok(Self {
+			negative,
+			weight,
+			scale,
+			digits,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{PgNumeric, PgNumericError};
+	use crate::{Number, NumberBuf};
+
+	fn numeric(s: &str) -> PgNumeric {
+		PgNumeric::new(Number::new(s).unwrap()).unwrap()
+	}
+
+	#[test]
+	fn digits() {
+		let n = numeric("12345.678");
+		assert_eq!(n.weight, 1);
+		assert_eq!(n.scale, 3);
+		assert_eq!(n.digits, [1, 2345, 6780]);
+
+		let n = numeric("-0.00012");
+		assert!(n.negative);
+		assert_eq!(n.weight, -1);
+		assert_eq!(n.scale, 5);
+		assert_eq!(n.digits, [1, 2000]);
+
+		let n = numeric("1e8");
+		assert_eq!(n.weight, 2);
+		assert_eq!(n.scale, 0);
+		assert_eq!(n.digits, [1]);
+
+		let n = numeric("-0.0");
+		assert!(!n.negative);
+		assert_eq!(n.scale, 1);
+		assert!(n.digits.is_empty())
+	}
+
+	#[test]
+	fn round_trip() {
+		for (s, expected) in [
+			("0", "0"),
+			("-0.0", "0.0"),
+			("1.10", "1.10"),
+			("-12345.678", "-12345.678"),
+			(
+				"123456789012345678901234567890",
+				"123456789012345678901234567890",
+			),
+			("1.5e-3", "0.0015"),
+			("25E+2", "2500"),
+			("1.25e1", "12.5"),
+			("0.00012", "0.00012"),
+		] {
+			let n: NumberBuf = numeric(s).to_number_buf().unwrap();
+			assert_eq!(n.as_str(), expected)
+		}
+	}
+
+	#[test]
+	fn out_of_range() {
+		for s in ["1e200000", "1e-20000"] {
+			assert_eq!(
+				PgNumeric::new(Number::new(s).unwrap()),
+				Err(PgNumericError::OutOfRange)
+			)
+		}
+	}
+
+	#[cfg(feature = "sqlx-postgres")]
+	#[test]
+	fn wire_format() {
+		let n = numeric("-12345.678");
+		let mut bytes = Vec::new();
+		n.encode(&mut bytes).unwrap();
+		assert_eq!(
+			bytes,
+			[0, 3, 0, 1, 0x40, 0, 0, 3, 0, 1, 0x09, 0x29, 0x1a, 0x7c]
+		);
+		assert_eq!(PgNumeric::decode(&bytes), Ok(n));
+
+		let nan = [0, 0, 0, 0, 0xc0, 0, 0, 0];
+		assert_eq!(PgNumeric::decode(&nan), Err(PgNumericError::NonFinite))
+	}
+}
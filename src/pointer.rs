@@ -0,0 +1,86 @@
+//! Smart pointer conversions.
+use crate::{Buffer, Number, NumberBuf};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+
+impl From<&Number> for Box<Number> {
+	#[inline(always)]
+	fn from(n: &Number) -> Self {
+		let bytes: Box<[u8]> = n.as_bytes().into();
+		// SAFETY: `Number` is a transparent wrapper around `[u8]`, and
+		//         `bytes` is a valid number.
+		unsafe { Box::from_raw(Box::into_raw(bytes) as *mut Number) }
+	}
+}
+
+impl From<&Number> for Arc<Number> {
+	#[inline(always)]
+	fn from(n: &Number) -> Self {
+		let bytes: Arc<[u8]> = n.as_bytes().into();
+		// SAFETY: `Number` is a transparent wrapper around `[u8]`, and
+		//         `bytes` is a valid number.
+		unsafe { Arc::from_raw(Arc::into_raw(bytes) as *const Number) }
+	}
+}
+
+impl From<&Number> for Rc<Number> {
+	#[inline(always)]
+	fn from(n: &Number) -> Self {
+		let bytes: Rc<[u8]> = n.as_bytes().into();
+		// SAFETY: `Number` is a transparent wrapper around `[u8]`, and
+		//         `bytes` is a valid number.
+		unsafe { Rc::from_raw(Rc::into_raw(bytes) as *const Number) }
+	}
+}
+
+impl From<NumberBuf> for Box<Number> {
+	#[inline(always)]
+	fn from(n: NumberBuf) -> Self {
+		let bytes = n.into_buffer().into_boxed_slice();
+		// SAFETY: `Number` is a transparent wrapper around `[u8]`, and
+		//         `bytes` is a valid number.
+		unsafe { Box::from_raw(Box::into_raw(bytes) as *mut Number) }
+	}
+}
+
+impl<B: Buffer> From<Box<Number>> for NumberBuf<B> {
+	#[inline(always)]
+	fn from(n: Box<Number>) -> Self {
+		// SAFETY: `Number` is a transparent wrapper around `[u8]`.
+		let bytes = unsafe { Box::from_raw(Box::into_raw(n) as *mut [u8]) };
+		unsafe { NumberBuf::new_unchecked(B::from_vec(bytes.into_vec())) }
+	}
+}
+
+impl Clone for Box<Number> {
+	#[inline(always)]
+	fn clone(&self) -> Self {
+		(**self).into()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Number, NumberBuf};
+	use alloc::rc::Rc;
+	use alloc::sync::Arc;
+
+	#[test]
+	fn smart_pointers() {
+		let n = Number::new("-1.5e3").unwrap();
+
+		let b: Box<Number> = n.into();
+		assert_eq!(&*b, n);
+		assert_eq!(&*b.clone(), n);
+		let a: Arc<Number> = n.into();
+		assert_eq!(a.as_str(), "-1.5e3");
+		let r: Rc<Number> = n.into();
+		assert_eq!(r.as_f64_lossy(), -1500.0);
+
+		let buf: NumberBuf = b.into();
+		assert_eq!(buf.as_number(), n);
+		let b: Box<Number> = buf.into();
+		assert_eq!(b.as_str(), "-1.5e3");
+	}
+}
@@ -0,0 +1,170 @@
+//! [`proptest`](https://docs.rs/proptest) strategies generating numbers.
+//!
+//! Numbers are built from collections of digits, so they shrink toward
+//! fewer and smaller digits, down to `0`.
+use crate::NumberBuf;
+use alloc::vec::Vec;
+use proptest::{
+	collection::{vec, SizeRange},
+	prelude::*,
+};
+
+/// Pushes the given digits of an integer part, without leading zeros.
+fn push_integer(bytes: &mut Vec<u8>, digits: &[u8]) {
+	let start = digits.iter().position(|d| *d != 0).unwrap_or(digits.len());
+	if start == digits.len() {
+		bytes.push(b'0')
+	} else {
+		bytes.extend(digits[start..].iter().map(|d| b'0' + d))
+	}
+}
+
+/// Strategy generating decimal digits.
+fn digits(len: impl Into<SizeRange>) -> impl Strategy<Value = Vec<u8>> {
+	vec(0u8..10, len)
+}
+
+/// Strategy generating any number.
+pub fn any_number() -> impl Strategy<Value = NumberBuf> {
+	prop_oneof![
+		integer_number(0..20),
+		decimal_number(0..20, 0..20),
+		scientific_number(1..20, 1..4),
+	]
+}
+
+/// Strategy generating integers, with up to the given number of `digits`
+/// (ignoring leading zeros).
+///
+/// Shrinks toward `0`.
+pub fn integer_number(digits: impl Into<SizeRange>) -> impl Strategy<Value = NumberBuf> {
+	(any::<bool>(), self::digits(digits)).prop_map(|(negative, digits)| {
+		let mut bytes = Vec::with_capacity(digits.len() + 1);
+		if negative {
+			bytes.push(b'-')
+		}
+
+		push_integer(&mut bytes, &digits);
+		unsafe { NumberBuf::new_unchecked(bytes) }
+	})
+}
+
+/// Strategy generating decimal numbers, with up to the given number of
+/// integer digits (ignoring leading zeros) and fraction digits.
+///
+/// No decimal point is generated when the fraction is empty.
+pub fn decimal_number(
+	integer_digits: impl Into<SizeRange>,
+	fraction_digits: impl Into<SizeRange>,
+) -> impl Strategy<Value = NumberBuf> {
+	(
+		any::<bool>(),
+		digits(integer_digits),
+		digits(fraction_digits),
+	)
+		.prop_map(|(negative, integer, fraction)| {
+			let mut bytes = Vec::with_capacity(integer.len() + fraction.len() + 2);
+			if negative {
+				bytes.push(b'-')
+			}
+
+			push_integer(&mut bytes, &integer);
+
+			if !fraction.is_empty() {
+				bytes.push(b'.');
+				bytes.extend(fraction.iter().map(|d| b'0' + d))
+			}
+
+			unsafe { NumberBuf::new_unchecked(bytes) }
+		})
+}
+
+/// Strategy generating numbers in scientific notation (`d.ddde±x`), with
+/// the given number of mantissa and exponent digits.
+///
+/// An empty mantissa or exponent is generated as `0`.
+pub fn scientific_number(
+	mantissa_digits: impl Into<SizeRange>,
+	exponent_digits: impl Into<SizeRange>,
+) -> impl Strategy<Value = NumberBuf> {
+	(
+		any::<bool>(),
+		digits(mantissa_digits),
+		any::<bool>(),
+		digits(exponent_digits),
+	)
+		.prop_map(|(negative, mantissa, negative_exponent, exponent)| {
+			let mut bytes = Vec::with_capacity(mantissa.len() + exponent.len() + 4);
+			if negative {
+				bytes.push(b'-')
+			}
+
+			match mantissa.split_first() {
+				Some((first, fraction)) => {
+					bytes.push(b'0' + first);
+					if !fraction.is_empty() {
+						bytes.push(b'.');
+						bytes.extend(fraction.iter().map(|d| b'0' + d))
+					}
+				}
+				None => bytes.push(b'0'),
+			}
+
+			bytes.push(b'e');
+			if negative_exponent {
+				bytes.push(b'-')
+			}
+
+			if exponent.is_empty() {
+				bytes.push(b'0')
+			} else {
+				bytes.extend(exponent.iter().map(|d| b'0' + d))
+			}
+
+			unsafe { NumberBuf::new_unchecked(bytes) }
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{any_number, decimal_number, integer_number, scientific_number};
+	use crate::Number;
+	use proptest::{
+		prelude::*,
+		strategy::ValueTree,
+		test_runner::{Config, TestRunner},
+	};
+
+	proptest! {
+		#[test]
+		fn valid_numbers(n in any_number()) {
+			prop_assert!(Number::new(n.as_bytes()).is_ok())
+		}
+
+		#[test]
+		fn integers(n in integer_number(0..30)) {
+			prop_assert!(Number::new(n.as_bytes()).is_ok());
+			prop_assert!(!n.has_decimal_point() && !n.has_exponent())
+		}
+
+		#[test]
+		fn decimals(n in decimal_number(0..5, 0..5)) {
+			prop_assert!(Number::new(n.as_bytes()).is_ok());
+			prop_assert!(!n.has_exponent())
+		}
+
+		#[test]
+		fn scientific(n in scientific_number(0..5, 0..3)) {
+			prop_assert!(Number::new(n.as_bytes()).is_ok());
+			prop_assert!(n.has_exponent())
+		}
+	}
+
+	#[test]
+	fn shrinks_to_zero() {
+		let mut runner = TestRunner::new(Config::default());
+		let mut tree = integer_number(10..20).new_tree(&mut runner).unwrap();
+		while tree.simplify() {}
+		assert_eq!(tree.current().as_str(), "0")
+	}
+}
@@ -0,0 +1,144 @@
+//! Conversions between numbers and Python `int`, `float` and
+//! `decimal.Decimal` objects.
+//!
+//! Integers are converted into Python `int` and other numbers into
+//! `decimal.Decimal`, preserving all their digits.
+use crate::{Buffer, Number, NumberBuf};
+use pyo3::{
+	exceptions::{PyTypeError, PyValueError},
+	sync::PyOnceLock,
+	types::{PyAnyMethods, PyBool, PyFloat, PyInt, PyStringMethods, PyType},
+	Borrowed, Bound, FromPyObject, IntoPyObject, Py, PyAny, PyErr, PyResult, Python,
+};
+
+static DECIMAL_CLS: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+
+/// Returns the Python `decimal.Decimal` class.
+fn decimal_cls(py: Python<'_>) -> PyResult<&Bound<'_, PyType>> {
+	DECIMAL_CLS.import(py, "decimal", "Decimal")
+}
+
+impl<'py> IntoPyObject<'py> for &Number {
+	type Target = PyAny;
+	type Output = Bound<'py, PyAny>;
+	type Error = PyErr;
+
+	/// Converts the number into a Python `int` if it has no fraction nor
+	/// exponent, or into a `decimal.Decimal` otherwise.
+	fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+		if self.has_decimal_point() || self.has_exponent() {
+			decimal_cls(py)?.call1((self.as_str(),))
+		} else {
+			py.get_type::<PyInt>().call1((self.as_str(),))
+		}
+	}
+}
+
+impl<'py, B: Buffer> IntoPyObject<'py> for &NumberBuf<B> {
+	type Target = PyAny;
+	type Output = Bound<'py, PyAny>;
+	type Error = PyErr;
+
+	#[inline(always)]
+	fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+		self.as_number().into_pyobject(py)
+	}
+}
+
+impl<'py, B: Buffer> IntoPyObject<'py> for NumberBuf<B> {
+	type Target = PyAny;
+	type Output = Bound<'py, PyAny>;
+	type Error = PyErr;
+
+	#[inline(always)]
+	fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+		self.as_number().into_pyobject(py)
+	}
+}
+
+impl<B: Buffer> FromPyObject<'_, '_> for NumberBuf<B> {
+	type Error = PyErr;
+
+	/// Extracts a Python `int`, `float` or `decimal.Decimal`.
+	///
+	/// Booleans are rejected, as well as non-finite floats and decimals.
+	fn extract(obj: Borrowed<'_, '_, PyAny>) -> PyResult<Self> {
+		if obj.is_instance_of::<PyBool>() {
+			return Err(PyTypeError::new_err("expected a number, found bool"));
+		}
+
+		if obj.is_instance_of::<PyFloat>() {
+			let f: f64 = obj.extract()?;
+			return NumberBuf::try_from(f).map_err(|_| PyValueError::new_err("non-finite float"));
+		}
+
+		if !obj.is_instance_of::<PyInt>() && !obj.is_instance(decimal_cls(obj.py())?)? {
+			return Err(PyTypeError::new_err("expected an int, float or Decimal"));
+		}
+
+		// `str` preserves all the digits of integers and decimals.
+		let s = obj.str()?;
+		let s = s.to_cow()?;
+		match Number::new(s.as_ref()) {
+			Ok(n) => Ok(unsafe { NumberBuf::new_unchecked(B::from_bytes(n.as_bytes())) }),
+			Err(_) => Err(PyValueError::new_err(alloc::format!(
+				"invalid JSON number: {s}"
+			))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::NumberBuf;
+	use pyo3::{
+		types::{PyAnyMethods, PyTypeMethods},
+		IntoPyObject, Python,
+	};
+
+	fn n(s: &str) -> NumberBuf {
+		s.parse().unwrap()
+	}
+
+	#[test]
+	fn round_trip() {
+		Python::initialize();
+		Python::attach(|py| {
+			for (s, ty) in [
+				("0", "int"),
+				("-123456789012345678901234567890", "int"),
+				("1.10", "Decimal"),
+				("-0.0", "Decimal"),
+				("1.5E-7", "Decimal"),
+				("1E+2", "Decimal"),
+			] {
+				let obj = n(s).into_pyobject(py).unwrap();
+				assert_eq!(obj.get_type().name().unwrap(), ty);
+				assert_eq!(obj.extract::<NumberBuf>().unwrap().as_str(), s)
+			}
+		})
+	}
+
+	#[test]
+	fn extract() {
+		Python::initialize();
+		Python::attach(|py| {
+			let eval = |code: &str| {
+				let code = std::ffi::CString::new(code).unwrap();
+				py.eval(&code, None, None).unwrap()
+			};
+
+			assert_eq!(eval("0.5").extract::<NumberBuf>().unwrap().as_str(), "0.5");
+			assert_eq!(
+				eval("2**70").extract::<NumberBuf>().unwrap(),
+				n("1180591620717411303424")
+			);
+			assert!(eval("True").extract::<NumberBuf>().is_err());
+			assert!(eval("'1'").extract::<NumberBuf>().is_err());
+			assert!(eval("float('nan')").extract::<NumberBuf>().is_err());
+			assert!(eval("__import__('decimal').Decimal('Infinity')")
+				.extract::<NumberBuf>()
+				.is_err())
+		})
+	}
+}
@@ -0,0 +1,234 @@
+use crate::{Buffer, NumberBuf};
+use alloc::{boxed::Box, vec::Vec};
+use quickcheck::{Arbitrary, Gen};
+
+/// Lexical parts of a number.
+struct Parts<'a> {
+	negative: bool,
+	integer: &'a [u8],
+	fraction: Option<&'a [u8]>,
+
+	/// Exponent marker (`e` or `E`) with its optional sign, and digits.
+	exponent: Option<(&'a [u8], &'a [u8])>,
+}
+
+impl<'a> Parts<'a> {
+	fn new(bytes: &'a [u8]) -> Self {
+		let (negative, bytes) = match bytes {
+			[b'-', rest @ ..] => (true, rest),
+			_ => (false, bytes),
+		};
+
+		let (mantissa, exponent) = match bytes.iter().position(|b| matches!(b, b'e' | b'E')) {
+			Some(i) => {
+				let (mantissa, exponent) = bytes.split_at(i);
+				let marker_len = if matches!(exponent[1], b'+' | b'-') {
+					2
+				} else {
+					1
+				};
+				(mantissa, Some(exponent.split_at(marker_len)))
+			}
+			None => (bytes, None),
+		};
+
+		let (integer, fraction) = match mantissa.iter().position(|b| *b == b'.') {
+			Some(i) => (&mantissa[..i], Some(&mantissa[i + 1..])),
+			None => (mantissa, None),
+		};
+
+		Self {
+			negative,
+			integer,
+			fraction,
+			exponent,
+		}
+	}
+
+	fn to_number<B: Buffer>(&self) -> NumberBuf<B> {
+		let mut bytes = Vec::new();
+
+		if self.negative {
+			bytes.push(b'-')
+		}
+
+		bytes.extend_from_slice(self.integer);
+
+		if let Some(fraction) = self.fraction {
+			bytes.push(b'.');
+			bytes.extend_from_slice(fraction)
+		}
+
+		if let Some((marker, digits)) = self.exponent {
+			bytes.extend_from_slice(marker);
+			bytes.extend_from_slice(digits)
+		}
+
+		unsafe { NumberBuf::new_unchecked(B::from_vec(bytes)) }
+	}
+}
+
+/// Generates up to `g.size()` decimal digits (at least one).
+fn arbitrary_digits(g: &mut Gen, bytes: &mut Vec<u8>, nonzero_first: bool) {
+	let len = 1 + usize::arbitrary(g) % g.size().max(1);
+
+	for i in 0..len {
+		let digits = if i == 0 && nonzero_first && len > 1 {
+			b"123456789".as_slice()
+		} else {
+			b"0123456789".as_slice()
+		};
+
+		bytes.push(*g.choose(digits).unwrap())
+	}
+}
+
+impl<B: Buffer + Clone + 'static> Arbitrary for NumberBuf<B> {
+	fn arbitrary(g: &mut Gen) -> Self {
+		let mut bytes = Vec::new();
+
+		if bool::arbitrary(g) {
+			bytes.push(b'-')
+		}
+
+		arbitrary_digits(g, &mut bytes, true);
+
+		if bool::arbitrary(g) {
+			bytes.push(b'.');
+			arbitrary_digits(g, &mut bytes, false)
+		}
+
+		if bool::arbitrary(g) {
+			bytes.extend_from_slice(g.choose(&[&b"e"[..], b"E", b"e+", b"e-"]).unwrap());
+			arbitrary_digits(g, &mut bytes, false)
+		}
+
+		unsafe { NumberBuf::new_unchecked(B::from_vec(bytes)) }
+	}
+
+	/// Shrinks the number by removing its parts (exponent, fraction, sign)
+	/// or the last digit of a part, always producing valid numbers.
+	fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+		let parts = Parts::new(self.as_bytes());
+		let mut candidates = Vec::new();
+
+		if self.as_bytes() != b"0" {
+			candidates.push(Parts::new(b"0"));
+		}
+
+		if parts.exponent.is_some() {
+			candidates.push(Parts {
+				exponent: None,
+				..parts
+			})
+		}
+
+		if parts.fraction.is_some() {
+			candidates.push(Parts {
+				fraction: None,
+				..parts
+			})
+		}
+
+		if parts.negative {
+			candidates.push(Parts {
+				negative: false,
+				..parts
+			})
+		}
+
+		if parts.integer.len() > 1 {
+			candidates.push(Parts {
+				integer: &parts.integer[..parts.integer.len() - 1],
+				..parts
+			})
+		}
+
+		if let Some(fraction) = parts.fraction.filter(|f| f.len() > 1) {
+			candidates.push(Parts {
+				fraction: Some(&fraction[..fraction.len() - 1]),
+				..parts
+			})
+		}
+
+		if let Some((marker, digits)) = parts.exponent {
+			if digits.len() > 1 {
+				candidates.push(Parts {
+					exponent: Some((marker, &digits[..digits.len() - 1])),
+					..parts
+				})
+			}
+
+			if marker.len() > 1 {
+				candidates.push(Parts {
+					exponent: Some((&marker[..1], digits)),
+					..parts
+				})
+			}
+		}
+
+		let candidates: Vec<Self> = candidates.iter().map(Parts::to_number).collect();
+		Box::new(candidates.into_iter())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Number, NumberBuf};
+	use quickcheck::{Arbitrary, Gen};
+
+	#[test]
+	fn valid_numbers() {
+		let mut g = Gen::new(16);
+		for _ in 0..1000 {
+			let n = NumberBuf::<Vec<u8>>::arbitrary(&mut g);
+			assert!(Number::new(n.as_bytes()).is_ok(), "{n}");
+			for m in n.shrink() {
+				assert!(Number::new(m.as_bytes()).is_ok(), "{m}")
+			}
+		}
+	}
+
+	#[test]
+	fn shrink() {
+		let n: NumberBuf = "-12.50e+03".parse().unwrap();
+		let shrunk: Vec<String> = n.shrink().map(|m| m.as_str().to_owned()).collect();
+		assert_eq!(
+			shrunk,
+			[
+				"0",
+				"-12.50",
+				"-12e+03",
+				"12.50e+03",
+				"-1.50e+03",
+				"-12.5e+03",
+				"-12.50e+0",
+				"-12.50e03"
+			]
+		);
+
+		let zero: NumberBuf = "0".parse().unwrap();
+		assert_eq!(zero.shrink().count(), 0)
+	}
+
+	#[test]
+	fn minimal_counterexample() {
+		fn prop(n: NumberBuf) -> bool {
+			!n.has_fraction() || n.as_str().len() < 6
+		}
+
+		let result = std::panic::catch_unwind(|| {
+			quickcheck::QuickCheck::new()
+				.tests(1000)
+				.quickcheck(prop as fn(NumberBuf) -> bool)
+		});
+
+		let message = result.unwrap_err();
+		let message = message.downcast_ref::<String>().unwrap();
+		let start = message.find("(\"").unwrap() + 2;
+		let end = message.find("\")").unwrap();
+		let n = Number::new(&message[start..end]).unwrap();
+		assert!(n.has_fraction());
+		assert_eq!(n.as_str().len(), 6)
+	}
+}
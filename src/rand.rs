@@ -0,0 +1,192 @@
+//! Random number generation.
+use crate::{decimal::Decimal, Buffer, Number, NumberBuf};
+use alloc::{string::ToString, vec::Vec};
+use core::ops::RangeInclusive;
+use rand::{distr::Distribution, Rng};
+
+impl<B: Buffer> NumberBuf<B> {
+	/// Generates a number uniformly distributed between `min` and `max`
+	/// (inclusive).
+	///
+	/// The generated number has as many fraction digits as the most precise
+	/// bound: `random_in(rng, 0, 1.5)` generates one of `0`, `0.1`, ...,
+	/// `1.5`.
+	///
+	/// # Panics
+	///
+	/// Panics if `min` is greater than `max`, or if one of them has an
+	/// exponent greater than [`Number::MAX_EXACT_EXPONENT`] in absolute value.
+	pub fn random_in<R: Rng + ?Sized>(rng: &mut R, min: &Number, max: &Number) -> Self {
+		let min = Decimal::new(min);
+		let max = Decimal::new(max);
+		let range = max.sub(&min);
+		assert!(!range.negative, "`min` is greater than `max`");
+
+		if range.is_zero() {
+			return min.into_number_buf();
+		}
+
+		let exponent = min.exponent.min(max.exponent);
+		let mut bound = range.digits;
+		bound.resize(bound.len() + (range.exponent - exponent) as usize, 0);
+
+		// Rejection sampling of an integer in `0..=bound`, with a first digit
+		// lower or equal to the first digit of `bound`, so that at least half
+		// of the samples are accepted.
+		let mut digits = Vec::with_capacity(bound.len());
+		loop {
+			digits.clear();
+			digits.push(rng.random_range(0..=bound[0]));
+			digits.extend((1..bound.len()).map(|_| rng.random_range(0..10u8)));
+
+			if digits <= bound {
+				break;
+			}
+		}
+
+		let mut offset = Decimal {
+			negative: false,
+			digits,
+			exponent,
+		};
+
+		offset.normalize();
+		min.add(&offset).into_number_buf()
+	}
+}
+
+/// Distribution of numbers with a chosen shape.
+///
+/// Each part of the number is sampled independently: the number of integer
+/// digits, the number of fraction digits, the digits themselves, the sign
+/// and the exponent.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NumberDistribution {
+	/// Number of digits of the integer part.
+	///
+	/// An integer part without digits is written `0`.
+	pub integer_digits: RangeInclusive<usize>,
+
+	/// Number of digits after the decimal point.
+	///
+	/// No decimal point is written when there are no fraction digits.
+	pub fraction_digits: RangeInclusive<usize>,
+
+	/// Range of the exponent, if any.
+	pub exponent: Option<RangeInclusive<i32>>,
+
+	/// Whether negative numbers are generated (half of the time).
+	pub signed: bool,
+}
+
+impl Default for NumberDistribution {
+	/// Generates signed numbers with `1` to `6` integer digits, up to `6`
+	/// fraction digits and no exponent.
+	fn default() -> Self {
+		Self {
+			integer_digits: 1..=6,
+			fraction_digits: 0..=6,
+			exponent: None,
+			signed: true,
+		}
+	}
+}
+
+impl<B: Buffer> Distribution<NumberBuf<B>> for NumberDistribution {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> NumberBuf<B> {
+		let integer_len = rng.random_range(self.integer_digits.clone());
+		let fraction_len = rng.random_range(self.fraction_digits.clone());
+		let mut bytes = Vec::with_capacity(integer_len + fraction_len + 16);
+
+		if self.signed && rng.random() {
+			bytes.push(b'-')
+		}
+
+		if integer_len == 0 {
+			bytes.push(b'0')
+		} else {
+			bytes.push(b'0' + rng.random_range(1..10u8));
+			bytes.extend((1..integer_len).map(|_| b'0' + rng.random_range(0..10u8)))
+		}
+
+		if fraction_len > 0 {
+			bytes.push(b'.');
+			bytes.extend((0..fraction_len).map(|_| b'0' + rng.random_range(0..10u8)))
+		}
+
+		if let Some(exponent) = &self.exponent {
+			bytes.push(b'e');
+			let e = rng.random_range(exponent.clone());
+			bytes.extend_from_slice(e.to_string().as_bytes())
+		}
+
+		unsafe { NumberBuf::new_unchecked(B::from_vec(bytes)) }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::NumberDistribution;
+	use crate::{Number, NumberBuf};
+	use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+	fn n(s: &str) -> &Number {
+		Number::new(s).unwrap()
+	}
+
+	#[test]
+	fn random_in() {
+		let mut rng = SmallRng::seed_from_u64(0);
+		let mut seen = [false; 16];
+
+		for _ in 0..1000 {
+			let x: NumberBuf = NumberBuf::random_in(&mut rng, n("0"), n("1.5"));
+			let tenths = (x.as_f64_lossy() * 10.0).round() as usize;
+			assert!(tenths <= 15, "{x}");
+			assert!(x.as_str().len() <= 3, "{x}");
+			seen[tenths] = true
+		}
+
+		assert!(seen.iter().all(|s| *s));
+
+		for _ in 0..1000 {
+			let x: NumberBuf =
+				NumberBuf::random_in(&mut rng, n("-1e-3"), n("12345678901234567890"));
+			let d = crate::decimal::Decimal::new(&x);
+			assert!(d.exponent >= -3, "{x}");
+			assert!(x.as_f64_lossy() >= -1e-3 && x.as_f64_lossy() <= 12345678901234567890.0)
+		}
+
+		let x: NumberBuf = NumberBuf::random_in(&mut rng, n("2.50"), n("25e-1"));
+		assert_eq!(x.as_str(), "2.5")
+	}
+
+	#[test]
+	#[should_panic]
+	fn random_in_empty() {
+		let mut rng = SmallRng::seed_from_u64(0);
+		let _: NumberBuf = NumberBuf::random_in(&mut rng, n("1"), n("0.5"));
+	}
+
+	#[test]
+	fn distribution() {
+		let mut rng = SmallRng::seed_from_u64(0);
+		let distribution = NumberDistribution {
+			integer_digits: 0..=3,
+			fraction_digits: 2..=2,
+			exponent: Some(-10..=10),
+			signed: false,
+		};
+
+		for _ in 0..1000 {
+			let x: NumberBuf = rng.sample(&distribution);
+			assert!(Number::new(x.as_bytes()).is_ok(), "{x}");
+			assert!(!x.is_negative() && x.has_exponent());
+
+			let (mantissa, _) = x.as_str().split_once('e').unwrap();
+			let (integer, fraction) = mantissa.split_once('.').unwrap();
+			assert!((1..=3).contains(&integer.len()));
+			assert_eq!(fraction.len(), 2)
+		}
+	}
+}
@@ -0,0 +1,138 @@
+//! Range checks.
+use crate::{decimal::Decimal, Number};
+use core::cmp::Ordering;
+
+impl Number {
+	/// Checks if this number converts into a finite `f64`, that is, if it
+	/// does not round to infinity.
+	///
+	/// This is checked exactly, without converting the number. Numbers
+	/// too small to be represented are rounded to zero, and hence fit.
+	pub fn fits_f64(&self) -> bool {
+		let d = Decimal::new(self);
+		match d.point() {
+			..=308 => true,
+			309 => {
+				// Midpoint between `f64::MAX` and `2^1024`, rounded up to
+				// infinity since `f64::MAX` has an odd mantissa.
+				let limit = Decimal::from_f64(f64::MAX).add(&Decimal::pow(2, 970));
+				d.cmp_abs(&limit) == Ordering::Less
+			}
+			_ => false,
+		}
+	}
+
+	/// Checks if the value of this number is an integer in the `i64` range,
+	/// whatever its notation (`1e2` and `5.0` fit).
+	pub fn fits_i64(&self) -> bool {
+		self.fits_integer(i64::MIN as i128, i64::MAX as i128)
+	}
+
+	/// Checks if the value of this number is an integer in the `u64` range,
+	/// whatever its notation (`1e2` and `5.0` fit).
+	pub fn fits_u64(&self) -> bool {
+		self.fits_integer(0, u64::MAX as i128)
+	}
+
+	/// Checks if the value of this number is an integer between `min` and
+	/// `max` (inclusive).
+	fn fits_integer(&self, min: i128, max: i128) -> bool {
+		let d = Decimal::new(self);
+		d.exponent >= 0
+			&& d.point() <= 20
+			&& d.cmp_signed(&Decimal::from_i128(min)) != Ordering::Less
+			&& d.cmp_signed(&Decimal::from_i128(max)) != Ordering::Greater
+	}
+
+	/// Checks if the value of this number is between the values of `min`
+	/// and `max`, bounds included if `inclusive` is `true`.
+	///
+	/// Values are compared exactly, regardless of their lexical
+	/// representations. Returns `false` if the order of the numbers depends
+	/// on the exact value of an exponent greater than
+	/// [`Number::MAX_EXACT_EXPONENT`] in absolute value.
+	pub fn in_range(&self, min: &Number, max: &Number, inclusive: bool) -> bool {
+		let d = Decimal::new(self);
+		let (Some(lower), Some(upper)) = (
+			d.partial_cmp_signed(&Decimal::new(min)),
+			d.partial_cmp_signed(&Decimal::new(max)),
+		) else {
+			return false;
+		};
+
+		if inclusive {
+			lower != Ordering::Less && upper != Ordering::Greater
+		} else {
+			lower == Ordering::Greater && upper == Ordering::Less
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::Number;
+
+	fn number(s: &str) -> &Number {
+		Number::new(s).unwrap()
+	}
+
+	#[test]
+	fn fits_f64() {
+		for s in ["1e308", "1.8e308", "-1.7976931348623158e308", "1e400"] {
+			assert_eq!(
+				number(s).fits_f64(),
+				number(s).as_f64_lossy().is_finite(),
+				"{s}"
+			)
+		}
+
+		assert!(number("0").fits_f64());
+		assert!(number("-1.5e300").fits_f64());
+		assert!(number("1e-1000").fits_f64());
+		assert!(number("1.7976931348623157e308").fits_f64());
+		assert!(number("-1.7976931348623158e308").fits_f64());
+
+		// `2^1024 - 2^970`, the smallest value rounding to infinity.
+		let limit = "179769313486231580793728971405303415079934132710037826936173778980444968292764750946649017977587207096330286416692887910946555547851940402630657488671505820681908902000708383676273854845817711531764475730270069855571366959622842914819860834936475292719074168444365510704342711559699508093042880177904174497792";
+		assert!(number(&format!("{limit}e-1")).fits_f64());
+		assert!(number(&format!("{}.9", &limit[..limit.len() - 1])).fits_f64());
+		assert!(!number(limit).fits_f64());
+		assert!(!number(&format!("{limit}.0")).fits_f64());
+
+		assert!(!number("1.8e308").fits_f64());
+		assert!(!number("-1e400").fits_f64());
+	}
+
+	#[test]
+	fn fits_integers() {
+		assert!(number("9223372036854775807").fits_i64());
+		assert!(number("-9223372036854775808").fits_i64());
+		assert!(!number("9223372036854775808").fits_i64());
+		assert!(number("1e18").fits_i64());
+		assert!(!number("1e19").fits_i64());
+		assert!(number("-5.0").fits_i64());
+		assert!(!number("0.5").fits_i64());
+
+		assert!(number("18446744073709551615").fits_u64());
+		assert!(!number("18446744073709551616").fits_u64());
+		assert!(number("1e19").fits_u64());
+		assert!(number("-0").fits_u64());
+		assert!(!number("-1").fits_u64())
+	}
+
+	#[test]
+	fn in_range() {
+		let (min, max) = (number("0"), number("1e2"));
+		assert!(number("0.0").in_range(min, max, true));
+		assert!(!number("0.0").in_range(min, max, false));
+		assert!(number("100").in_range(min, max, true));
+		assert!(!number("100").in_range(min, max, false));
+		assert!(number("99.999").in_range(min, max, false));
+		assert!(!number("-1e-100").in_range(min, max, true));
+		assert!(!number("100.00001").in_range(min, max, true));
+
+		let max = number("1e99999999999999999999");
+		assert!(number("1e100").in_range(min, max, false));
+		assert!(!number("1e99999999999999999998").in_range(min, max, true))
+	}
+}
@@ -0,0 +1,298 @@
+//! Relaxed (JSON5) and lenient number parsing.
+use crate::{InvalidNumber, Number, NumberBuf};
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+/// Relaxed number parsing error.
+///
+/// The inner value is the data failed to be parsed.
+#[derive(Clone, Copy, Debug)]
+pub enum RelaxedNumberError<T> {
+	/// The input is not a valid JSON5 number.
+	Invalid(T),
+
+	/// The input is a valid JSON5 number, but is not finite (`Infinity` or
+	/// `NaN`) and hence has no JSON representation.
+	NonFinite(T),
+}
+
+impl<T> RelaxedNumberError<T> {
+	/// Returns the data that failed to be parsed.
+	pub fn into_inner(self) -> T {
+		match self {
+			Self::Invalid(t) | Self::NonFinite(t) => t,
+		}
+	}
+}
+
+impl<T: fmt::Display> fmt::Display for RelaxedNumberError<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Invalid(t) => write!(f, "invalid JSON5 number: {t}"),
+			Self::NonFinite(t) => write!(f, "non-finite number: {t}"),
+		}
+	}
+}
+
+impl<T: fmt::Display + fmt::Debug> core::error::Error for RelaxedNumberError<T> {}
+
+impl Number {
+	/// Parses the given input `data` as a
+	/// [JSON5 number](https://spec.json5.org/#numbers) and normalizes it into
+	/// a JSON number.
+	///
+	/// On top of the JSON syntax, this accepts:
+	///   - an explicit `+` sign, which is removed (`+1` becomes `1`);
+	///   - a leading or trailing decimal point (`.5` becomes `0.5` and `5.`
+	///     becomes `5`);
+	///   - hexadecimal integers (`0x1F` becomes `31`).
+	///
+	/// `Infinity` and `NaN` are valid JSON5 numbers, but are rejected with
+	/// [`RelaxedNumberError::NonFinite`].
+	pub fn new_relaxed<B: AsRef<[u8]> + ?Sized>(
+		data: &B,
+	) -> Result<NumberBuf, RelaxedNumberError<&B>> {
+		let s = data.as_ref();
+
+		if Number::new(s).is_ok() {
+			return Ok(unsafe { NumberBuf::new_unchecked(s.to_vec()) });
+		}
+
+		let (negative, unsigned) = match s {
+			[b'+', rest @ ..] => (false, rest),
+			[b'-', rest @ ..] => (true, rest),
+			_ => (false, s),
+		};
+
+		let mut result = Vec::with_capacity(s.len() + 1);
+		if negative {
+			result.push(b'-')
+		}
+
+		let valid = match unsigned {
+			b"Infinity" | b"NaN" => return Err(RelaxedNumberError::NonFinite(data)),
+			[b'0', b'x' | b'X', hex @ ..] => push_hex(&mut result, hex),
+			decimal => push_decimal(&mut result, decimal, Syntax::JSON5),
+		};
+
+		if valid {
+			Ok(unsafe { NumberBuf::new_unchecked(result) })
+		} else {
+			Err(RelaxedNumberError::Invalid(data))
+		}
+	}
+
+	/// Parses the given input `data` as a JSON number, tolerating leading
+	/// zeros and an explicit `+` sign, and normalizes it into a JSON number.
+	///
+	/// Leading zeros are removed (`007` becomes `7` and `00.5` becomes `0.5`)
+	/// as well as the `+` sign (`+1.5` becomes `1.5`).
+	pub fn new_lenient<B: AsRef<[u8]> + ?Sized>(data: &B) -> Result<NumberBuf, InvalidNumber<&B>> {
+		let s = data.as_ref();
+
+		if Number::new(s).is_ok() {
+			return Ok(unsafe { NumberBuf::new_unchecked(s.to_vec()) });
+		}
+
+		let (negative, unsigned) = match s {
+			[b'+', rest @ ..] => (false, rest),
+			[b'-', rest @ ..] => (true, rest),
+			_ => (false, s),
+		};
+
+		let mut result = Vec::with_capacity(s.len());
+		if negative {
+			result.push(b'-')
+		}
+
+		if push_decimal(&mut result, unsigned, Syntax::Lenient) {
+			Ok(unsafe { NumberBuf::new_unchecked(result) })
+		} else {
+			Err(InvalidNumber(data))
+		}
+	}
+}
+
+/// Non-standard decimal number syntax.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Syntax {
+	/// JSON5 syntax, allowing leading and trailing decimal points.
+	JSON5,
+
+	/// JSON syntax allowing leading zeros.
+	Lenient,
+}
+
+/// Pushes the decimal representation of the given hexadecimal digits.
+///
+/// Returns `false` if the input is not a non-empty sequence of hexadecimal
+/// digits.
+fn push_hex(result: &mut Vec<u8>, hex: &[u8]) -> bool {
+	if hex.is_empty() {
+		return false;
+	}
+
+	// Decimal digits, least significant first.
+	let mut digits = vec![0u8];
+
+	for b in hex {
+		let mut carry = match (*b as char).to_digit(16) {
+			Some(d) => d,
+			None => return false,
+		};
+
+		for d in &mut digits {
+			let v = *d as u32 * 16 + carry;
+			*d = (v % 10) as u8;
+			carry = v / 10
+		}
+
+		while carry > 0 {
+			digits.push((carry % 10) as u8);
+			carry /= 10
+		}
+	}
+
+	while digits.len() > 1 && digits.last() == Some(&0) {
+		digits.pop();
+	}
+
+	result.extend(digits.iter().rev().map(|d| b'0' + d));
+	true
+}
+
+/// Pushes the JSON representation of the given unsigned decimal number.
+///
+/// Returns `false` if the input is not a valid unsigned decimal number
+/// according to the given `syntax`.
+fn push_decimal(result: &mut Vec<u8>, s: &[u8], syntax: Syntax) -> bool {
+	let digits = |s: &[u8]| s.iter().take_while(|b| b.is_ascii_digit()).count();
+
+	let integer_len = digits(s);
+	let (mut integer, rest) = s.split_at(integer_len);
+
+	let (point, fraction, rest) = match rest {
+		[b'.', rest @ ..] => {
+			let (fraction, rest) = rest.split_at(digits(rest));
+			(true, fraction, rest)
+		}
+		_ => (false, &[][..], rest),
+	};
+
+	match syntax {
+		Syntax::JSON5 => {
+			if integer.is_empty() && fraction.is_empty() {
+				return false;
+			}
+
+			if integer.len() > 1 && integer[0] == b'0' {
+				return false;
+			}
+		}
+		Syntax::Lenient => {
+			if integer.is_empty() || (point && fraction.is_empty()) {
+				return false;
+			}
+
+			while integer.len() > 1 && integer[0] == b'0' {
+				integer = &integer[1..]
+			}
+		}
+	}
+
+	let exponent = match rest {
+		[] => rest,
+		[b'e' | b'E', exponent @ ..] => {
+			let unsigned = match exponent {
+				[b'+' | b'-', unsigned @ ..] => unsigned,
+				_ => exponent,
+			};
+
+			if unsigned.is_empty() || digits(unsigned) != unsigned.len() {
+				return false;
+			}
+
+			rest
+		}
+		_ => return false,
+	};
+
+	if integer.is_empty() {
+		result.push(b'0')
+	} else {
+		result.extend_from_slice(integer)
+	}
+
+	if !fraction.is_empty() {
+		result.push(b'.');
+		result.extend_from_slice(fraction)
+	}
+
+	result.extend_from_slice(exponent);
+	true
+}
+
+#[cfg(test)]
+mod tests {
+	use super::RelaxedNumberError;
+	use crate::Number;
+
+	fn relaxed(s: &str) -> Result<String, RelaxedNumberError<&str>> {
+		Number::new_relaxed(s).map(|n| n.as_str().to_owned())
+	}
+
+	#[test]
+	fn json() {
+		for s in ["0", "-0", "12.5", "-1.5e-3", "1E+10"] {
+			assert_eq!(relaxed(s).unwrap(), s)
+		}
+	}
+
+	#[test]
+	fn json5() {
+		assert_eq!(relaxed("+1").unwrap(), "1");
+		assert_eq!(relaxed("+1.5e2").unwrap(), "1.5e2");
+		assert_eq!(relaxed(".5").unwrap(), "0.5");
+		assert_eq!(relaxed("-.5").unwrap(), "-0.5");
+		assert_eq!(relaxed("5.").unwrap(), "5");
+		assert_eq!(relaxed("5.e3").unwrap(), "5e3");
+		assert_eq!(relaxed("0x1F").unwrap(), "31");
+		assert_eq!(relaxed("-0XdecaF").unwrap(), "-912559");
+		assert_eq!(relaxed("0x0").unwrap(), "0");
+		assert_eq!(
+			relaxed("0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF").unwrap(),
+			u128::MAX.to_string()
+		);
+	}
+
+	#[test]
+	fn lenient() {
+		let lenient = |s| Number::new_lenient(s).map(|n| n.as_str().to_owned());
+		assert_eq!(lenient("12.5e01").unwrap(), "12.5e01");
+		assert_eq!(lenient("007").unwrap(), "7");
+		assert_eq!(lenient("-007").unwrap(), "-7");
+		assert_eq!(lenient("000").unwrap(), "0");
+		assert_eq!(lenient("00.5").unwrap(), "0.5");
+		assert_eq!(lenient("+1.5").unwrap(), "1.5");
+		assert_eq!(lenient("+0012E3").unwrap(), "12E3");
+		for s in ["", "+", "+-1", ".5", "5.", "5.e3", "0x1", "Infinity", "1e"] {
+			assert!(lenient(s).is_err())
+		}
+	}
+
+	#[test]
+	fn non_finite() {
+		for s in ["Infinity", "+Infinity", "-Infinity", "NaN", "-NaN"] {
+			assert!(matches!(relaxed(s), Err(RelaxedNumberError::NonFinite(_))))
+		}
+	}
+
+	#[test]
+	fn invalid() {
+		for s in [
+			"", "+", ".", "-.", "+-1", "01", "0x", "0xG", "1e", "1e+", ".e1", "1.5.", "inf", "0b1",
+		] {
+			assert!(matches!(relaxed(s), Err(RelaxedNumberError::Invalid(_))))
+		}
+	}
+}
@@ -0,0 +1,183 @@
+//! Zero-copy archives of numbers.
+//!
+//! A [`NumberBuf`] is archived as an [`ArchivedNumber`], holding the
+//! lexical representation of the number. Validating an archive (with
+//! [`rkyv::access`] for instance) checks that every archived number is a
+//! valid JSON number, so archived numbers can then be used as [`Number`]
+//! without deserialization.
+use crate::{Buffer, InvalidNumber, Number, NumberBuf};
+use alloc::string::String;
+use core::{borrow::Borrow, fmt, ops::Deref};
+use rkyv::{
+	bytecheck::CheckBytes,
+	rancor::{Fallible, Source},
+	ser::{Allocator, Writer},
+	vec::{ArchivedVec, VecResolver},
+	Archive, Deserialize, Place, Portable, Serialize,
+};
+
+/// Archived [`NumberBuf`].
+#[repr(transparent)]
+pub struct ArchivedNumber(ArchivedVec<u8>);
+
+// SAFETY: `ArchivedNumber` is a transparent wrapper around the portable
+// `ArchivedVec<u8>`.
+unsafe impl Portable for ArchivedNumber {}
+
+impl ArchivedNumber {
+	/// Returns the archived number.
+	#[inline(always)]
+	pub fn as_number(&self) -> &Number {
+		// SAFETY: archived numbers are valid numbers. This is checked during
+		// validation.
+		unsafe { Number::new_unchecked(self.0.as_slice()) }
+	}
+}
+
+impl Deref for ArchivedNumber {
+	type Target = Number;
+
+	#[inline(always)]
+	fn deref(&self) -> &Number {
+		self.as_number()
+	}
+}
+
+impl AsRef<Number> for ArchivedNumber {
+	#[inline(always)]
+	fn as_ref(&self) -> &Number {
+		self.as_number()
+	}
+}
+
+impl Borrow<Number> for ArchivedNumber {
+	#[inline(always)]
+	fn borrow(&self) -> &Number {
+		self.as_number()
+	}
+}
+
+impl PartialEq for ArchivedNumber {
+	#[inline(always)]
+	fn eq(&self, other: &Self) -> bool {
+		self.as_number() == other.as_number()
+	}
+}
+
+impl Eq for ArchivedNumber {}
+
+impl PartialEq<Number> for ArchivedNumber {
+	#[inline(always)]
+	fn eq(&self, other: &Number) -> bool {
+		self.as_number() == other
+	}
+}
+
+impl<B: Buffer> PartialEq<NumberBuf<B>> for ArchivedNumber {
+	#[inline(always)]
+	fn eq(&self, other: &NumberBuf<B>) -> bool {
+		self.as_number() == other.as_number()
+	}
+}
+
+impl fmt::Display for ArchivedNumber {
+	#[inline(always)]
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.as_number().fmt(f)
+	}
+}
+
+impl fmt::Debug for ArchivedNumber {
+	#[inline(always)]
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.as_number().fmt(f)
+	}
+}
+
+// SAFETY: the bytes are checked to be a valid `ArchivedVec<u8>` holding a
+// valid number.
+unsafe impl<C> CheckBytes<C> for ArchivedNumber
+where
+	C: Fallible + ?Sized,
+	C::Error: Source,
+	ArchivedVec<u8>: CheckBytes<C>,
+{
+	unsafe fn check_bytes(value: *const Self, context: &mut C) -> Result<(), C::Error> {
+		ArchivedVec::<u8>::check_bytes(value.cast(), context)?;
+
+		let bytes = (*value).0.as_slice();
+		match Number::new(bytes) {
+			Ok(_) => Ok(()),
+			Err(_) => Err(C::Error::new(InvalidNumber(
+				String::from_utf8_lossy(bytes).into_owned(),
+			))),
+		}
+	}
+}
+
+impl<B: Buffer> Archive for NumberBuf<B> {
+	type Archived = ArchivedNumber;
+	type Resolver = VecResolver;
+
+	#[inline(always)]
+	fn resolve(&self, resolver: VecResolver, out: Place<ArchivedNumber>) {
+		// SAFETY: `ArchivedNumber` is a transparent wrapper around
+		// `ArchivedVec<u8>`.
+		let out = unsafe { out.cast_unchecked::<ArchivedVec<u8>>() };
+		ArchivedVec::resolve_from_slice(self.as_bytes(), resolver, out)
+	}
+}
+
+impl<B: Buffer, S> Serialize<S> for NumberBuf<B>
+where
+	S: Fallible + Allocator + Writer + ?Sized,
+{
+	#[inline(always)]
+	fn serialize(&self, serializer: &mut S) -> Result<VecResolver, S::Error> {
+		ArchivedVec::serialize_from_slice(self.as_bytes(), serializer)
+	}
+}
+
+impl<B: Buffer, D: Fallible + ?Sized> Deserialize<NumberBuf<B>, D> for ArchivedNumber {
+	#[inline(always)]
+	fn deserialize(&self, _: &mut D) -> Result<NumberBuf<B>, D::Error> {
+		Ok(unsafe { NumberBuf::new_unchecked(B::from_bytes(self.0.as_slice())) })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::ArchivedNumber;
+	use crate::NumberBuf;
+	use rkyv::{rancor::Error, vec::ArchivedVec};
+
+	#[test]
+	fn round_trip() {
+		let numbers: Vec<NumberBuf> = ["0", "-1.10", "1e-999", "123456789012345678901234567890"]
+			.iter()
+			.map(|s| s.parse().unwrap())
+			.collect();
+
+		let bytes = rkyv::to_bytes::<Error>(&numbers).unwrap();
+		let archived = rkyv::access::<ArchivedVec<ArchivedNumber>, Error>(&bytes).unwrap();
+		assert_eq!(archived.len(), numbers.len());
+		for (a, n) in archived.iter().zip(&numbers) {
+			assert_eq!(a, n);
+			assert_eq!(a.as_str(), n.as_str())
+		}
+
+		assert_eq!(archived[1].as_f64_lossy(), -1.1);
+
+		let deserialized = rkyv::deserialize::<Vec<NumberBuf>, Error>(archived).unwrap();
+		assert_eq!(deserialized, numbers)
+	}
+
+	#[test]
+	fn invalid_archive() {
+		let n: NumberBuf = "12.5".parse().unwrap();
+		let mut bytes = rkyv::to_bytes::<Error>(&n).unwrap();
+		let i = bytes.iter().position(|b| *b == b'.').unwrap();
+		bytes[i] = b'x';
+		assert!(rkyv::access::<ArchivedNumber, Error>(&bytes).is_err())
+	}
+}
@@ -0,0 +1,223 @@
+//! MessagePack support.
+//!
+//! The arbitrary precision structure used to serialize numbers in
+//! human-readable formats has no meaning in MessagePack, where it appears as
+//! a map with a magic key. The [`Ext`] wrapper instead serializes numbers as
+//! a MessagePack extension type holding their lexical representation, as
+//! understood by [`rmp-serde`](https://docs.rs/rmp-serde).
+use crate::{Buffer, Number, NumberBuf};
+use alloc::vec::Vec;
+use core::{borrow::Borrow, fmt, marker::PhantomData, ops::Deref};
+use serde::{
+	de::{self, Deserialize, Deserializer, SeqAccess},
+	ser::{Serialize, SerializeTuple, Serializer},
+};
+
+/// MessagePack extension type used to encode numbers.
+pub const EXT_TYPE: i8 = 74;
+
+/// Name of the newtype struct through which `rmp-serde` encodes extension
+/// types.
+const EXT_STRUCT_NAME: &str = "_ExtStruct";
+
+/// Number wrapper serialized as a MessagePack extension type.
+///
+/// In non human-readable formats, the number is serialized as the
+/// `(EXT_TYPE, bytes)` tuple of a newtype struct named `_ExtStruct`, which
+/// `rmp-serde` encodes as a MessagePack extension of type [`EXT_TYPE`]
+/// whose data is the lexical representation of the number. Its exact digits
+/// are thus preserved through JSON → MessagePack → JSON round trips.
+///
+/// Human-readable formats are unaffected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ext<N>(pub N);
+
+impl<N> Ext<N> {
+	/// Returns the wrapped number.
+	#[inline(always)]
+	pub fn into_inner(self) -> N {
+		self.0
+	}
+}
+
+impl<N> Deref for Ext<N> {
+	type Target = N;
+
+	#[inline(always)]
+	fn deref(&self) -> &N {
+		&self.0
+	}
+}
+
+/// Bytes serialized with `serialize_bytes`.
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+	#[inline(always)]
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_bytes(self.0)
+	}
+}
+
+/// Extension type payload.
+struct Payload<'a>(&'a Number);
+
+impl Serialize for Payload<'_> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut tuple = serializer.serialize_tuple(2)?;
+		tuple.serialize_element(&EXT_TYPE)?;
+		tuple.serialize_element(&Bytes(self.0.as_bytes()))?;
+		tuple.end()
+	}
+}
+
+impl<N: Borrow<Number>> Serialize for Ext<N> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let n = self.0.borrow();
+		if serializer.is_human_readable() {
+			n.serialize(serializer)
+		} else {
+			serializer.serialize_newtype_struct(EXT_STRUCT_NAME, &Payload(n))
+		}
+	}
+}
+
+impl<'de, B: Buffer> Deserialize<'de> for Ext<NumberBuf<B>> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		if deserializer.is_human_readable() {
+			NumberBuf::deserialize(deserializer).map(Self)
+		} else {
+			deserializer
+				.deserialize_newtype_struct(EXT_STRUCT_NAME, ExtVisitor(PhantomData))
+				.map(Self)
+		}
+	}
+}
+
+/// Extension type visitor.
+struct ExtVisitor<B>(PhantomData<B>);
+
+impl<'de, B: Buffer> de::Visitor<'de> for ExtVisitor<B> {
+	type Value = NumberBuf<B>;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		write!(formatter, "a MessagePack number extension")
+	}
+
+	#[inline(always)]
+	fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserializer.deserialize_tuple(2, self)
+	}
+
+	fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: SeqAccess<'de>,
+	{
+		let ty: i8 = seq
+			.next_element()?
+			.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+		if ty != EXT_TYPE {
+			return Err(de::Error::invalid_value(
+				de::Unexpected::Signed(ty as i64),
+				&"the number extension type",
+			));
+		}
+
+		let ByteBuf(bytes) = seq
+			.next_element()?
+			.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+		match Number::new(&bytes) {
+			Ok(_) => Ok(unsafe { NumberBuf::new_unchecked(B::from_vec(bytes)) }),
+			Err(_) => Err(de::Error::invalid_value(
+				de::Unexpected::Bytes(&bytes),
+				&"a JSON number",
+			)),
+		}
+	}
+}
+
+/// Bytes deserialized with `deserialize_bytes`.
+struct ByteBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+	#[inline(always)]
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserializer.deserialize_bytes(ByteBufVisitor)
+	}
+}
+
+/// Byte buffer visitor.
+struct ByteBufVisitor;
+
+impl<'de> de::Visitor<'de> for ByteBufVisitor {
+	type Value = ByteBuf;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		write!(formatter, "bytes")
+	}
+
+	#[inline(always)]
+	fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+		Ok(ByteBuf(v.to_vec()))
+	}
+
+	#[inline(always)]
+	fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+		Ok(ByteBuf(v))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Ext, EXT_TYPE};
+	use crate::NumberBuf;
+	type Data = Vec<Ext<NumberBuf>>;
+
+	#[test]
+	fn json_msgpack_json() {
+		for json in [
+			"[1.10]",
+			"[-12,0.5]",
+			"[1e-999]",
+			"[123456789012345678901234567890]",
+		] {
+			let data: Data = serde_json::from_str(json).unwrap();
+			let msgpack = rmp_serde::to_vec(&data).unwrap();
+			let data: Data = rmp_serde::from_slice(&msgpack).unwrap();
+			assert_eq!(serde_json::to_string(&data).unwrap(), json)
+		}
+	}
+
+	#[test]
+	fn ext_encoding() {
+		let n = Ext(NumberBuf::new(b"1.5".to_vec()).unwrap());
+		let msgpack = rmp_serde::to_vec(&n).unwrap();
+		assert_eq!(msgpack, [0xc7, 3, EXT_TYPE as u8, b'1', b'.', b'5']);
+
+		let invalid = [0xc7, 2, EXT_TYPE as u8, b'1', b'.'];
+		assert!(rmp_serde::from_slice::<Ext<NumberBuf>>(&invalid).is_err());
+
+		let other = [0xc7, 3, 1, b'1', b'.', b'5'];
+		assert!(rmp_serde::from_slice::<Ext<NumberBuf>>(&other).is_err())
+	}
+}
@@ -0,0 +1,154 @@
+//! Rounding.
+use crate::{decimal::Decimal, Number, NumberBuf};
+use alloc::borrow::ToOwned;
+use core::cmp::Ordering;
+
+/// Rounding mode.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum RoundingMode {
+	/// Round to the nearest value, ties to the even neighbor.
+	#[default]
+	HalfEven,
+
+	/// Round to the nearest value, ties away from zero.
+	HalfUp,
+
+	/// Round toward negative infinity.
+	Floor,
+
+	/// Round toward positive infinity.
+	Ceil,
+
+	/// Round toward zero.
+	Trunc,
+}
+
+impl RoundingMode {
+	/// Decides if an inexact result must be rounded away from zero.
+	///
+	/// The `half` parameter gives how the discarded part compares to half a
+	/// unit of the last kept digit, which is odd if `odd` is `true`.
+	pub(crate) fn rounds_away_from_zero(self, negative: bool, odd: bool, half: Ordering) -> bool {
+		match self {
+			Self::HalfEven => half == Ordering::Greater || (half == Ordering::Equal && odd),
+			Self::HalfUp => half != Ordering::Less,
+			Self::Floor => negative,
+			Self::Ceil => !negative,
+			Self::Trunc => false,
+		}
+	}
+}
+
+impl Number {
+	/// Rounds this number to `places` digits after the decimal point.
+	///
+	/// The result is written in the same notation as arithmetic operation
+	/// results, without trailing fractional zeros. Numbers with an exponent
+	/// greater than [`Number::MAX_EXACT_EXPONENT`] are integers, and hence
+	/// returned unchanged.
+	pub fn round_to_places(&self, places: u32, rounding: RoundingMode) -> NumberBuf {
+		let d = Decimal::new(self);
+		if d.is_saturated() && d.exponent > 0 {
+			return self.to_owned();
+		}
+
+		d.round(-(places as i64), rounding).into_number_buf()
+	}
+
+	/// Rounds this number to `digits` significant digits.
+	///
+	/// The result is written in the same notation as arithmetic operation
+	/// results, without trailing fractional zeros.
+	///
+	/// # Panics
+	///
+	/// Panics if `digits` is zero.
+	pub fn round_to_significant(&self, digits: u32, rounding: RoundingMode) -> NumberBuf {
+		assert!(digits > 0, "no significant digits");
+		let d = Decimal::new(self);
+		if d.is_saturated() {
+			// Round the significand, and shift the result by the exact
+			// exponent.
+			let (significand, exponent) = Decimal::split_exponent(self);
+			let last = significand.point().saturating_sub(digits as i64);
+			let bytes = significand
+				.round(last, rounding)
+				.to_scientific_shifted(b'e', None, &exponent);
+			return unsafe { NumberBuf::new_unchecked(bytes) };
+		}
+
+		let exponent = d.point().saturating_sub(digits as i64);
+		d.round(exponent, rounding).into_number_buf()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Number, RoundingMode};
+
+	fn places(s: &str, places: u32, rounding: RoundingMode) -> String {
+		Number::new(s)
+			.unwrap()
+			.round_to_places(places, rounding)
+			.as_str()
+			.to_owned()
+	}
+
+	fn significant(s: &str, digits: u32, rounding: RoundingMode) -> String {
+		Number::new(s)
+			.unwrap()
+			.round_to_significant(digits, rounding)
+			.as_str()
+			.to_owned()
+	}
+
+	#[test]
+	fn round_to_places() {
+		use RoundingMode::*;
+		assert_eq!(places("2.675", 2, HalfUp), "2.68");
+		assert_eq!(places("2.675", 2, HalfEven), "2.68");
+		assert_eq!(places("2.665", 2, HalfEven), "2.66");
+		assert_eq!(places("2.6651", 2, HalfEven), "2.67");
+		assert_eq!(places("-2.675", 2, HalfUp), "-2.68");
+		assert_eq!(places("-2.671", 2, Floor), "-2.68");
+		assert_eq!(places("-2.679", 2, Ceil), "-2.67");
+		assert_eq!(places("2.679", 2, Trunc), "2.67");
+		assert_eq!(places("9.999", 2, HalfEven), "10");
+		assert_eq!(places("1.5", 0, HalfEven), "2");
+		assert_eq!(places("2.5", 0, HalfEven), "2");
+		assert_eq!(places("0.004", 2, HalfEven), "0");
+		assert_eq!(places("0.004", 2, Ceil), "0.01");
+		assert_eq!(places("0.005", 2, HalfUp), "0.01");
+		assert_eq!(places("0.0001", 2, Ceil), "0.01");
+		assert_eq!(places("1.25e1", 0, HalfEven), "12");
+		assert_eq!(places("12", 2, HalfEven), "12");
+		assert_eq!(
+			places("1.5e99999999999999999999", 2, HalfEven),
+			"1.5e99999999999999999999"
+		);
+		assert_eq!(places("1e-99999999999999999999", 2, HalfEven), "0");
+		assert_eq!(places("-1e-99999999999999999999", 2, Floor), "-0.01");
+	}
+
+	#[test]
+	fn round_to_significant() {
+		use RoundingMode::*;
+		assert_eq!(significant("123456", 2, HalfEven), "120000");
+		assert_eq!(significant("0.0012345", 3, HalfUp), "0.00123");
+		assert_eq!(significant("-9.96", 2, HalfEven), "-10");
+		assert_eq!(significant("1.5e-20", 1, HalfEven), "2e-20");
+		assert_eq!(significant("0", 3, Ceil), "0");
+		assert_eq!(
+			significant("1.25e99999999999999999999", 2, HalfEven),
+			"1.2e99999999999999999999"
+		);
+		assert_eq!(
+			significant("-99.5E+99999999999999999999", 2, HalfUp),
+			"-1e100000000000000000001"
+		);
+		assert_eq!(
+			significant("123e-99999999999999999999", 1, Trunc),
+			"1e-99999999999999999997"
+		);
+	}
+}
@@ -0,0 +1,113 @@
+use crate::{decimal, Buffer, Number, NumberBuf};
+use alloc::string::ToString;
+use core::fmt;
+use rust_decimal::Decimal;
+
+/// Maximum scale of a [`Decimal`].
+const MAX_SCALE: i64 = 28;
+
+/// Maximum mantissa of a [`Decimal`] (`2^96 - 1`).
+const MAX_MANTISSA: u128 = (1 << 96) - 1;
+
+/// Error returned when converting a [`Number`] into a [`Decimal`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TryIntoDecimalError {
+	/// The number has more than 28 fractional digits.
+	Scale,
+
+	/// The number's significant digits do not fit in 96 bits.
+	Overflow,
+}
+
+impl fmt::Display for TryIntoDecimalError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Scale => f.write_str("too many fractional digits for a decimal"),
+			Self::Overflow => f.write_str("number too large for a decimal"),
+		}
+	}
+}
+
+impl core::error::Error for TryIntoDecimalError {}
+
+impl<'n> TryFrom<&'n Number> for Decimal {
+	type Error = TryIntoDecimalError;
+
+	fn try_from(n: &'n Number) -> Result<Self, Self::Error> {
+		let mut d = decimal::Decimal::new(n);
+
+		let scale = if d.exponent < 0 {
+			if -d.exponent > MAX_SCALE {
+				return Err(TryIntoDecimalError::Scale);
+			}
+
+			let scale = -d.exponent as u32;
+			d.exponent = 0;
+			scale
+		} else {
+			0
+		};
+
+		let mantissa = d
+			.to_u128()
+			.filter(|m| *m <= MAX_MANTISSA)
+			.ok_or(TryIntoDecimalError::Overflow)?;
+
+		Ok(Decimal::from_parts(
+			mantissa as u32,
+			(mantissa >> 32) as u32,
+			(mantissa >> 64) as u32,
+			d.negative,
+			scale,
+		))
+	}
+}
+
+impl<B: Buffer> TryFrom<NumberBuf<B>> for Decimal {
+	type Error = TryIntoDecimalError;
+
+	#[inline(always)]
+	fn try_from(n: NumberBuf<B>) -> Result<Self, Self::Error> {
+		n.as_number().try_into()
+	}
+}
+
+impl<B: Buffer> From<Decimal> for NumberBuf<B> {
+	#[inline(always)]
+	fn from(d: Decimal) -> Self {
+		unsafe { Self::new_unchecked(B::from_vec(d.to_string().into_bytes())) }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Number, NumberBuf};
+	use rust_decimal::Decimal;
+
+	fn to_decimal(s: &str) -> Result<Decimal, super::TryIntoDecimalError> {
+		Number::new(s).unwrap().try_into()
+	}
+
+	#[test]
+	fn number_to_decimal() {
+		assert_eq!(to_decimal("12.34").unwrap(), Decimal::new(1234, 2));
+		assert_eq!(to_decimal("-1.5e2").unwrap(), Decimal::new(-150, 0));
+		assert_eq!(to_decimal("25e-28").unwrap(), Decimal::new(25, 28));
+		assert_eq!(to_decimal("0e99").unwrap(), Decimal::ZERO);
+		assert_eq!(
+			to_decimal("79228162514264337593543950335").unwrap(),
+			Decimal::MAX
+		);
+		assert_eq!(to_decimal("1e-29"), Err(super::TryIntoDecimalError::Scale));
+		assert_eq!(
+			to_decimal("79228162514264337593543950336"),
+			Err(super::TryIntoDecimalError::Overflow)
+		);
+	}
+
+	#[test]
+	fn decimal_to_number() {
+		let n: NumberBuf = Decimal::new(-1250, 3).into();
+		assert_eq!(n.as_str(), "-1.250")
+	}
+}
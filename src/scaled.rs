@@ -0,0 +1,151 @@
+//! Scaled integer conversions and decompositions.
+use crate::{decimal::Decimal, Number, RoundingMode};
+use core::fmt;
+
+/// Scaled integer conversion error.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScaleError {
+	/// The scaled value does not fit in the target integer type.
+	Overflow,
+
+	/// The number has more fraction digits than the scale allows.
+	Inexact,
+}
+
+impl fmt::Display for ScaleError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Overflow => f.write_str("scaled number out of range"),
+			Self::Inexact => f.write_str("number has too many fraction digits"),
+		}
+	}
+}
+
+impl core::error::Error for ScaleError {}
+
+/// Returns the given decimal multiplied by `10^scale`, which must be an
+/// integer.
+fn scaled_i128(mut d: Decimal, scale: u32) -> Result<i128, ScaleError> {
+	d.exponent = d.exponent.saturating_add(scale as i64);
+	d.to_i128().ok_or(ScaleError::Overflow)
+}
+
+impl Number {
+	/// Returns the value of this number multiplied by `10^scale`, as an exact
+	/// integer.
+	///
+	/// This is the fixed-point representation of the number with `scale`
+	/// fraction digits: `12.34` with a scale of `3` gives `12340`. Fails if
+	/// the number has more than `scale` significant fraction digits, or if
+	/// the result does not fit in an `i128`.
+	pub fn to_scaled_i128(&self, scale: u32) -> Result<i128, ScaleError> {
+		let d = Decimal::new(self);
+		if d.exponent < -(scale as i64) {
+			return Err(ScaleError::Inexact);
+		}
+
+		scaled_i128(d, scale)
+	}
+
+	/// Returns the value of this number multiplied by `10^scale`, rounded to
+	/// an integer using the given rounding mode.
+	///
+	/// Fails if the result does not fit in an `i128`.
+	pub fn to_scaled_i128_rounded(
+		&self,
+		scale: u32,
+		rounding: RoundingMode,
+	) -> Result<i128, ScaleError> {
+		let d = Decimal::new(self).round(-(scale as i64), rounding);
+		scaled_i128(d, scale)
+	}
+
+	/// Decomposes the value of this number into a coefficient and an
+	/// exponent, such that the value is `coefficient × 10^exponent`.
+	///
+	/// The coefficient has no trailing zeros (`1.500e3` gives `(15, 2)`), and
+	/// zero gives `(0, 0)`. Returns `None` if the coefficient does not fit in
+	/// an `i128` or the exponent in an `i32`.
+	pub fn to_mantissa_exponent(&self) -> Option<(i128, i32)> {
+		let mut d = Decimal::new(self);
+		let exponent = i32::try_from(d.exponent).ok()?;
+		d.exponent = 0;
+		Some((d.to_i128()?, exponent))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::ScaleError;
+	use crate::{Number, RoundingMode};
+
+	fn number(s: &str) -> &Number {
+		Number::new(s).unwrap()
+	}
+
+	#[test]
+	fn to_scaled_i128() {
+		assert_eq!(number("12.34").to_scaled_i128(2), Ok(1234));
+		assert_eq!(number("12.34").to_scaled_i128(3), Ok(12340));
+		assert_eq!(number("-0.5").to_scaled_i128(2), Ok(-50));
+		assert_eq!(number("1.2340").to_scaled_i128(3), Ok(1234));
+		assert_eq!(number("1e2").to_scaled_i128(0), Ok(100));
+		assert_eq!(number("1234e-2").to_scaled_i128(2), Ok(1234));
+		assert_eq!(number("-0.0").to_scaled_i128(4), Ok(0));
+		assert_eq!(number("12.345").to_scaled_i128(2), Err(ScaleError::Inexact));
+		assert_eq!(
+			number("1e-1000").to_scaled_i128(2),
+			Err(ScaleError::Inexact)
+		);
+		assert_eq!(
+			number("-170141183460469231731687303715884105728").to_scaled_i128(0),
+			Ok(i128::MIN)
+		);
+		assert_eq!(
+			number("170141183460469231731687303715884105728").to_scaled_i128(0),
+			Err(ScaleError::Overflow)
+		);
+		assert_eq!(number("1e38").to_scaled_i128(1), Err(ScaleError::Overflow));
+		assert_eq!(
+			number("1e1000000000").to_scaled_i128(2),
+			Err(ScaleError::Overflow)
+		)
+	}
+
+	#[test]
+	fn to_mantissa_exponent() {
+		let decompose = |s| number(s).to_mantissa_exponent();
+		assert_eq!(decompose("1.500e3"), Some((15, 2)));
+		assert_eq!(decompose("-12.34"), Some((-1234, -2)));
+		assert_eq!(decompose("100"), Some((1, 2)));
+		assert_eq!(decompose("0.00120"), Some((12, -4)));
+		assert_eq!(decompose("-0.0e5"), Some((0, 0)));
+		assert_eq!(decompose("1e-2147483648"), Some((1, i32::MIN)));
+		assert_eq!(decompose("1e2147483648"), None);
+		assert_eq!(
+			decompose("-170141183460469231731687303715884105728e-3"),
+			Some((i128::MIN, -3))
+		);
+		assert_eq!(decompose("170141183460469231731687303715884105727.1"), None)
+	}
+
+	#[test]
+	fn to_scaled_i128_rounded() {
+		use RoundingMode::*;
+		assert_eq!(
+			number("12.345").to_scaled_i128_rounded(2, HalfEven),
+			Ok(1234)
+		);
+		assert_eq!(number("12.345").to_scaled_i128_rounded(2, HalfUp), Ok(1235));
+		assert_eq!(
+			number("-12.341").to_scaled_i128_rounded(2, Floor),
+			Ok(-1235)
+		);
+		assert_eq!(number("1e-1000").to_scaled_i128_rounded(2, Ceil), Ok(1));
+		assert_eq!(number("12.3").to_scaled_i128_rounded(2, Trunc), Ok(1230));
+		assert_eq!(
+			number("1e39").to_scaled_i128_rounded(0, HalfEven),
+			Err(ScaleError::Overflow)
+		)
+	}
+}
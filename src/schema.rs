@@ -0,0 +1,201 @@
+//! JSON Schema numeric validation.
+//!
+//! Implements the numeric keywords of the JSON Schema validation
+//! vocabulary using exact decimal arithmetic, so that `0.3` is a multiple
+//! of `0.1` and `1e400` is greater than `1.7976931348623157e308`.
+use crate::{decimal::Decimal, Buffer, Number, NumberBuf};
+use alloc::vec::Vec;
+use core::{cmp::Ordering, fmt};
+
+/// Numeric constraints of a JSON Schema.
+#[derive(Clone, PartialEq, Eq)]
+pub struct NumericConstraints<B = Vec<u8>> {
+	/// Inclusive lower bound (`minimum` keyword).
+	pub minimum: Option<NumberBuf<B>>,
+
+	/// Inclusive upper bound (`maximum` keyword).
+	pub maximum: Option<NumberBuf<B>>,
+
+	/// Exclusive lower bound (`exclusiveMinimum` keyword).
+	pub exclusive_minimum: Option<NumberBuf<B>>,
+
+	/// Exclusive upper bound (`exclusiveMaximum` keyword).
+	pub exclusive_maximum: Option<NumberBuf<B>>,
+
+	/// Divisor of valid numbers (`multipleOf` keyword).
+	///
+	/// JSON Schema requires it to be strictly positive. If it is zero, only
+	/// zero is valid.
+	pub multiple_of: Option<NumberBuf<B>>,
+}
+
+impl<B> Default for NumericConstraints<B> {
+	fn default() -> Self {
+		Self {
+			minimum: None,
+			maximum: None,
+			exclusive_minimum: None,
+			exclusive_maximum: None,
+			multiple_of: None,
+		}
+	}
+}
+
+impl<B: Buffer> fmt::Debug for NumericConstraints<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("NumericConstraints")
+			.field("minimum", &self.minimum)
+			.field("maximum", &self.maximum)
+			.field("exclusive_minimum", &self.exclusive_minimum)
+			.field("exclusive_maximum", &self.exclusive_maximum)
+			.field("multiple_of", &self.multiple_of)
+			.finish()
+	}
+}
+
+/// Violated numeric constraint.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConstraintError {
+	/// The number is lower than `minimum`.
+	Minimum,
+
+	/// The number is greater than `maximum`.
+	Maximum,
+
+	/// The number is lower than or equal to `exclusiveMinimum`.
+	ExclusiveMinimum,
+
+	/// The number is greater than or equal to `exclusiveMaximum`.
+	ExclusiveMaximum,
+
+	/// The number is not a multiple of `multipleOf`.
+	MultipleOf,
+}
+
+impl fmt::Display for ConstraintError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Minimum => f.write_str("number lower than the minimum"),
+			Self::Maximum => f.write_str("number greater than the maximum"),
+			Self::ExclusiveMinimum => f.write_str("number not greater than the exclusive minimum"),
+			Self::ExclusiveMaximum => f.write_str("number not lower than the exclusive maximum"),
+			Self::MultipleOf => f.write_str("number not a multiple of the given divisor"),
+		}
+	}
+}
+
+impl core::error::Error for ConstraintError {}
+
+impl<B: Buffer> NumericConstraints<B> {
+	/// Checks that the given number satisfies every constraint, returning
+	/// the first violated one otherwise.
+	///
+	/// Values are compared exactly, regardless of their lexical
+	/// representations. A bound is violated if its order with the number
+	/// depends on the exact value of an exponent greater than
+	/// [`Number::MAX_EXACT_EXPONENT`] in absolute value.
+	pub fn validate(&self, n: &Number) -> Result<(), ConstraintError> {
+		let d = Decimal::new(n);
+		let cmp = |bound: &Option<NumberBuf<B>>| {
+			bound
+				.as_ref()
+				.map(|bound| d.partial_cmp_signed(&Decimal::new(bound)))
+		};
+
+		if matches!(cmp(&self.minimum), Some(None | Some(Ordering::Less))) {
+			return Err(ConstraintError::Minimum);
+		}
+
+		if matches!(cmp(&self.maximum), Some(None | Some(Ordering::Greater))) {
+			return Err(ConstraintError::Maximum);
+		}
+
+		if matches!(cmp(&self.exclusive_minimum), Some(o) if o != Some(Ordering::Greater)) {
+			return Err(ConstraintError::ExclusiveMinimum);
+		}
+
+		if matches!(cmp(&self.exclusive_maximum), Some(o) if o != Some(Ordering::Less)) {
+			return Err(ConstraintError::ExclusiveMaximum);
+		}
+
+		if let Some(divisor) = &self.multiple_of {
+			if !n.is_multiple_of(divisor) {
+				return Err(ConstraintError::MultipleOf);
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{ConstraintError, NumericConstraints};
+	use crate::{Number, NumberBuf};
+
+	fn number(s: &str) -> NumberBuf {
+		s.parse().unwrap()
+	}
+
+	fn validate(c: &NumericConstraints, s: &str) -> Result<(), ConstraintError> {
+		c.validate(Number::new(s).unwrap())
+	}
+
+	#[test]
+	fn bounds() {
+		let c = NumericConstraints {
+			minimum: Some(number("0")),
+			maximum: Some(number("1e2")),
+			..Default::default()
+		};
+
+		assert_eq!(validate(&c, "0.0"), Ok(()));
+		assert_eq!(validate(&c, "100"), Ok(()));
+		assert_eq!(validate(&c, "-1e-400"), Err(ConstraintError::Minimum));
+		assert_eq!(
+			validate(&c, "100.000000000000000001"),
+			Err(ConstraintError::Maximum)
+		);
+
+		let c = NumericConstraints {
+			exclusive_minimum: Some(number("0")),
+			exclusive_maximum: Some(number("1.7976931348623157e308")),
+			..Default::default()
+		};
+
+		assert_eq!(validate(&c, "1e-400"), Ok(()));
+		assert_eq!(validate(&c, "-0.0"), Err(ConstraintError::ExclusiveMinimum));
+		assert_eq!(
+			validate(&c, "17976931348623157e292"),
+			Err(ConstraintError::ExclusiveMaximum)
+		);
+		assert_eq!(
+			validate(&c, "1e400"),
+			Err(ConstraintError::ExclusiveMaximum)
+		);
+
+		let c = NumericConstraints {
+			maximum: Some(number("1e99999999999999999999")),
+			..Default::default()
+		};
+
+		assert_eq!(validate(&c, "1e100"), Ok(()));
+		assert_eq!(
+			validate(&c, "1e99999999999999999998"),
+			Err(ConstraintError::Maximum)
+		)
+	}
+
+	#[test]
+	fn multiple_of() {
+		let c = NumericConstraints {
+			multiple_of: Some(number("0.1")),
+			..Default::default()
+		};
+
+		assert_eq!(validate(&c, "0.3"), Ok(()));
+		assert_eq!(validate(&c, "4.5e10"), Ok(()));
+		assert_eq!(validate(&c, "0.35"), Err(ConstraintError::MultipleOf));
+		assert_eq!(validate(&NumericConstraints::default(), "0.35"), Ok(()))
+	}
+}
@@ -0,0 +1,280 @@
+use crate::{CowNumber, IntNumber, IntNumberBuf, Number, NumberBuf, UNumber, UNumberBuf};
+use alloc::borrow::Cow;
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+/// JSON number regular expression, used for numbers serialized as strings.
+#[cfg(feature = "serde")]
+const NUMBER_PATTERN: &str = r"^-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?$";
+
+/// Schema of arbitrary precision numbers.
+///
+/// The `decimal` format is the one registered by OpenAPI for decimal numbers
+/// of unspecified precision and range.
+fn number_schema() -> Schema {
+	json_schema!({
+		"type": "number",
+		"format": "decimal"
+	})
+}
+
+impl JsonSchema for Number {
+	#[inline(always)]
+	fn inline_schema() -> bool {
+		true
+	}
+
+	#[inline(always)]
+	fn schema_name() -> Cow<'static, str> {
+		"Number".into()
+	}
+
+	#[inline(always)]
+	fn json_schema(_: &mut SchemaGenerator) -> Schema {
+		number_schema()
+	}
+}
+
+impl<B> JsonSchema for NumberBuf<B> {
+	#[inline(always)]
+	fn inline_schema() -> bool {
+		true
+	}
+
+	#[inline(always)]
+	fn schema_name() -> Cow<'static, str> {
+		Number::schema_name()
+	}
+
+	#[inline(always)]
+	fn json_schema(_: &mut SchemaGenerator) -> Schema {
+		number_schema()
+	}
+}
+
+impl<B> JsonSchema for CowNumber<'_, B> {
+	#[inline(always)]
+	fn inline_schema() -> bool {
+		true
+	}
+
+	#[inline(always)]
+	fn schema_name() -> Cow<'static, str> {
+		Number::schema_name()
+	}
+
+	#[inline(always)]
+	fn json_schema(_: &mut SchemaGenerator) -> Schema {
+		number_schema()
+	}
+}
+
+impl JsonSchema for IntNumber {
+	#[inline(always)]
+	fn inline_schema() -> bool {
+		true
+	}
+
+	#[inline(always)]
+	fn schema_name() -> Cow<'static, str> {
+		"IntNumber".into()
+	}
+
+	#[inline(always)]
+	fn json_schema(_: &mut SchemaGenerator) -> Schema {
+		json_schema!({
+			"type": "integer"
+		})
+	}
+}
+
+impl<B> JsonSchema for IntNumberBuf<B> {
+	#[inline(always)]
+	fn inline_schema() -> bool {
+		true
+	}
+
+	#[inline(always)]
+	fn schema_name() -> Cow<'static, str> {
+		IntNumber::schema_name()
+	}
+
+	#[inline(always)]
+	fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+		IntNumber::json_schema(generator)
+	}
+}
+
+impl JsonSchema for UNumber {
+	#[inline(always)]
+	fn inline_schema() -> bool {
+		true
+	}
+
+	#[inline(always)]
+	fn schema_name() -> Cow<'static, str> {
+		"UNumber".into()
+	}
+
+	#[inline(always)]
+	fn json_schema(_: &mut SchemaGenerator) -> Schema {
+		json_schema!({
+			"type": "number",
+			"format": "decimal",
+			"minimum": 0
+		})
+	}
+}
+
+impl<B> JsonSchema for UNumberBuf<B> {
+	#[inline(always)]
+	fn inline_schema() -> bool {
+		true
+	}
+
+	#[inline(always)]
+	fn schema_name() -> Cow<'static, str> {
+		UNumber::schema_name()
+	}
+
+	#[inline(always)]
+	fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+		UNumber::json_schema(generator)
+	}
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+	use super::NUMBER_PATTERN;
+	use crate::serde::{Lossless, NumberKey, Preserve};
+	use alloc::borrow::Cow;
+	use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+	impl<N: JsonSchema> JsonSchema for Preserve<N> {
+		#[inline(always)]
+		fn inline_schema() -> bool {
+			N::inline_schema()
+		}
+
+		#[inline(always)]
+		fn schema_name() -> Cow<'static, str> {
+			N::schema_name()
+		}
+
+		#[inline(always)]
+		fn schema_id() -> Cow<'static, str> {
+			N::schema_id()
+		}
+
+		#[inline(always)]
+		fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+			N::json_schema(generator)
+		}
+	}
+
+	impl<T: JsonSchema> JsonSchema for Lossless<T> {
+		#[inline(always)]
+		fn inline_schema() -> bool {
+			T::inline_schema()
+		}
+
+		#[inline(always)]
+		fn schema_name() -> Cow<'static, str> {
+			T::schema_name()
+		}
+
+		#[inline(always)]
+		fn schema_id() -> Cow<'static, str> {
+			T::schema_id()
+		}
+
+		#[inline(always)]
+		fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+			T::json_schema(generator)
+		}
+	}
+
+	#[cfg(feature = "rmp")]
+	impl<N: JsonSchema> JsonSchema for crate::rmp::Ext<N> {
+		#[inline(always)]
+		fn inline_schema() -> bool {
+			N::inline_schema()
+		}
+
+		#[inline(always)]
+		fn schema_name() -> Cow<'static, str> {
+			N::schema_name()
+		}
+
+		#[inline(always)]
+		fn schema_id() -> Cow<'static, str> {
+			N::schema_id()
+		}
+
+		#[inline(always)]
+		fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+			N::json_schema(generator)
+		}
+	}
+
+	impl<B> JsonSchema for NumberKey<B> {
+		#[inline(always)]
+		fn inline_schema() -> bool {
+			true
+		}
+
+		#[inline(always)]
+		fn schema_name() -> Cow<'static, str> {
+			"NumberKey".into()
+		}
+
+		fn json_schema(_: &mut SchemaGenerator) -> Schema {
+			json_schema!({
+				"type": "string",
+				"pattern": NUMBER_PATTERN
+			})
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::NumberBuf;
+
+	#[test]
+	fn number_schema() {
+		let schema = schemars::schema_for!(NumberBuf);
+		assert_eq!(schema.get("type").unwrap(), "number");
+		assert_eq!(schema.get("format").unwrap(), "decimal");
+	}
+
+	#[test]
+	fn int_number_schema() {
+		let schema = schemars::schema_for!(crate::IntNumberBuf);
+		assert_eq!(schema.get("type").unwrap(), "integer");
+	}
+
+	#[test]
+	fn unsigned_number_schema() {
+		let schema = schemars::schema_for!(crate::UNumberBuf);
+		assert_eq!(schema.get("type").unwrap(), "number");
+		assert_eq!(schema.get("minimum").unwrap(), 0);
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn wrappers() {
+		use crate::serde::{Lossless, NumberKey, Preserve};
+
+		let schema = schemars::schema_for!(Preserve<NumberBuf>);
+		assert_eq!(schema.get("type").unwrap(), "number");
+
+		let schema = schemars::schema_for!(Lossless<f64>);
+		assert_eq!(schema.get("format").unwrap(), "double");
+
+		let schema = schemars::schema_for!(NumberKey);
+		assert_eq!(schema.get("type").unwrap(), "string");
+
+		let pattern = schema.get("pattern").unwrap().as_str().unwrap();
+		assert!(pattern.starts_with("^-?"))
+	}
+}
@@ -1,8 +1,15 @@
-use crate::{Buffer, InvalidNumber, Number, NumberBuf};
+use crate::{decimal::Decimal, Buffer, BufferMut, CowNumber, InvalidNumber, Number, NumberBuf};
+use alloc::{
+	borrow::ToOwned,
+	boxed::Box,
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use core::{borrow::Borrow, fmt, marker::PhantomData, ops::Deref};
 use de::{Deserialize, Deserializer};
 use ser::{Serialize, Serializer};
 use serde::{de, forward_to_deserialize_any, ser};
-use std::{fmt, marker::PhantomData};
 
 /// Structure name used to serialize number with arbitrary precision.
 ///
@@ -16,21 +23,36 @@ impl Serialize for Number {
 	where
 		S: Serializer,
 	{
-		if self.has_decimal_point() {
-			use serde::ser::SerializeStruct;
-			let mut s = serializer.serialize_struct(TOKEN, 1)?;
-			s.serialize_field(TOKEN, self.as_str())?;
-			s.end()
+		if !serializer.is_human_readable() {
+			serializer.serialize_str(self.as_str())
+		} else if self.has_decimal_point() {
+			serialize_arbitrary_precision(self, serializer)
 		} else if let Some(v) = self.as_i64() {
 			serializer.serialize_i64(v)
 		} else if let Some(v) = self.as_u64() {
 			serializer.serialize_u64(v)
+		} else if let Some(v) = self.as_i128() {
+			serializer.serialize_i128(v)
+		} else if let Some(v) = self.as_u128() {
+			serializer.serialize_u128(v)
 		} else {
-			Err(<S::Error as ser::Error>::custom("number too large"))
+			serialize_arbitrary_precision(self, serializer)
 		}
 	}
 }
 
+/// Serializes the given number as the arbitrary precision structure used by
+/// `serde_json`.
+fn serialize_arbitrary_precision<S>(n: &Number, serializer: S) -> Result<S::Ok, S::Error>
+where
+	S: Serializer,
+{
+	use serde::ser::SerializeStruct;
+	let mut s = serializer.serialize_struct(TOKEN, 1)?;
+	s.serialize_field(TOKEN, n.as_str())?;
+	s.end()
+}
+
 impl<B: Buffer> Serialize for NumberBuf<B> {
 	#[inline]
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -46,7 +68,241 @@ impl<'de, B: Buffer> Deserialize<'de> for NumberBuf<B> {
 	where
 		D: Deserializer<'de>,
 	{
-		deserializer.deserialize_any(Visitor(PhantomData))
+		if deserializer.is_human_readable() {
+			deserializer.deserialize_any(Visitor(PhantomData))
+		} else {
+			deserializer.deserialize_str(LexicalVisitor(PhantomData))
+		}
+	}
+}
+
+/// Deserializes a boxed number.
+///
+/// Thanks to the `rc` feature of `serde`, this also allows `Arc<Number>` and
+/// `Rc<Number>` to be deserialized.
+impl<'de> Deserialize<'de> for Box<Number> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		NumberBuf::deserialize(deserializer).map(NumberBuf::into_boxed_number)
+	}
+}
+
+/// Number wrapper preserving its lexical representation when serialized.
+///
+/// By default, numbers representable as 64-bit or 128-bit integers are
+/// serialized as such. This wrapper always serializes the number using the
+/// arbitrary precision structure of `serde_json`, preserving its exact
+/// digits (`-0` stays `-0` instead of becoming `0`).
+///
+/// Non human-readable formats are unaffected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Preserve<N>(pub N);
+
+impl<N> Preserve<N> {
+	/// Returns the wrapped number.
+	#[inline(always)]
+	pub fn into_inner(self) -> N {
+		self.0
+	}
+}
+
+impl<N> Deref for Preserve<N> {
+	type Target = N;
+
+	#[inline(always)]
+	fn deref(&self) -> &N {
+		&self.0
+	}
+}
+
+impl<N: Borrow<Number>> Serialize for Preserve<N> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let n = self.0.borrow();
+		if serializer.is_human_readable() {
+			serialize_arbitrary_precision(n, serializer)
+		} else {
+			serializer.serialize_str(n.as_str())
+		}
+	}
+}
+
+impl<'de, N: Deserialize<'de>> Deserialize<'de> for Preserve<N> {
+	#[inline(always)]
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		N::deserialize(deserializer).map(Self)
+	}
+}
+
+/// Number wrapper to be used as map key.
+///
+/// Map keys are strings in JSON objects, so numbers cannot be used as keys
+/// directly. This wrapper serializes the number as a string, and parses
+/// it back from a string when deserialized.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NumberKey<B = Vec<u8>>(pub NumberBuf<B>);
+
+impl<B> NumberKey<B> {
+	/// Returns the wrapped number.
+	#[inline(always)]
+	pub fn into_inner(self) -> NumberBuf<B> {
+		self.0
+	}
+}
+
+impl<B> Deref for NumberKey<B> {
+	type Target = NumberBuf<B>;
+
+	#[inline(always)]
+	fn deref(&self) -> &NumberBuf<B> {
+		&self.0
+	}
+}
+
+impl<B: Buffer> fmt::Debug for NumberKey<B> {
+	#[inline(always)]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<B> From<NumberBuf<B>> for NumberKey<B> {
+	#[inline(always)]
+	fn from(n: NumberBuf<B>) -> Self {
+		Self(n)
+	}
+}
+
+impl<B: Buffer> Serialize for NumberKey<B> {
+	#[inline(always)]
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(self.0.as_str())
+	}
+}
+
+impl<'de, B: Buffer> Deserialize<'de> for NumberKey<B> {
+	#[inline(always)]
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserializer
+			.deserialize_str(LexicalVisitor(PhantomData))
+			.map(Self)
+	}
+}
+
+/// Float wrapper whose deserialization fails on precision loss.
+///
+/// Deserializing a `Lossless<f64>` (or `Lossless<f32>`) fails if the input
+/// number does not have the same value as its nearest float, instead of
+/// silently rounding it. For instance `0.1` is accepted, since it is the
+/// shortest representation of the nearest `f64`, but
+/// `0.10000000000000000001` is rejected.
+///
+/// Precision loss can only be detected if the deserializer preserves the
+/// digits of the input number, such as `serde_json` with the
+/// `arbitrary_precision` feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Lossless<T>(pub T);
+
+impl<T> Lossless<T> {
+	/// Returns the wrapped value.
+	#[inline(always)]
+	pub fn into_inner(self) -> T {
+		self.0
+	}
+}
+
+impl<T> Deref for Lossless<T> {
+	type Target = T;
+
+	#[inline(always)]
+	fn deref(&self) -> &T {
+		&self.0
+	}
+}
+
+macro_rules! impl_lossless {
+	($($ty:ident: $as_lossy:ident),*) => {
+		$(
+			impl Serialize for Lossless<$ty> {
+				#[inline(always)]
+				fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+				where
+					S: Serializer,
+				{
+					self.0.serialize(serializer)
+				}
+			}
+
+			impl<'de> Deserialize<'de> for Lossless<$ty> {
+				fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+				where
+					D: Deserializer<'de>,
+				{
+					let n: NumberBuf = NumberBuf::deserialize(deserializer)?;
+					let f = n.$as_lossy();
+					let nearest = NumberBuf::<Vec<u8>>::try_from(f).ok();
+					match nearest {
+						Some(nearest) if n.value_eq(&nearest) => Ok(Self(f)),
+						_ => Err(de::Error::custom(format_args!(
+							"number `{n}` is not exactly representable as `{}`",
+							stringify!($ty)
+						))),
+					}
+				}
+			}
+		)*
+	};
+}
+
+impl_lossless!(f32: as_f32_lossy, f64: as_f64_lossy);
+
+/// Lexical representation visitor.
+///
+/// Used with non human-readable formats, where numbers are serialized using
+/// their lexical representation.
+struct LexicalVisitor<B>(PhantomData<B>);
+
+impl<B: Buffer> de::Visitor<'_> for LexicalVisitor<B> {
+	type Value = NumberBuf<B>;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("lexical representation of a JSON number")
+	}
+
+	fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+		self.visit_bytes(v.as_bytes())
+	}
+
+	fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+		self.visit_byte_buf(v.into_bytes())
+	}
+
+	fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+		match Number::new(v) {
+			Ok(n) => Ok(unsafe { NumberBuf::new_unchecked(B::from_bytes(n.as_bytes())) }),
+			Err(_) => Err(E::invalid_value(de::Unexpected::Bytes(v), &self)),
+		}
+	}
+
+	fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+		if Number::new(&v).is_ok() {
+			Ok(unsafe { NumberBuf::new_unchecked(B::from_vec(v)) })
+		} else {
+			Err(E::invalid_value(de::Unexpected::Bytes(&v), &self))
+		}
 	}
 }
 
@@ -70,6 +326,16 @@ impl<'de, B: Buffer> de::Visitor<'de> for Visitor<B> {
 		Ok(value.into())
 	}
 
+	#[inline]
+	fn visit_u128<E: de::Error>(self, value: u128) -> Result<NumberBuf<B>, E> {
+		Ok(value.into())
+	}
+
+	#[inline]
+	fn visit_i128<E: de::Error>(self, value: i128) -> Result<NumberBuf<B>, E> {
+		Ok(value.into())
+	}
+
 	#[inline]
 	fn visit_f64<E: de::Error>(self, value: f64) -> Result<NumberBuf<B>, E> {
 		NumberBuf::try_from(value)
@@ -80,38 +346,6 @@ impl<'de, B: Buffer> de::Visitor<'de> for Visitor<B> {
 	where
 		A: de::MapAccess<'de>,
 	{
-		struct Key;
-
-		impl<'de> Deserialize<'de> for Key {
-			fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-			where
-				D: Deserializer<'de>,
-			{
-				struct KeyVisitor;
-
-				impl<'de> de::Visitor<'de> for KeyVisitor {
-					type Value = Key;
-
-					fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-						formatter.write_str("a valid number field")
-					}
-
-					fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-					where
-						E: de::Error,
-					{
-						if v == TOKEN {
-							Ok(Key)
-						} else {
-							Err(serde::de::Error::custom("expected field with custom name"))
-						}
-					}
-				}
-
-				deserializer.deserialize_identifier(KeyVisitor)
-			}
-		}
-
 		struct Value<B>(NumberBuf<B>);
 
 		impl<'de, B: Buffer> Deserialize<'de> for Value<B> {
@@ -162,6 +396,296 @@ impl<'de, B: Buffer> de::Visitor<'de> for Visitor<B> {
 	}
 }
 
+/// Number seed, deserializing a number into an existing buffer.
+///
+/// The buffer is only overwritten once a valid number has been deserialized,
+/// reusing its allocated capacity. This is useful to amortize allocations
+/// when deserializing many numbers in a loop.
+///
+/// ```
+/// # use json_number::{NumberBuf, serde::NumberBufSeed};
+/// use serde::de::DeserializeSeed;
+///
+/// let mut n: NumberBuf = "0".parse().unwrap();
+/// for input in ["1.10", "-12", "0.5"] {
+///     let mut de = serde_json::Deserializer::from_str(input);
+///     NumberBufSeed::new(&mut n).deserialize(&mut de).unwrap();
+///     assert_eq!(n.as_str(), input)
+/// }
+/// ```
+pub struct NumberBufSeed<'a, B>(pub &'a mut NumberBuf<B>);
+
+impl<'a, B> NumberBufSeed<'a, B> {
+	/// Creates a new seed deserializing into the given number buffer.
+	#[inline(always)]
+	pub fn new(buffer: &'a mut NumberBuf<B>) -> Self {
+		Self(buffer)
+	}
+
+	/// Replaces the number with the given integer.
+	fn set_integer(self, negative: bool, mut value: u128)
+	where
+		B: BufferMut,
+	{
+		let mut bytes = [0u8; 40];
+		let mut i = bytes.len();
+
+		loop {
+			i -= 1;
+			bytes[i] = b'0' + (value % 10) as u8;
+			value /= 10;
+			if value == 0 {
+				break;
+			}
+		}
+
+		if negative {
+			i -= 1;
+			bytes[i] = b'-'
+		}
+
+		self.0.set_bytes(&bytes[i..])
+	}
+}
+
+impl<'de, B: BufferMut> de::DeserializeSeed<'de> for NumberBufSeed<'_, B> {
+	type Value = ();
+
+	fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		if deserializer.is_human_readable() {
+			deserializer.deserialize_any(self)
+		} else {
+			deserializer.deserialize_str(self)
+		}
+	}
+}
+
+impl<'de, B: BufferMut> de::Visitor<'de> for NumberBufSeed<'_, B> {
+	type Value = ();
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("JSON number")
+	}
+
+	fn visit_u64<E: de::Error>(self, v: u64) -> Result<(), E> {
+		self.set_integer(false, v as u128);
+		Ok(())
+	}
+
+	fn visit_i64<E: de::Error>(self, v: i64) -> Result<(), E> {
+		self.set_integer(v < 0, v.unsigned_abs() as u128);
+		Ok(())
+	}
+
+	fn visit_u128<E: de::Error>(self, v: u128) -> Result<(), E> {
+		self.set_integer(false, v);
+		Ok(())
+	}
+
+	fn visit_i128<E: de::Error>(self, v: i128) -> Result<(), E> {
+		self.set_integer(v < 0, v.unsigned_abs());
+		Ok(())
+	}
+
+	fn visit_f64<E: de::Error>(self, v: f64) -> Result<(), E> {
+		let n: NumberBuf = NumberBuf::try_from(v)
+			.map_err(|_| E::invalid_value(de::Unexpected::Float(v), &self))?;
+		self.0.set_bytes(n.as_bytes());
+		Ok(())
+	}
+
+	fn visit_str<E: de::Error>(self, v: &str) -> Result<(), E> {
+		self.visit_bytes(v.as_bytes())
+	}
+
+	fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<(), E> {
+		match Number::new(v) {
+			Ok(n) => {
+				self.0.set_bytes(n.as_bytes());
+				Ok(())
+			}
+			Err(_) => Err(E::invalid_value(de::Unexpected::Bytes(v), &self)),
+		}
+	}
+
+	fn visit_map<A>(self, mut map: A) -> Result<(), A::Error>
+	where
+		A: de::MapAccess<'de>,
+	{
+		match map.next_key()? {
+			Some(Key) => map.next_value_seed(self),
+			None => Err(de::Error::invalid_type(de::Unexpected::Map, &self)),
+		}
+	}
+}
+
+/// Arbitrary precision number structure field.
+struct Key;
+
+impl<'de> Deserialize<'de> for Key {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct KeyVisitor;
+
+		impl<'de> de::Visitor<'de> for KeyVisitor {
+			type Value = Key;
+
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				formatter.write_str("a valid number field")
+			}
+
+			fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+			where
+				E: de::Error,
+			{
+				if v == TOKEN {
+					Ok(Key)
+				} else {
+					Err(serde::de::Error::custom("expected field with custom name"))
+				}
+			}
+		}
+
+		deserializer.deserialize_identifier(KeyVisitor)
+	}
+}
+
+/// Borrowed number visitor.
+struct BorrowedVisitor;
+
+impl<'de> de::Visitor<'de> for BorrowedVisitor {
+	type Value = &'de Number;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("borrowed string containing a JSON number")
+	}
+
+	fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+		self.visit_borrowed_bytes(v.as_bytes())
+	}
+
+	fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+		Number::new(v).map_err(|_| E::invalid_value(de::Unexpected::Bytes(v), &self))
+	}
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for &'a Number {
+	/// Deserializes a number borrowed from the input.
+	///
+	/// Only deserializers able to lend a string (or bytes) holding the
+	/// lexical representation of the number are supported.
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserializer.deserialize_str(BorrowedVisitor)
+	}
+}
+
+/// Clone-on-write number visitor.
+///
+/// Borrows the number whenever the deserializer lends its lexical
+/// representation, and falls back to [`Visitor`] otherwise.
+struct CowVisitor<B>(PhantomData<B>);
+
+impl<'de, B: Buffer> de::Visitor<'de> for CowVisitor<B> {
+	type Value = CowNumber<'de, B>;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("JSON number")
+	}
+
+	fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+		Visitor(PhantomData).visit_u64(v).map(CowNumber::Owned)
+	}
+
+	fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+		Visitor(PhantomData).visit_i64(v).map(CowNumber::Owned)
+	}
+
+	fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+		Visitor(PhantomData).visit_u128(v).map(CowNumber::Owned)
+	}
+
+	fn visit_i128<E: de::Error>(self, v: i128) -> Result<Self::Value, E> {
+		Visitor(PhantomData).visit_i128(v).map(CowNumber::Owned)
+	}
+
+	fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+		Visitor(PhantomData).visit_f64(v).map(CowNumber::Owned)
+	}
+
+	fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+		BorrowedVisitor
+			.visit_borrowed_str(v)
+			.map(CowNumber::Borrowed)
+	}
+
+	fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+		BorrowedVisitor
+			.visit_borrowed_bytes(v)
+			.map(CowNumber::Borrowed)
+	}
+
+	fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+		LexicalVisitor(PhantomData)
+			.visit_str(v)
+			.map(CowNumber::Owned)
+	}
+
+	fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+		LexicalVisitor(PhantomData)
+			.visit_string(v)
+			.map(CowNumber::Owned)
+	}
+
+	fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+		LexicalVisitor(PhantomData)
+			.visit_bytes(v)
+			.map(CowNumber::Owned)
+	}
+
+	fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+		LexicalVisitor(PhantomData)
+			.visit_byte_buf(v)
+			.map(CowNumber::Owned)
+	}
+
+	fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+	where
+		A: de::MapAccess<'de>,
+	{
+		match map.next_key()? {
+			Some(Key) => map.next_value(),
+			None => Err(de::Error::invalid_type(de::Unexpected::Map, &self)),
+		}
+	}
+}
+
+impl<'de, B: Buffer> Deserialize<'de> for CowNumber<'de, B> {
+	/// Deserializes a number, borrowing it from the input whenever the
+	/// deserializer lends its lexical representation.
+	///
+	/// On top of JSON numbers, strings holding the lexical representation of
+	/// a number are accepted, which is the only way to borrow numbers from
+	/// human-readable formats.
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		if deserializer.is_human_readable() {
+			deserializer.deserialize_any(CowVisitor(PhantomData))
+		} else {
+			deserializer.deserialize_str(CowVisitor(PhantomData))
+		}
+	}
+}
+
 /// Unexpected value that is not a number.
 #[derive(Debug)]
 pub struct Unexpected(String);
@@ -172,7 +696,7 @@ impl fmt::Display for Unexpected {
 	}
 }
 
-impl std::error::Error for Unexpected {}
+impl core::error::Error for Unexpected {}
 
 impl de::Error for Unexpected {
 	fn custom<T>(msg: T) -> Self
@@ -191,62 +715,518 @@ impl de::Error for Unexpected {
 	}
 }
 
-impl<'de, B: Buffer> Deserializer<'de> for NumberBuf<B> {
-	type Error = Unexpected;
+/// Returns the value of the given number as an integer of type `T`, if it
+/// is an integer in range, whatever its notation (`1e2` and `5.0` fit).
+fn integer_value<T: TryFrom<i128> + TryFrom<u128>>(n: &Number) -> Option<T> {
+	let d = Decimal::new(n);
+	if d.negative {
+		T::try_from(d.to_i128()?).ok()
+	} else {
+		T::try_from(d.to_u128()?).ok()
+	}
+}
 
+/// Implements typed integer deserialization methods, performing range-checked
+/// conversions of the value of the number.
+macro_rules! deserialize_integers {
+	($($method:ident: $ty:ty => $visit:ident),*) => {
+		$(
+			fn $method<V>(self, visitor: V) -> Result<V::Value, E>
+			where
+				V: de::Visitor<'de>,
+			{
+				let n: &Number = &self.number;
+				match n.as_str().parse::<$ty>().ok().or_else(|| integer_value(n)) {
+					Some(v) => visitor.$visit(v),
+					None => Err(integer_error(n, &visitor)),
+				}
+			}
+		)*
+	};
+}
+
+/// Number deserializer.
+///
+/// Deserializes the number `N` (such as a `&Number`, `NumberBuf` or
+/// `CowNumber`) reporting errors with the error type `E`. This is the
+/// deserializer returned by the [`IntoDeserializer`](de::IntoDeserializer)
+/// implementations.
+///
+/// Integers are visited as integers and numbers exactly representable as
+/// `f64` are visited as floats. Other numbers are visited as the arbitrary
+/// precision map used by `serde_json`.
+pub struct NumberDeserializer<N, E = Unexpected> {
+	number: N,
+	error: PhantomData<E>,
+}
+
+impl<N, E> NumberDeserializer<N, E> {
+	/// Creates a new deserializer for the given number.
 	#[inline(always)]
-	fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	pub fn new(number: N) -> Self {
+		Self {
+			number,
+			error: PhantomData,
+		}
+	}
+}
+
+impl<'de, N: Deref<Target = Number>, E: de::Error> Deserializer<'de> for NumberDeserializer<N, E> {
+	type Error = E;
+
+	fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, E>
 	where
-		V: serde::de::Visitor<'de>,
+		V: de::Visitor<'de>,
 	{
-		self.as_number().deserialize_any(visitor)
+		let n: &Number = &self.number;
+		if let Some(u) = n.as_u64() {
+			visitor.visit_u64(u)
+		} else if let Some(i) = n.as_i64() {
+			visitor.visit_i64(i)
+		} else if let Some(f) = n.as_f64_lossless() {
+			visitor.visit_f64(f)
+		} else {
+			visitor.visit_map(NumberAsMapAccess(Some(n), PhantomData))
+		}
 	}
 
-	forward_to_deserialize_any! {
-		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
-		bytes byte_buf option unit unit_struct seq tuple
-		tuple_struct map struct newtype_struct enum identifier ignored_any
+	#[inline(always)]
+	fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, E>
+	where
+		V: de::Visitor<'de>,
+	{
+		visitor.visit_f32(self.number.as_f32_lossy())
 	}
-}
 
-impl<'de, 'n, B: Buffer> Deserializer<'de> for &'n NumberBuf<B> {
-	type Error = Unexpected;
+	#[inline(always)]
+	fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, E>
+	where
+		V: de::Visitor<'de>,
+	{
+		visitor.visit_f64(self.number.as_f64_lossy())
+	}
+
+	deserialize_integers! {
+		deserialize_u8: u8 => visit_u8,
+		deserialize_u16: u16 => visit_u16,
+		deserialize_u32: u32 => visit_u32,
+		deserialize_u64: u64 => visit_u64,
+		deserialize_u128: u128 => visit_u128,
+		deserialize_i8: i8 => visit_i8,
+		deserialize_i16: i16 => visit_i16,
+		deserialize_i32: i32 => visit_i32,
+		deserialize_i64: i64 => visit_i64,
+		deserialize_i128: i128 => visit_i128
+	}
+
+	#[inline(always)]
+	fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, E>
+	where
+		V: de::Visitor<'de>,
+	{
+		visitor.visit_some(self)
+	}
 
 	#[inline(always)]
-	fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, E>
 	where
-		V: serde::de::Visitor<'de>,
+		V: de::Visitor<'de>,
 	{
-		self.as_number().deserialize_any(visitor)
+		visitor.visit_newtype_struct(self)
 	}
 
 	forward_to_deserialize_any! {
-		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
-		bytes byte_buf option unit unit_struct seq tuple
-		tuple_struct map struct newtype_struct enum identifier ignored_any
+		bool char str string bytes byte_buf unit unit_struct seq tuple
+		tuple_struct map struct enum identifier ignored_any
 	}
 }
 
-impl<'de, 'n> Deserializer<'de> for &'n Number {
-	type Error = Unexpected;
+/// Returns the error produced when the number `n` is not the expected
+/// integer.
+fn integer_error<E: de::Error>(n: &Number, expected: &dyn de::Expected) -> E {
+	if Decimal::new(n).exponent < 0 {
+		E::invalid_type(de::Unexpected::Float(n.as_f64_lossy()), expected)
+	} else {
+		E::invalid_value(de::Unexpected::Other(&format!("integer `{n}`")), expected)
+	}
+}
+
+/// Implements `Deserializer` by delegating to [`NumberDeserializer`].
+macro_rules! delegate_deserializer {
+	() => {
+		type Error = Unexpected;
+
+		delegate_deserializer! {
+			deserialize_any deserialize_f32 deserialize_f64
+			deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+			deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+			deserialize_option
+		}
+
+		#[inline(always)]
+		fn deserialize_newtype_struct<V>(
+			self,
+			name: &'static str,
+			visitor: V,
+		) -> Result<V::Value, Self::Error>
+		where
+			V: de::Visitor<'de>,
+		{
+			NumberDeserializer::<&Number, Self::Error>::new(&self)
+				.deserialize_newtype_struct(name, visitor)
+		}
+
+		forward_to_deserialize_any! {
+			bool char str string bytes byte_buf unit unit_struct seq tuple
+			tuple_struct map struct enum identifier ignored_any
+		}
+	};
+	($($method:ident)*) => {
+		$(
+			#[inline(always)]
+			fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+			where
+				V: de::Visitor<'de>,
+			{
+				NumberDeserializer::<&Number, Self::Error>::new(&self).$method(visitor)
+			}
+		)*
+	};
+}
+
+impl<'de, B: Buffer> Deserializer<'de> for NumberBuf<B> {
+	delegate_deserializer!();
+}
+
+impl<'de, B: Buffer> Deserializer<'de> for &NumberBuf<B> {
+	delegate_deserializer!();
+}
+
+impl<'de> Deserializer<'de> for &Number {
+	delegate_deserializer!();
+}
+
+impl<'de, E: de::Error> de::IntoDeserializer<'de, E> for &Number {
+	type Deserializer = NumberDeserializer<Self, E>;
 
 	#[inline(always)]
-	fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	fn into_deserializer(self) -> Self::Deserializer {
+		NumberDeserializer::new(self)
+	}
+}
+
+impl<'de, B: Buffer, E: de::Error> de::IntoDeserializer<'de, E> for NumberBuf<B> {
+	type Deserializer = NumberDeserializer<Self, E>;
+
+	#[inline(always)]
+	fn into_deserializer(self) -> Self::Deserializer {
+		NumberDeserializer::new(self)
+	}
+}
+
+impl<'de, B: Buffer, E: de::Error> de::IntoDeserializer<'de, E> for CowNumber<'_, B> {
+	type Deserializer = NumberDeserializer<Self, E>;
+
+	#[inline(always)]
+	fn into_deserializer(self) -> Self::Deserializer {
+		NumberDeserializer::new(self)
+	}
+}
+
+/// Arbitrary precision number map access.
+///
+/// Presents a number as the single entry map used by `serde_json` to
+/// represent numbers with arbitrary precision, where the key is [`TOKEN`] and
+/// the value is the lexical representation of the number.
+struct NumberAsMapAccess<'n, E>(Option<&'n Number>, PhantomData<E>);
+
+impl<'de, E: de::Error> de::MapAccess<'de> for NumberAsMapAccess<'_, E> {
+	type Error = E;
+
+	fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, E>
 	where
-		V: serde::de::Visitor<'de>,
+		K: de::DeserializeSeed<'de>,
 	{
-		if let Some(u) = self.as_u64() {
-			visitor.visit_u64(u)
-		} else if let Some(i) = self.as_i64() {
-			visitor.visit_i64(i)
+		if self.0.is_some() {
+			seed.deserialize(de::value::BorrowedStrDeserializer::new(TOKEN))
+				.map(Some)
 		} else {
-			visitor.visit_f64(self.as_f64_lossy())
+			Ok(None)
 		}
 	}
 
-	forward_to_deserialize_any! {
-		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
-		bytes byte_buf option unit unit_struct seq tuple
-		tuple_struct map struct newtype_struct enum identifier ignored_any
+	fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, E>
+	where
+		V: de::DeserializeSeed<'de>,
+	{
+		match self.0.take() {
+			Some(n) => seed.deserialize(de::value::StrDeserializer::new(n.as_str())),
+			None => Err(de::Error::custom("number already consumed")),
+		}
+	}
+
+	fn size_hint(&self) -> Option<usize> {
+		Some(self.0.is_some() as usize)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{CowNumber, Number, NumberBuf};
+	use serde::{
+		de::value::{Error, I128Deserializer, U128Deserializer},
+		Deserialize,
+	};
+
+	fn round_trip(s: &str) -> String {
+		let n = Number::new(s).unwrap();
+		NumberBuf::<Vec<u8>>::deserialize(n)
+			.unwrap()
+			.as_str()
+			.to_owned()
+	}
+
+	#[test]
+	fn deserialize_any() {
+		assert_eq!(round_trip("12"), "12");
+		assert_eq!(round_trip("-12"), "-12");
+		assert_eq!(round_trip("0.5"), "0.5");
+		assert_eq!(round_trip("1.10"), "1.10");
+		assert_eq!(round_trip("1e999"), "1e999");
+		assert_eq!(
+			round_trip("3.14159265358979323846264338327950288"),
+			"3.14159265358979323846264338327950288"
+		);
+
+		let value = serde_json::Value::deserialize(Number::new("1.10").unwrap()).unwrap();
+		assert_eq!(value.to_string(), "1.10");
+	}
+
+	#[test]
+	fn large_integers() {
+		for s in [
+			"340282366920938463463374607431768211455",
+			"-170141183460469231731687303715884105728",
+			"1000000000000000000000000000000000000000000",
+			"1e5",
+		] {
+			let n = Number::new(s).unwrap();
+			assert_eq!(serde_json::to_string(n).unwrap(), s)
+		}
+
+		let n: NumberBuf =
+			NumberBuf::deserialize(U128Deserializer::<Error>::new(u128::MAX)).unwrap();
+		assert_eq!(n.as_str(), u128::MAX.to_string());
+		let n: NumberBuf =
+			NumberBuf::deserialize(I128Deserializer::<Error>::new(i128::MIN)).unwrap();
+		assert_eq!(n.as_str(), i128::MIN.to_string());
+	}
+
+	#[test]
+	fn binary_format() {
+		for s in [
+			"0",
+			"-12",
+			"1.10",
+			"1e999",
+			"340282366920938463463374607431768211456",
+		] {
+			let n: NumberBuf = s.parse().unwrap();
+			let bytes = bincode::serialize(&n).unwrap();
+			let m: NumberBuf = bincode::deserialize(&bytes).unwrap();
+			assert_eq!(m, n)
+		}
+
+		let bytes = bincode::serialize("1.").unwrap();
+		assert!(bincode::deserialize::<NumberBuf>(&bytes).is_err())
+	}
+
+	#[test]
+	fn borrowed() {
+		let n: &Number = serde_json::from_str("\"1.10\"").unwrap();
+		assert_eq!(n.as_str(), "1.10");
+		assert!(serde_json::from_str::<&Number>("1.10").is_err());
+		assert!(serde_json::from_str::<&Number>("\"1.\"").is_err());
+
+		let c: CowNumber = serde_json::from_str("\"1.10\"").unwrap();
+		assert!(c.is_borrowed());
+		let c: CowNumber = serde_json::from_str("1.10").unwrap();
+		assert!(c.is_owned());
+		assert_eq!(c.as_str(), "1.10");
+		let c: CowNumber = serde_json::from_str("12").unwrap();
+		assert_eq!(c.as_str(), "12");
+
+		let n: NumberBuf = "-1.5e3".parse().unwrap();
+		let bytes = bincode::serialize(&n).unwrap();
+		let c: CowNumber = bincode::deserialize(&bytes).unwrap();
+		assert!(c.is_borrowed());
+		assert_eq!(c, *n.as_number());
+		let m: &Number = bincode::deserialize(&bytes).unwrap();
+		assert_eq!(m, n.as_number());
+	}
+
+	#[test]
+	fn seed() {
+		use super::NumberBufSeed;
+		use bincode::Options;
+		use serde::de::DeserializeSeed;
+
+		let mut n: NumberBuf = "0".parse().unwrap();
+		for s in [
+			"12",
+			"-12",
+			"1.10",
+			"340282366920938463463374607431768211455",
+			"-170141183460469231731687303715884105728",
+		] {
+			let mut de = serde_json::Deserializer::from_str(s);
+			NumberBufSeed::new(&mut n).deserialize(&mut de).unwrap();
+			assert_eq!(n.as_str(), s)
+		}
+
+		let mut de = serde_json::Deserializer::from_str("true");
+		assert!(NumberBufSeed::new(&mut n).deserialize(&mut de).is_err());
+		assert_eq!(n.as_str(), "-170141183460469231731687303715884105728");
+
+		let bytes = bincode::serialize("-1.5e3").unwrap();
+		let options = bincode::DefaultOptions::new().with_fixint_encoding();
+		let mut de = bincode::Deserializer::from_slice(&bytes, options);
+		NumberBufSeed::new(&mut n).deserialize(&mut de).unwrap();
+		assert_eq!(n.as_str(), "-1.5e3");
+		assert!(n.capacity() >= 40)
+	}
+
+	#[test]
+	fn into_deserializer() {
+		use serde::de::{value::Error, IntoDeserializer};
+
+		let n = Number::new("12").unwrap();
+		let i = u32::deserialize(IntoDeserializer::<Error>::into_deserializer(n)).unwrap();
+		assert_eq!(i, 12);
+
+		let n: NumberBuf = "1.10".parse().unwrap();
+		let m = NumberBuf::<Vec<u8>>::deserialize(IntoDeserializer::<Error>::into_deserializer(
+			n.clone(),
+		))
+		.unwrap();
+		assert_eq!(m, n);
+
+		let value = serde_json::Value::deserialize(IntoDeserializer::<Error>::into_deserializer(n))
+			.unwrap();
+		assert_eq!(value.to_string(), "1.10");
+
+		let c = CowNumber::<Vec<u8>>::Borrowed(Number::new("-5").unwrap());
+		let i = i64::deserialize(IntoDeserializer::<Error>::into_deserializer(c)).unwrap();
+		assert_eq!(i, -5)
+	}
+
+	#[test]
+	fn typed() {
+		let n = |s| Number::new(s).unwrap();
+		assert_eq!(u8::deserialize(n("255")).unwrap(), 255);
+		assert_eq!(
+			i128::deserialize(n("-170141183460469231731687303715884105728")).unwrap(),
+			i128::MIN
+		);
+		assert_eq!(Option::<u16>::deserialize(n("12")).unwrap(), Some(12));
+		assert_eq!(
+			u8::deserialize(n("256")).unwrap_err().to_string(),
+			"invalid value: integer `256`, expected u8"
+		);
+		assert_eq!(
+			i32::deserialize(n("1.5")).unwrap_err().to_string(),
+			"invalid type: floating point `1.5`, expected i32"
+		);
+		assert!(u64::deserialize(n("-1")).is_err());
+		assert_eq!(u32::deserialize(n("1e2")).unwrap(), 100);
+		assert_eq!(i64::deserialize(n("5.0")).unwrap(), 5);
+		assert_eq!(i8::deserialize(n("-1.28E+2")).unwrap(), -128);
+		assert_eq!(u64::deserialize(n("-0.0")).unwrap(), 0);
+		assert_eq!(
+			u8::deserialize(n("2.56e2")).unwrap_err().to_string(),
+			"invalid value: integer `2.56e2`, expected u8"
+		);
+		assert!(u128::deserialize(n("1e99999999999999999999")).is_err());
+	}
+
+	#[test]
+	fn smart_pointers() {
+		use std::{rc::Rc, sync::Arc};
+
+		let n: Box<Number> = serde_json::from_str("1.10").unwrap();
+		assert_eq!(n.as_str(), "1.10");
+		let n: Arc<Number> = serde_json::from_str("-12").unwrap();
+		assert_eq!(n.as_str(), "-12");
+		let n: Rc<Number> = serde_json::from_str("0.10").unwrap();
+		assert_eq!(serde_json::to_string(&n).unwrap(), "0.10");
+		assert!(serde_json::from_str::<Box<Number>>("true").is_err())
+	}
+
+	#[test]
+	fn lossless() {
+		use super::Lossless;
+
+		for (s, f) in [
+			("0.1", 0.1),
+			("1.10", 1.1),
+			("-12", -12.0),
+			("1.5e3", 1500.0),
+		] {
+			let l: Lossless<f64> = serde_json::from_str(s).unwrap();
+			assert_eq!(l.into_inner(), f)
+		}
+
+		for s in [
+			"0.10000000000000000001",
+			"9007199254740993",
+			"1e400",
+			"true",
+		] {
+			assert!(serde_json::from_str::<Lossless<f64>>(s).is_err())
+		}
+
+		assert!(serde_json::from_str::<Lossless<f32>>("16777216").is_ok());
+		assert!(serde_json::from_str::<Lossless<f32>>("16777217").is_err());
+		assert_eq!(serde_json::to_string(&Lossless(0.5)).unwrap(), "0.5")
+	}
+
+	#[test]
+	fn preserve() {
+		use super::Preserve;
+
+		for s in [
+			"12",
+			"-0",
+			"1.50e-2",
+			"1.10",
+			"340282366920938463463374607431768211455",
+		] {
+			let n: Preserve<NumberBuf> = serde_json::from_str(s).unwrap();
+			assert_eq!(serde_json::to_string(&n).unwrap(), s);
+			assert_eq!(serde_json::to_string(&Preserve(n.as_number())).unwrap(), s)
+		}
+	}
+
+	#[test]
+	fn map_key() {
+		use super::NumberKey;
+		use std::collections::BTreeMap;
+
+		let mut map = BTreeMap::new();
+		map.insert(NumberKey("1.5".parse().unwrap()), 1);
+		map.insert(NumberKey("-12".parse().unwrap()), 2);
+		let json = serde_json::to_string(&map).unwrap();
+		assert_eq!(json, r#"{"-12":2,"1.5":1}"#);
+
+		let parsed: BTreeMap<NumberKey, u32> = serde_json::from_str(&json).unwrap();
+		assert_eq!(parsed, map);
+		assert!(serde_json::from_str::<BTreeMap<NumberKey, u32>>(r#"{"a":1}"#).is_err())
+	}
+
+	#[test]
+	fn deserialize_float() {
+		let n = Number::new("1.10").unwrap();
+		assert_eq!(f64::deserialize(n).unwrap(), 1.1);
+		assert_eq!(f32::deserialize(n).unwrap(), 1.1);
 	}
 }
@@ -1,39 +1,277 @@
-use crate::{Buffer, Number, NumberBuf};
+use crate::{Buffer, InvalidNumber, Number, NumberBuf};
+use alloc::{borrow::ToOwned, boxed::Box, string::ToString, vec::Vec};
+use core::fmt;
+use serde_json::value::RawValue;
+
+/// `serde_json::Number` conversion error.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConversionError {
+	/// The `serde_json::Number` is not a valid JSON number.
+	///
+	/// This can only happen with numbers built using
+	/// `serde_json::Number::from_string_unchecked`.
+	Invalid,
+
+	/// The number cannot be represented exactly by a `serde_json::Number`.
+	Inexact,
+
+	/// The number is out of the range of `serde_json::Number`.
+	///
+	/// This can only happen when the `arbitrary_precision` feature of
+	/// `serde_json` is disabled.
+	OutOfRange,
+
+	/// The `serde_json::Value` is not a number.
+	///
+	/// Holds the kind of value found instead (`null`, `boolean`, `string`,
+	/// `array` or `object`).
+	NotANumber(&'static str),
+}
+
+impl fmt::Display for ConversionError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Invalid => f.write_str("invalid `serde_json::Number`"),
+			Self::Inexact => {
+				f.write_str("number is not exactly representable as a `serde_json::Number`")
+			}
+			Self::OutOfRange => f.write_str("number is out of the `serde_json::Number` range"),
+			Self::NotANumber(kind) => write!(f, "expected a number, found {kind}"),
+		}
+	}
+}
+
+impl core::error::Error for ConversionError {}
+
+/// `fmt::Write` adapter writing into a byte vector.
+struct BytesWriter(Vec<u8>);
+
+impl fmt::Write for BytesWriter {
+	#[inline(always)]
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.0.extend_from_slice(s.as_bytes());
+		Ok(())
+	}
+}
+
+impl<B: Buffer> NumberBuf<B> {
+	/// Converts the given `serde_json::Number`, failing if it is not a valid
+	/// JSON number.
+	///
+	/// The number is directly formatted into the buffer, without
+	/// intermediate string.
+	pub fn try_from_serde_json(n: &serde_json::Number) -> Result<Self, ConversionError> {
+		use fmt::Write;
+		let mut writer = BytesWriter(Vec::with_capacity(24));
+		write!(writer, "{n}").map_err(|_| ConversionError::Invalid)?;
+		let bytes = writer.0;
+
+		if Number::new(&bytes).is_ok() {
+			Ok(unsafe { NumberBuf::new_unchecked(B::from_vec(bytes)) })
+		} else {
+			Err(ConversionError::Invalid)
+		}
+	}
+}
+
+impl<B: Buffer> From<&serde_json::Number> for NumberBuf<B> {
+	/// Converts the given `serde_json::Number`.
+	///
+	/// # Panics
+	///
+	/// Panics if the number is not a valid JSON number, which can only
+	/// happen with numbers built using
+	/// `serde_json::Number::from_string_unchecked`. Use
+	/// [`NumberBuf::try_from_serde_json`] to handle this case.
+	#[inline(always)]
+	fn from(n: &serde_json::Number) -> Self {
+		Self::try_from_serde_json(n).expect("invalid `serde_json::Number`")
+	}
+}
 
 impl<B: Buffer> From<serde_json::Number> for NumberBuf<B> {
+	/// Converts the given `serde_json::Number`.
+	///
+	/// # Panics
+	///
+	/// Panics if the number is not a valid JSON number. See
+	/// [`NumberBuf::try_from_serde_json`].
 	#[inline(always)]
 	fn from(n: serde_json::Number) -> Self {
-		NumberBuf::new(B::from_vec(n.to_string().into_bytes()))
-			.ok()
-			.expect("invalid `serde_json::Number`")
+		Self::from(&n)
 	}
 }
 
-impl<B: Buffer> From<NumberBuf<B>> for serde_json::Number {
+impl<B: Buffer> TryFrom<&serde_json::Value> for NumberBuf<B> {
+	type Error = ConversionError;
+
+	/// Converts the given JSON value, failing if it is not a number.
+	fn try_from(value: &serde_json::Value) -> Result<Self, ConversionError> {
+		use serde_json::Value;
+		match value {
+			Value::Number(n) => Self::try_from_serde_json(n),
+			Value::Null => Err(ConversionError::NotANumber("null")),
+			Value::Bool(_) => Err(ConversionError::NotANumber("boolean")),
+			Value::String(_) => Err(ConversionError::NotANumber("string")),
+			Value::Array(_) => Err(ConversionError::NotANumber("array")),
+			Value::Object(_) => Err(ConversionError::NotANumber("object")),
+		}
+	}
+}
+
+impl<B: Buffer> TryFrom<serde_json::Value> for NumberBuf<B> {
+	type Error = ConversionError;
+
+	/// Converts the given JSON value, failing if it is not a number.
 	#[inline(always)]
-	fn from(n: NumberBuf<B>) -> Self {
-		Self::from(n.as_number())
+	fn try_from(value: serde_json::Value) -> Result<Self, ConversionError> {
+		Self::try_from(&value)
+	}
+}
+
+impl Number {
+	/// Converts this number into a `serde_json::Number` only if the
+	/// operation does not induce imprecisions/approximations.
+	///
+	/// When the `arbitrary_precision` feature of `serde_json` is enabled,
+	/// the exact digits of the number are preserved and this never fails.
+	/// Otherwise this fails if the number cannot be represented exactly by
+	/// an `u64`, `i64` or `f64`.
+	pub fn try_into_serde_json_exact(&self) -> Result<serde_json::Number, ConversionError> {
+		let result: serde_json::Number = self
+			.as_str()
+			.parse()
+			.map_err(|_| ConversionError::OutOfRange)?;
+		let result_str = result.to_string();
+		match Number::new(result_str.as_str()) {
+			Ok(m) if m.value_eq(self) => Ok(result),
+			_ => Err(ConversionError::Inexact),
+		}
+	}
+}
+
+impl<B: Buffer> TryFrom<NumberBuf<B>> for serde_json::Number {
+	type Error = ConversionError;
+
+	#[inline(always)]
+	fn try_from(n: NumberBuf<B>) -> Result<Self, ConversionError> {
+		Self::try_from(n.as_number())
+	}
+}
+
+impl<'n> TryFrom<&'n Number> for serde_json::Number {
+	type Error = ConversionError;
+
+	/// Converts the number, preserving its exact digits whenever possible
+	/// (see [`Number::try_into_serde_json_exact`]) and approximating it
+	/// with the nearest `f64` otherwise.
+	///
+	/// Fails if the number is out of the `f64` range and the
+	/// `arbitrary_precision` feature of `serde_json` is disabled.
+	fn try_from(n: &'n Number) -> Result<Self, ConversionError> {
+		match n.try_into_serde_json_exact() {
+			Ok(result) => Ok(result),
+			Err(_) => Self::from_f64(n.as_f64_lossy()).ok_or(ConversionError::OutOfRange),
+		}
+	}
+}
+
+/// `fmt::Write` adapter writing into a fixed size stack buffer.
+#[cfg(not(feature = "arbitrary-precision"))]
+struct StackWriter {
+	bytes: [u8; 32],
+	len: usize,
+}
+
+#[cfg(not(feature = "arbitrary-precision"))]
+impl fmt::Write for StackWriter {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		let end = self.len + s.len();
+		self.bytes
+			.get_mut(self.len..end)
+			.ok_or(fmt::Error)?
+			.copy_from_slice(s.as_bytes());
+		self.len = end;
+		Ok(())
 	}
 }
 
-impl<'n> From<&'n Number> for serde_json::Number {
-	fn from(n: &'n Number) -> Self {
-		if let Some(u) = n.as_u64() {
-			u.into()
-		} else if let Some(i) = n.as_i64() {
-			i.into()
+impl PartialEq<serde_json::Number> for Number {
+	/// Compares the values of the numbers, regardless of their lexical
+	/// representations.
+	#[cfg(feature = "arbitrary-precision")]
+	fn eq(&self, other: &serde_json::Number) -> bool {
+		Number::new(other.as_str()).is_ok_and(|other| self.value_eq(other))
+	}
+
+	/// Compares the values of the numbers, regardless of their lexical
+	/// representations.
+	///
+	/// Integers and floats are formatted on the stack. Longer numbers, only
+	/// found when the `arbitrary_precision` feature of `serde_json` is
+	/// enabled by another crate, are formatted on the heap.
+	#[cfg(not(feature = "arbitrary-precision"))]
+	fn eq(&self, other: &serde_json::Number) -> bool {
+		use fmt::Write;
+		let mut writer = StackWriter {
+			bytes: [0; 32],
+			len: 0,
+		};
+
+		if write!(writer, "{other}").is_ok() {
+			Number::new(&writer.bytes[..writer.len]).is_ok_and(|other| self.value_eq(other))
 		} else {
-			match n.as_str().parse() {
-				Ok(n) => n,
-				Err(_) => Self::from_f64(n.as_f64_lossy()).unwrap(),
-			}
+			let other = other.to_string();
+			Number::new(other.as_str()).is_ok_and(|other| self.value_eq(other))
 		}
 	}
 }
 
+impl<B: Buffer> PartialEq<serde_json::Number> for NumberBuf<B> {
+	#[inline(always)]
+	fn eq(&self, other: &serde_json::Number) -> bool {
+		self.as_number() == other
+	}
+}
+
+impl PartialEq<Number> for serde_json::Number {
+	#[inline(always)]
+	fn eq(&self, other: &Number) -> bool {
+		other == self
+	}
+}
+
+impl<B: Buffer> PartialEq<NumberBuf<B>> for serde_json::Number {
+	#[inline(always)]
+	fn eq(&self, other: &NumberBuf<B>) -> bool {
+		other.as_number() == self
+	}
+}
+
+impl Number {
+	/// Converts this number into a raw JSON value.
+	pub fn to_raw_value(&self) -> Box<RawValue> {
+		// A JSON number is always a valid JSON value.
+		RawValue::from_string(self.as_str().to_owned()).unwrap()
+	}
+}
+
+impl<'a> TryFrom<&'a RawValue> for &'a Number {
+	type Error = InvalidNumber<&'a str>;
+
+	/// Borrows the number held by the given raw JSON value, ignoring
+	/// surrounding whitespaces.
+	fn try_from(value: &'a RawValue) -> Result<Self, Self::Error> {
+		let json = value.get();
+		let trimmed = json.trim_matches([' ', '\t', '\n', '\r']);
+		Number::new(trimmed).map_err(|_| InvalidNumber(json))
+	}
+}
+
 #[cfg(test)]
 mod tests {
-	use crate::NumberBuf;
+	use super::ConversionError;
+	use crate::{Number, NumberBuf};
 
 	#[test]
 	fn serde_json_arbitrary_precision_compatibility() {
@@ -46,4 +284,87 @@ mod tests {
 		let m: NumberBuf = serde_json_n.into();
 		assert_eq!(n, m)
 	}
+
+	#[test]
+	fn invalid_serde_json_number() {
+		let n = serde_json::Number::from_string_unchecked("1.".to_owned());
+		assert_eq!(
+			NumberBuf::<Vec<u8>>::try_from_serde_json(&n),
+			Err(ConversionError::Invalid)
+		)
+	}
+
+	#[test]
+	fn raw_value() {
+		let raw: Box<serde_json::value::RawValue> = serde_json::from_str(" 1.10 ").unwrap();
+		let n: &Number = raw.as_ref().try_into().unwrap();
+		assert_eq!(n.as_str(), "1.10");
+		assert_eq!(n.to_raw_value().get(), "1.10");
+
+		let raw: Box<serde_json::value::RawValue> = serde_json::from_str("\"1\"").unwrap();
+		assert!(<&Number>::try_from(raw.as_ref()).is_err())
+	}
+
+	#[test]
+	fn from_value() {
+		let value: serde_json::Value = serde_json::from_str("[1.10, true, null]").unwrap();
+		let n = NumberBuf::<Vec<u8>>::try_from(&value[0]).unwrap();
+		assert_eq!(n.as_str(), "1.10");
+
+		let e = NumberBuf::<Vec<u8>>::try_from(value[1].clone()).unwrap_err();
+		assert_eq!(e, ConversionError::NotANumber("boolean"));
+		assert_eq!(e.to_string(), "expected a number, found boolean");
+		assert!(NumberBuf::<Vec<u8>>::try_from(&value[2]).is_err())
+	}
+
+	#[test]
+	fn equality() {
+		let a = Number::new("1.10").unwrap();
+		let b: serde_json::Number = "11e-1".parse().unwrap();
+		assert_eq!(*a, b);
+		assert_eq!(b, *a);
+
+		let a: NumberBuf = "-0".parse().unwrap();
+		let b = serde_json::Number::from(0);
+		assert_eq!(a, b);
+		assert_eq!(b, a);
+
+		let b = serde_json::Number::from(1);
+		assert_ne!(a, b);
+		assert_ne!(b, a);
+
+		let a = Number::new("1").unwrap();
+		let b: serde_json::Number = "1e99999999999999999999".parse().unwrap();
+		assert_ne!(*a, b);
+		assert_ne!(b, *a);
+
+		let long = "1.000000000000000000000000000000000000001";
+		let b: serde_json::Number = long.parse().unwrap();
+		assert_eq!(*Number::new(long).unwrap(), b);
+		assert_ne!(*a, b)
+	}
+
+	#[test]
+	fn from_borrowed() {
+		for s in ["0", "-12", "1.10", "1e-999", "18446744073709551616"] {
+			let n: serde_json::Number = s.parse().unwrap();
+			let m: NumberBuf = (&n).into();
+			assert_eq!(m.as_str(), s)
+		}
+	}
+
+	#[test]
+	fn exact_digits() {
+		for s in [
+			"1.10",
+			"-0",
+			"1e-999",
+			"3.14159265358979323846264338327950288",
+		] {
+			let n = Number::new(s).unwrap();
+			let m = n.try_into_serde_json_exact().unwrap();
+			assert_eq!(m.to_string(), s);
+			assert_eq!(serde_json::Number::try_from(n).unwrap().to_string(), s)
+		}
+	}
 }
@@ -0,0 +1,192 @@
+use crate::{Buffer, Number, NumberBuf};
+use core::fmt;
+use simd_json::{prelude::ValueAsScalar, BorrowedValue, Node, OwnedValue, StaticNode};
+
+/// `simd-json` conversion error.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConversionError {
+	/// The value is not a number.
+	NotANumber,
+
+	/// The number is out of the `f64` range, and hence cannot be
+	/// represented by `simd-json`.
+	OutOfRange,
+}
+
+impl fmt::Display for ConversionError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NotANumber => f.write_str("value is not a number"),
+			Self::OutOfRange => f.write_str("number is out of the `f64` range"),
+		}
+	}
+}
+
+impl core::error::Error for ConversionError {}
+
+/// Converts the given scalar value into a number, if it is one.
+fn from_scalar<B: Buffer>(value: &impl ValueAsScalar) -> Result<NumberBuf<B>, ConversionError> {
+	if let Some(u) = value.as_u64() {
+		Ok(u.into())
+	} else if let Some(i) = value.as_i64() {
+		Ok(i.into())
+	} else if let Some(u) = value.as_u128() {
+		Ok(u.into())
+	} else if let Some(i) = value.as_i128() {
+		Ok(i.into())
+	} else if let Some(f) = value.as_f64() {
+		NumberBuf::try_from(f).map_err(|_| ConversionError::OutOfRange)
+	} else {
+		Err(ConversionError::NotANumber)
+	}
+}
+
+impl<B: Buffer> TryFrom<&StaticNode> for NumberBuf<B> {
+	type Error = ConversionError;
+
+	#[inline(always)]
+	fn try_from(node: &StaticNode) -> Result<Self, ConversionError> {
+		from_scalar(node)
+	}
+}
+
+impl<B: Buffer> TryFrom<StaticNode> for NumberBuf<B> {
+	type Error = ConversionError;
+
+	#[inline(always)]
+	fn try_from(node: StaticNode) -> Result<Self, ConversionError> {
+		from_scalar(&node)
+	}
+}
+
+impl<B: Buffer> TryFrom<&OwnedValue> for NumberBuf<B> {
+	type Error = ConversionError;
+
+	#[inline(always)]
+	fn try_from(value: &OwnedValue) -> Result<Self, ConversionError> {
+		from_scalar(value)
+	}
+}
+
+impl<B: Buffer> TryFrom<&BorrowedValue<'_>> for NumberBuf<B> {
+	type Error = ConversionError;
+
+	#[inline(always)]
+	fn try_from(value: &BorrowedValue<'_>) -> Result<Self, ConversionError> {
+		from_scalar(value)
+	}
+}
+
+impl<B: Buffer> TryFrom<&Node<'_>> for NumberBuf<B> {
+	type Error = ConversionError;
+
+	/// Converts a node of a `simd-json` tape, without building an
+	/// intermediate value.
+	#[inline(always)]
+	fn try_from(node: &Node<'_>) -> Result<Self, ConversionError> {
+		match node {
+			Node::Static(node) => from_scalar(node),
+			_ => Err(ConversionError::NotANumber),
+		}
+	}
+}
+
+impl TryFrom<&Number> for StaticNode {
+	type Error = ConversionError;
+
+	/// Converts the number, approximating it with the nearest `f64` if it
+	/// is not a 64-bit integer.
+	fn try_from(n: &Number) -> Result<Self, ConversionError> {
+		if let Some(u) = n.as_u64() {
+			Ok(u.into())
+		} else if let Some(i) = n.as_i64() {
+			Ok(i.into())
+		} else {
+			let f = n.as_f64_lossy();
+			if f.is_finite() {
+				Ok(f.into())
+			} else {
+				Err(ConversionError::OutOfRange)
+			}
+		}
+	}
+}
+
+impl<B: Buffer> TryFrom<&NumberBuf<B>> for StaticNode {
+	type Error = ConversionError;
+
+	#[inline(always)]
+	fn try_from(n: &NumberBuf<B>) -> Result<Self, ConversionError> {
+		Self::try_from(n.as_number())
+	}
+}
+
+impl TryFrom<&Number> for OwnedValue {
+	type Error = ConversionError;
+
+	#[inline(always)]
+	fn try_from(n: &Number) -> Result<Self, ConversionError> {
+		StaticNode::try_from(n).map(Self::Static)
+	}
+}
+
+impl TryFrom<&Number> for BorrowedValue<'_> {
+	type Error = ConversionError;
+
+	#[inline(always)]
+	fn try_from(n: &Number) -> Result<Self, ConversionError> {
+		StaticNode::try_from(n).map(Self::Static)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::ConversionError;
+	use crate::{Number, NumberBuf};
+	use simd_json::{BorrowedValue, OwnedValue, StaticNode};
+
+	#[test]
+	fn from_simd_json() {
+		let n: NumberBuf = StaticNode::I64(-12).try_into().unwrap();
+		assert_eq!(n.as_str(), "-12");
+		let n: NumberBuf = StaticNode::F64(1.5).try_into().unwrap();
+		assert_eq!(n.as_str(), "1.5");
+		assert_eq!(
+			NumberBuf::<Vec<u8>>::try_from(StaticNode::Null),
+			Err(ConversionError::NotANumber)
+		);
+
+		let mut json = br#"[18446744073709551615, 0.25, "1"]"#.to_vec();
+		let value: OwnedValue = simd_json::to_owned_value(&mut json).unwrap();
+		let OwnedValue::Array(items) = &value else {
+			panic!("not an array")
+		};
+		let n: NumberBuf = (&items[0]).try_into().unwrap();
+		assert_eq!(n.as_str(), "18446744073709551615");
+		assert!(NumberBuf::<Vec<u8>>::try_from(&items[2]).is_err());
+
+		let mut json = br#"[-1, 2.5e3, null]"#.to_vec();
+		let tape = simd_json::to_tape(&mut json).unwrap();
+		let numbers: Vec<NumberBuf> = tape
+			.0
+			.iter()
+			.filter_map(|node| node.try_into().ok())
+			.collect();
+		assert_eq!(numbers.len(), 2);
+		assert_eq!(numbers[0].as_str(), "-1");
+		assert_eq!(numbers[1].as_str(), "2500")
+	}
+
+	#[test]
+	fn to_simd_json() {
+		let n = Number::new("12").unwrap();
+		assert_eq!(StaticNode::try_from(n), Ok(StaticNode::U64(12)));
+		let n = Number::new("-0.5").unwrap();
+		assert_eq!(
+			OwnedValue::try_from(n),
+			Ok(OwnedValue::Static(StaticNode::F64(-0.5)))
+		);
+		let n = Number::new("1e999").unwrap();
+		assert_eq!(BorrowedValue::try_from(n), Err(ConversionError::OutOfRange))
+	}
+}
@@ -0,0 +1,108 @@
+//! Small integers interning.
+use crate::{Buffer, Number, NumberBuf};
+
+/// Smallest interned integer.
+const MIN: i64 = -128;
+
+/// Largest interned integer.
+const MAX: i64 = 255;
+
+/// Number of interned integers.
+const LEN: usize = (MAX - MIN + 1) as usize;
+
+/// Lexical representations of the integers from `MIN` to `MAX`, with their
+/// length.
+static SMALL_INTEGERS: [([u8; 4], usize); LEN] = {
+	let mut table = [([0u8; 4], 0usize); LEN];
+	let mut i = 0;
+	while i < LEN {
+		let n = i as i64 + MIN;
+		let mut abs = n.unsigned_abs();
+
+		// Digits, in reverse order.
+		let mut digits = [0u8; 3];
+		let mut len = 0;
+		loop {
+			digits[len] = b'0' + (abs % 10) as u8;
+			abs /= 10;
+			len += 1;
+			if abs == 0 {
+				break;
+			}
+		}
+
+		let (bytes, offset) = &mut table[i];
+		let mut j = 0;
+		if n < 0 {
+			bytes[0] = b'-';
+			j = 1
+		}
+
+		let mut k = 0;
+		while k < len {
+			bytes[j + k] = digits[len - 1 - k];
+			k += 1
+		}
+
+		*offset = j + len;
+		i += 1
+	}
+
+	table
+};
+
+/// Returns the interned number equal to `i`, if any.
+#[inline(always)]
+pub(crate) fn small_integer(i: i64) -> Option<&'static Number> {
+	if (MIN..=MAX).contains(&i) {
+		let (bytes, len) = &SMALL_INTEGERS[(i - MIN) as usize];
+		Some(unsafe { Number::new_unchecked(&bytes[..*len]) })
+	} else {
+		None
+	}
+}
+
+impl Number {
+	/// Returns the given small integer as a static number, without any
+	/// allocation.
+	#[inline(always)]
+	pub fn from_small(n: u8) -> &'static Number {
+		small_integer(n as i64).unwrap()
+	}
+}
+
+impl<B: Buffer> NumberBuf<B> {
+	/// Creates a number buffer from the given small integer, copying its
+	/// static lexical representation instead of formatting it.
+	///
+	/// Integer conversions (`From<u8>`, `From<i32>`, etc.) use the same
+	/// table for values between `-128` and `255`.
+	#[inline(always)]
+	pub fn from_small(n: u8) -> Self {
+		unsafe { Self::new_unchecked(B::from_bytes(Number::from_small(n).as_bytes())) }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Number, NumberBuf};
+
+	#[test]
+	fn small_integers() {
+		for i in -200i64..300 {
+			match super::small_integer(i) {
+				Some(n) => assert_eq!(n.as_str(), i.to_string()),
+				None => assert!(!(-128..=255).contains(&i)),
+			}
+		}
+
+		assert_eq!(Number::from_small(0).as_str(), "0");
+		assert_eq!(NumberBuf::<Vec<u8>>::from_small(255).as_str(), "255");
+		assert_eq!(NumberBuf::<Vec<u8>>::from(-128i8).as_str(), "-128");
+		assert_eq!(NumberBuf::<Vec<u8>>::from(256u16).as_str(), "256");
+		assert_eq!(
+			NumberBuf::<Vec<u8>>::from(u64::MAX).as_str(),
+			"18446744073709551615"
+		);
+	}
+}
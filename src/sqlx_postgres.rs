@@ -0,0 +1,122 @@
+//! Postgres `NUMERIC` support for `sqlx`.
+use crate::{pg_numeric::PgNumeric, Buffer, InvalidNumber, Number, NumberBuf};
+use alloc::{borrow::ToOwned, boxed::Box};
+use sqlx::{
+	encode::IsNull,
+	error::BoxDynError,
+	postgres::{
+		types::Oid, PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef,
+	},
+	Decode, Encode, Postgres, Type,
+};
+
+/// `NUMERIC` type.
+const NUMERIC: PgTypeInfo = PgTypeInfo::with_oid(Oid(1700));
+
+/// `NUMERIC[]` type.
+const NUMERIC_ARRAY: PgTypeInfo = PgTypeInfo::with_oid(Oid(1231));
+
+impl<B> Type<Postgres> for NumberBuf<B> {
+	#[inline(always)]
+	fn type_info() -> PgTypeInfo {
+		NUMERIC
+	}
+}
+
+impl<B> PgHasArrayType for NumberBuf<B> {
+	#[inline(always)]
+	fn array_type_info() -> PgTypeInfo {
+		NUMERIC_ARRAY
+	}
+}
+
+impl Type<Postgres> for Number {
+	#[inline(always)]
+	fn type_info() -> PgTypeInfo {
+		NUMERIC
+	}
+}
+
+impl Encode<'_, Postgres> for Number {
+	/// Encodes the number in the binary `NUMERIC` format, preserving its
+	/// digits and scale.
+	fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+		PgNumeric::new(self)?.encode(buf)?;
+		Ok(IsNull::No)
+	}
+}
+
+impl<B: Buffer> Encode<'_, Postgres> for NumberBuf<B> {
+	#[inline(always)]
+	fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+		self.as_number().encode_by_ref(buf)
+	}
+}
+
+impl<B: Buffer> Decode<'_, Postgres> for NumberBuf<B> {
+	/// Decodes a `NUMERIC` value, in plain decimal notation with as many
+	/// digits after the decimal point as its scale.
+	///
+	/// Fails on `NaN` and infinite values.
+	fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+		match value.format() {
+			PgValueFormat::Binary => Ok(PgNumeric::decode(value.as_bytes()?)?.to_number_buf()?),
+			PgValueFormat::Text => {
+				let s = value.as_str()?;
+				match Number::new(s) {
+					Ok(n) => Ok(unsafe { NumberBuf::new_unchecked(B::from_bytes(n.as_bytes())) }),
+					Err(_) => Err(Box::new(InvalidNumber(s.to_owned()))),
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{pg_numeric::PgNumeric, Number, NumberBuf};
+	use sqlx::{
+		encode::IsNull,
+		postgres::{PgArgumentBuffer, PgHasArrayType},
+		Encode, Postgres, Type,
+	};
+
+	fn round_trip(s: &str) -> NumberBuf {
+		let n: NumberBuf = s.parse().unwrap();
+		let mut buf = PgArgumentBuffer::default();
+		let is_null = Encode::<Postgres>::encode_by_ref(&n, &mut buf).unwrap();
+		assert!(matches!(is_null, IsNull::No));
+		PgNumeric::decode(&buf).unwrap().to_number_buf().unwrap()
+	}
+
+	#[test]
+	fn round_trips() {
+		for (input, expected) in [
+			("0", "0"),
+			("-12.50", "-12.50"),
+			("123456789.000001", "123456789.000001"),
+			("1.5e3", "1500"),
+			("1.5e-3", "0.0015"),
+		] {
+			assert_eq!(round_trip(input).as_str(), expected)
+		}
+	}
+
+	#[test]
+	fn type_info() {
+		assert_eq!(<Number as Type<Postgres>>::type_info(), super::NUMERIC);
+		assert_eq!(<NumberBuf as Type<Postgres>>::type_info(), super::NUMERIC);
+		assert_eq!(
+			NumberBuf::<Vec<u8>>::array_type_info(),
+			super::NUMERIC_ARRAY
+		)
+	}
+
+	#[test]
+	fn out_of_range() {
+		let mut buf = PgArgumentBuffer::default();
+		assert!(
+			Encode::<Postgres>::encode_by_ref(Number::new("1e200000").unwrap(), &mut buf).is_err()
+		)
+	}
+}
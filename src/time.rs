@@ -0,0 +1,142 @@
+//! Duration and timestamp conversions.
+use crate::{decimal::Decimal, Buffer, Number, NumberBuf};
+use alloc::{format, string::ToString};
+use core::time::Duration;
+
+/// Number of nanoseconds in a second.
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// Returns the absolute value of the given decimal in nanoseconds, if it is
+/// an integer number of nanoseconds fitting in a `Duration`.
+fn to_nanos(d: &Decimal) -> Option<Duration> {
+	if d.is_zero() {
+		return Some(Duration::ZERO);
+	}
+
+	// `u64::MAX` seconds have 20 integer digits.
+	if d.exponent < -9 || d.point() > 20 {
+		return None;
+	}
+
+	let mut nanos: u128 = 0;
+	for digit in &d.digits {
+		nanos = nanos * 10 + *digit as u128
+	}
+
+	nanos *= 10u128.pow((d.exponent + 9) as u32);
+	let secs = u64::try_from(nanos / NANOS_PER_SEC).ok()?;
+	Some(Duration::new(secs, (nanos % NANOS_PER_SEC) as u32))
+}
+
+impl Number {
+	/// Converts this number of seconds into a `Duration`.
+	///
+	/// The conversion is exact: it fails if the number is negative, too
+	/// large, or more precise than a nanosecond (`1.0000000001`).
+	pub fn to_duration(&self) -> Option<Duration> {
+		let d = Decimal::new(self);
+		if d.negative {
+			None
+		} else {
+			to_nanos(&d)
+		}
+	}
+
+	/// Converts this number of seconds since the Unix epoch into a
+	/// `SystemTime`.
+	///
+	/// Negative numbers are times before the epoch. The conversion is exact:
+	/// it fails if the number is more precise than a nanosecond, or out of
+	/// the `SystemTime` range.
+	#[cfg(feature = "std")]
+	pub fn to_system_time(&self) -> Option<std::time::SystemTime> {
+		let d = Decimal::new(self);
+		let duration = to_nanos(&d)?;
+		if d.negative {
+			std::time::UNIX_EPOCH.checked_sub(duration)
+		} else {
+			std::time::UNIX_EPOCH.checked_add(duration)
+		}
+	}
+}
+
+impl<B: Buffer> NumberBuf<B> {
+	/// Creates a number of seconds from the given duration, without losing
+	/// precision.
+	///
+	/// The output has no trailing zeros in its fractional part: one and a
+	/// half second gives `1.5`.
+	pub fn from_duration(duration: Duration) -> Self {
+		let nanos = duration.subsec_nanos();
+		let s = if nanos == 0 {
+			duration.as_secs().to_string()
+		} else {
+			let s = format!("{}.{nanos:09}", duration.as_secs());
+			s.trim_end_matches('0').to_string()
+		};
+
+		unsafe { Self::new_unchecked(B::from_vec(s.into_bytes())) }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Number, NumberBuf};
+	use core::time::Duration;
+
+	fn number(s: &str) -> &Number {
+		Number::new(s).unwrap()
+	}
+
+	#[test]
+	fn to_duration() {
+		assert_eq!(
+			number("1699999999.123456789").to_duration(),
+			Some(Duration::new(1699999999, 123456789))
+		);
+		assert_eq!(number("0").to_duration(), Some(Duration::ZERO));
+		assert_eq!(number("-0.0").to_duration(), Some(Duration::ZERO));
+		assert_eq!(number("1.5e1").to_duration(), Some(Duration::new(15, 0)));
+		assert_eq!(number("1e-9").to_duration(), Some(Duration::new(0, 1)));
+		assert_eq!(
+			number("1.0000000000").to_duration(),
+			Some(Duration::new(1, 0))
+		);
+		assert_eq!(
+			number("18446744073709551615.999999999").to_duration(),
+			Some(Duration::MAX)
+		);
+		assert_eq!(number("18446744073709551616").to_duration(), None);
+		assert_eq!(number("1e100").to_duration(), None);
+		assert_eq!(number("1e-10").to_duration(), None);
+		assert_eq!(number("-1").to_duration(), None)
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn to_system_time() {
+		use std::time::UNIX_EPOCH;
+		assert_eq!(
+			number("1699999999.123456789").to_system_time(),
+			Some(UNIX_EPOCH + Duration::new(1699999999, 123456789))
+		);
+		assert_eq!(
+			number("-1.5").to_system_time(),
+			Some(UNIX_EPOCH - Duration::new(1, 500000000))
+		);
+		assert_eq!(number("0.1e-9").to_system_time(), None)
+	}
+
+	#[test]
+	fn from_duration() {
+		let n = |d| NumberBuf::<Vec<u8>>::from_duration(d);
+		assert_eq!(
+			n(Duration::new(1699999999, 123456789)).as_str(),
+			"1699999999.123456789"
+		);
+		assert_eq!(n(Duration::new(1, 500000000)).as_str(), "1.5");
+		assert_eq!(n(Duration::new(0, 1)).as_str(), "0.000000001");
+		assert_eq!(n(Duration::ZERO).as_str(), "0");
+		assert_eq!(n(Duration::MAX).to_duration(), Some(Duration::MAX))
+	}
+}
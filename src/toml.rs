@@ -0,0 +1,146 @@
+use crate::{Buffer, Number, NumberBuf};
+use core::fmt;
+
+/// TOML conversion error.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConversionError {
+	/// The TOML value is not a number.
+	NotANumber,
+
+	/// The TOML float is not finite (`inf` or `nan`), and hence has no JSON
+	/// representation.
+	NonFinite,
+
+	/// The integer does not fit in a TOML (64-bit signed) integer.
+	Overflow,
+
+	/// The number cannot be represented exactly by a TOML float.
+	Inexact,
+}
+
+impl fmt::Display for ConversionError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NotANumber => f.write_str("TOML value is not a number"),
+			Self::NonFinite => f.write_str("non-finite TOML float"),
+			Self::Overflow => f.write_str("integer does not fit in a TOML integer"),
+			Self::Inexact => f.write_str("number is not exactly representable as a TOML float"),
+		}
+	}
+}
+
+impl core::error::Error for ConversionError {}
+
+impl<B: Buffer> TryFrom<&toml::Value> for NumberBuf<B> {
+	type Error = ConversionError;
+
+	/// Converts a TOML integer or float.
+	fn try_from(value: &toml::Value) -> Result<Self, ConversionError> {
+		match value {
+			toml::Value::Integer(i) => Ok((*i).into()),
+			toml::Value::Float(f) => {
+				NumberBuf::try_from(*f).map_err(|_| ConversionError::NonFinite)
+			}
+			_ => Err(ConversionError::NotANumber),
+		}
+	}
+}
+
+impl<B: Buffer> TryFrom<toml::Value> for NumberBuf<B> {
+	type Error = ConversionError;
+
+	#[inline(always)]
+	fn try_from(value: toml::Value) -> Result<Self, ConversionError> {
+		Self::try_from(&value)
+	}
+}
+
+impl Number {
+	/// Converts the number into a TOML integer or float, without rounding.
+	///
+	/// Numbers without fraction or exponent are converted into integers,
+	/// failing with [`ConversionError::Overflow`] if they do not fit.
+	/// Other numbers are converted into floats, failing with
+	/// [`ConversionError::Inexact`] if the nearest float does not have
+	/// the same value.
+	pub fn to_toml(&self) -> Result<toml::Value, ConversionError> {
+		if !self.has_decimal_point() && !self.has_exponent() {
+			return self
+				.as_i64()
+				.map(toml::Value::Integer)
+				.ok_or(ConversionError::Overflow);
+		}
+
+		let f = self.as_f64_lossy();
+		match NumberBuf::<alloc::vec::Vec<u8>>::try_from(f) {
+			Ok(nearest) if nearest.value_eq(self) => Ok(toml::Value::Float(f)),
+			_ => Err(ConversionError::Inexact),
+		}
+	}
+}
+
+impl TryFrom<&Number> for toml::Value {
+	type Error = ConversionError;
+
+	/// Converts the number into a TOML integer or float, without rounding.
+	///
+	/// See [`Number::to_toml`], which should be preferred since the
+	/// inherent `toml::Value::try_from` function shadows this method.
+	#[inline(always)]
+	fn try_from(n: &Number) -> Result<Self, ConversionError> {
+		n.to_toml()
+	}
+}
+
+impl<B: Buffer> TryFrom<&NumberBuf<B>> for toml::Value {
+	type Error = ConversionError;
+
+	#[inline(always)]
+	fn try_from(n: &NumberBuf<B>) -> Result<Self, ConversionError> {
+		n.to_toml()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::ConversionError;
+	use crate::{Number, NumberBuf};
+
+	#[test]
+	fn from_toml() {
+		let table: toml::Table = "a = -12\nb = 1.5e3\nc = inf\nd = 'x'".parse().unwrap();
+		let n: NumberBuf = (&table["a"]).try_into().unwrap();
+		assert_eq!(n.as_str(), "-12");
+		let n: NumberBuf = (&table["b"]).try_into().unwrap();
+		assert_eq!(n.as_str(), "1500");
+		assert_eq!(
+			NumberBuf::<Vec<u8>>::try_from(&table["c"]),
+			Err(ConversionError::NonFinite)
+		);
+		assert_eq!(
+			NumberBuf::<Vec<u8>>::try_from(&table["d"]),
+			Err(ConversionError::NotANumber)
+		)
+	}
+
+	#[test]
+	fn to_toml() {
+		let to_toml = |s| Number::new(s).unwrap().to_toml();
+		assert_eq!(to_toml("-12"), Ok(toml::Value::Integer(-12)));
+		assert_eq!(to_toml("1.50"), Ok(toml::Value::Float(1.5)));
+		assert_eq!(to_toml("1e2"), Ok(toml::Value::Float(100.0)));
+		assert_eq!(
+			to_toml("9223372036854775808"),
+			Err(ConversionError::Overflow)
+		);
+		assert_eq!(
+			to_toml("0.10000000000000000001"),
+			Err(ConversionError::Inexact)
+		);
+		assert_eq!(to_toml("1e999"), Err(ConversionError::Inexact));
+
+		let n: NumberBuf = "0.5".parse().unwrap();
+		let value: Result<toml::Value, _> = (&n).try_into();
+		assert_eq!(value, Ok(toml::Value::Float(0.5)))
+	}
+}
@@ -0,0 +1,62 @@
+//! Non-negative numbers.
+use crate::{Buffer, Number, NumberBuf};
+
+number_newtype! {
+	/// Non-negative JSON number.
+	///
+	/// Statically guarantees that the value of the number is greater or
+	/// equal to zero. Negative zero (`-0`) is accepted.
+	UNumber,
+
+	/// Non-negative JSON number buffer.
+	UNumberBuf,
+	as_unsigned_number,
+	|n: &Number| !n.is_negative(),
+	"a non-negative number"
+}
+
+macro_rules! impl_from_uint {
+	($($ty:ty),*) => {
+		$(
+			impl<B: Buffer> From<$ty> for UNumberBuf<B> {
+				#[inline(always)]
+				fn from(i: $ty) -> Self {
+					unsafe { Self::new_unchecked(NumberBuf::from(i)) }
+				}
+			}
+		)*
+	};
+}
+
+impl_from_uint!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+	use super::{UNumber, UNumberBuf};
+	use crate::{Number, NumberBuf};
+
+	#[test]
+	fn new() {
+		assert!(UNumber::new(Number::new("0").unwrap()).is_some());
+		assert!(UNumber::new(Number::new("-0.0").unwrap()).is_some());
+		assert!(UNumber::new(Number::new("1e-400").unwrap()).is_some());
+		assert!(UNumber::new(Number::new("-1e-400").unwrap()).is_none());
+
+		let n: NumberBuf = "-3".parse().unwrap();
+		assert_eq!(UNumberBuf::new(n.clone()), Err(n));
+		assert_eq!(
+			UNumberBuf::<Vec<u8>>::from(42u64)
+				.as_unsigned_number()
+				.as_u64(),
+			Some(42)
+		)
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde() {
+		let n: UNumberBuf = serde_json::from_str("12.5").unwrap();
+		assert_eq!(n.as_str(), "12.5");
+		assert!(serde_json::from_str::<UNumberBuf>("-1").is_err())
+	}
+}
@@ -0,0 +1,77 @@
+use crate::{CowNumber, Number, NumberBuf};
+use alloc::borrow::Cow;
+use utoipa::{
+	openapi::{
+		schema::{ObjectBuilder, Schema, SchemaFormat, Type},
+		RefOr,
+	},
+	PartialSchema, ToSchema,
+};
+
+/// Schema of arbitrary precision numbers.
+///
+/// The `decimal` format is the one registered by OpenAPI for decimal numbers
+/// of unspecified precision and range.
+fn number_schema() -> RefOr<Schema> {
+	ObjectBuilder::new()
+		.schema_type(Type::Number)
+		.format(Some(SchemaFormat::Custom("decimal".into())))
+		.into()
+}
+
+impl PartialSchema for Number {
+	#[inline(always)]
+	fn schema() -> RefOr<Schema> {
+		number_schema()
+	}
+}
+
+impl ToSchema for Number {
+	#[inline(always)]
+	fn name() -> Cow<'static, str> {
+		"Number".into()
+	}
+}
+
+impl<B> PartialSchema for NumberBuf<B> {
+	#[inline(always)]
+	fn schema() -> RefOr<Schema> {
+		number_schema()
+	}
+}
+
+impl<B> ToSchema for NumberBuf<B> {
+	#[inline(always)]
+	fn name() -> Cow<'static, str> {
+		Number::name()
+	}
+}
+
+impl<B> PartialSchema for CowNumber<'_, B> {
+	#[inline(always)]
+	fn schema() -> RefOr<Schema> {
+		number_schema()
+	}
+}
+
+impl<B> ToSchema for CowNumber<'_, B> {
+	#[inline(always)]
+	fn name() -> Cow<'static, str> {
+		Number::name()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::NumberBuf;
+	use utoipa::{PartialSchema, ToSchema};
+
+	#[test]
+	fn number_schema() {
+		assert_eq!(NumberBuf::<Vec<u8>>::name(), "Number");
+		assert_eq!(
+			serde_json::to_value(NumberBuf::<Vec<u8>>::schema()).unwrap(),
+			serde_json::json!({ "type": "number", "format": "decimal" })
+		)
+	}
+}
@@ -0,0 +1,206 @@
+//! Conversions between numbers and JavaScript `Number`, `BigInt` and
+//! `JsValue`.
+use crate::{Buffer, Number, NumberBuf};
+use alloc::{string::ToString, vec::Vec};
+use core::fmt;
+use js_sys::BigInt;
+use wasm_bindgen::JsValue;
+
+/// Largest integer `n` such that `n` and `n + 1` are exactly representable
+/// as JavaScript numbers (`Number.MAX_SAFE_INTEGER`).
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+/// JavaScript conversion error.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConversionError {
+	/// The JavaScript value is not a number nor a `BigInt`.
+	NotANumber,
+
+	/// The JavaScript number is not finite, and hence has no JSON
+	/// representation.
+	NonFinite,
+
+	/// The number is not an integer, and cannot be converted into a
+	/// `BigInt`.
+	NotAnInteger,
+
+	/// The number cannot be represented exactly by a JavaScript number.
+	Inexact,
+}
+
+impl fmt::Display for ConversionError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NotANumber => f.write_str("JavaScript value is not a number"),
+			Self::NonFinite => f.write_str("non-finite JavaScript number"),
+			Self::NotAnInteger => f.write_str("number is not an integer"),
+			Self::Inexact => f.write_str("number is not exactly representable in JavaScript"),
+		}
+	}
+}
+
+impl core::error::Error for ConversionError {}
+
+/// JavaScript representation of a number.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Repr {
+	/// Exact JavaScript number.
+	Number(f64),
+
+	/// Integer too large to be safely represented by a JavaScript number.
+	BigInt,
+}
+
+impl Repr {
+	fn new(n: &Number) -> Result<Self, ConversionError> {
+		if !n.has_decimal_point() && !n.has_exponent() {
+			match n.as_i64() {
+				Some(i) if i.abs() <= MAX_SAFE_INTEGER => Ok(Self::Number(n.as_f64_lossy())),
+				_ => Ok(Self::BigInt),
+			}
+		} else {
+			exact_f64(n).map(Self::Number)
+		}
+	}
+}
+
+/// Returns the JavaScript number with the same value as `n`, if any.
+fn exact_f64(n: &Number) -> Result<f64, ConversionError> {
+	let f = n.as_f64_lossy();
+	match NumberBuf::<Vec<u8>>::try_from(f) {
+		Ok(nearest) if nearest.value_eq(n) => Ok(f),
+		_ => Err(ConversionError::Inexact),
+	}
+}
+
+impl Number {
+	/// Converts this number into a JavaScript value, without loss of
+	/// precision.
+	///
+	/// Integers outside of the safe integer range of JavaScript numbers
+	/// (`±(2^53 - 1)`) are converted into a `BigInt`. Other numbers are
+	/// converted into a JavaScript number, failing with
+	/// [`ConversionError::Inexact`] if it would be approximated.
+	pub fn to_js_value(&self) -> Result<JsValue, ConversionError> {
+		match Repr::new(self)? {
+			Repr::Number(f) => Ok(JsValue::from_f64(f)),
+			Repr::BigInt => Ok(self.to_big_int()?.into()),
+		}
+	}
+
+	/// Converts this number into a JavaScript `BigInt`.
+	///
+	/// Fails with [`ConversionError::NotAnInteger`] if the number has a
+	/// fraction or an exponent.
+	pub fn to_big_int(&self) -> Result<BigInt, ConversionError> {
+		if self.has_decimal_point() || self.has_exponent() {
+			return Err(ConversionError::NotAnInteger);
+		}
+
+		BigInt::new(&JsValue::from_str(self.as_str())).map_err(|_| ConversionError::NotAnInteger)
+	}
+}
+
+impl TryFrom<&Number> for JsValue {
+	type Error = ConversionError;
+
+	#[inline(always)]
+	fn try_from(n: &Number) -> Result<Self, ConversionError> {
+		n.to_js_value()
+	}
+}
+
+impl<B: Buffer> TryFrom<&NumberBuf<B>> for JsValue {
+	type Error = ConversionError;
+
+	#[inline(always)]
+	fn try_from(n: &NumberBuf<B>) -> Result<Self, ConversionError> {
+		n.to_js_value()
+	}
+}
+
+impl TryFrom<&Number> for js_sys::Number {
+	type Error = ConversionError;
+
+	/// Converts the number, failing if it cannot be represented exactly.
+	fn try_from(n: &Number) -> Result<Self, ConversionError> {
+		exact_f64(n).map(Self::from)
+	}
+}
+
+impl TryFrom<&Number> for BigInt {
+	type Error = ConversionError;
+
+	#[inline(always)]
+	fn try_from(n: &Number) -> Result<Self, ConversionError> {
+		n.to_big_int()
+	}
+}
+
+impl<B: Buffer> TryFrom<&js_sys::Number> for NumberBuf<B> {
+	type Error = ConversionError;
+
+	/// Converts the JavaScript number, failing if it is not finite.
+	fn try_from(n: &js_sys::Number) -> Result<Self, ConversionError> {
+		NumberBuf::try_from(n.value_of()).map_err(|_| ConversionError::NonFinite)
+	}
+}
+
+impl<B: Buffer> From<&BigInt> for NumberBuf<B> {
+	fn from(n: &BigInt) -> Self {
+		// The decimal representation of a `BigInt` is a valid JSON number.
+		let digits = ToString::to_string(n);
+		unsafe { NumberBuf::new_unchecked(B::from_vec(digits.into_bytes())) }
+	}
+}
+
+impl<B: Buffer> TryFrom<&JsValue> for NumberBuf<B> {
+	type Error = ConversionError;
+
+	/// Converts the JavaScript number or `BigInt`.
+	fn try_from(value: &JsValue) -> Result<Self, ConversionError> {
+		if let Some(f) = value.as_f64() {
+			NumberBuf::try_from(f).map_err(|_| ConversionError::NonFinite)
+		} else if value.is_bigint() {
+			Ok(Self::from(&BigInt::from(value.clone())))
+		} else {
+			Err(ConversionError::NotANumber)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{ConversionError, Repr};
+	use crate::Number;
+
+	fn repr(s: &str) -> Result<Repr, ConversionError> {
+		Repr::new(Number::new(s).unwrap())
+	}
+
+	#[test]
+	fn representation() {
+		assert_eq!(repr("12"), Ok(Repr::Number(12.0)));
+		assert_eq!(
+			repr("9007199254740991"),
+			Ok(Repr::Number(9007199254740991.0))
+		);
+		assert_eq!(
+			repr("-9007199254740991"),
+			Ok(Repr::Number(-9007199254740991.0))
+		);
+		assert_eq!(repr("9007199254740992"), Ok(Repr::BigInt));
+		assert_eq!(repr("-123456789012345678901234567890"), Ok(Repr::BigInt));
+		assert_eq!(repr("0.5"), Ok(Repr::Number(0.5)));
+		assert_eq!(repr("1e2"), Ok(Repr::Number(100.0)));
+		assert_eq!(
+			repr("0.1000000000000000000001"),
+			Err(ConversionError::Inexact)
+		);
+
+		let Ok(Repr::Number(zero)) = repr("-0") else {
+			panic!("not a number")
+		};
+		assert!(zero.is_sign_negative())
+	}
+}
@@ -0,0 +1,178 @@
+//! XML Schema datatypes.
+use crate::{decimal::Decimal, float, Number, NumberBuf};
+use alloc::string::String;
+use core::fmt;
+
+/// XML Schema numeric datatype.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum XsdNumericType {
+	/// `xsd:byte`, integers between `-128` and `127`.
+	Byte,
+
+	/// `xsd:short`, integers between `-32768` and `32767`.
+	Short,
+
+	/// `xsd:int`, 32-bit signed integers.
+	Int,
+
+	/// `xsd:long`, 64-bit signed integers.
+	Long,
+
+	/// `xsd:integer`, arbitrary integers.
+	Integer,
+
+	/// `xsd:decimal`, arbitrary decimal numbers.
+	Decimal,
+
+	/// `xsd:double`, IEEE 754 double precision floating point numbers.
+	Double,
+}
+
+impl XsdNumericType {
+	/// Returns the local name of the datatype in the XML Schema namespace.
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::Byte => "byte",
+			Self::Short => "short",
+			Self::Int => "int",
+			Self::Long => "long",
+			Self::Integer => "integer",
+			Self::Decimal => "decimal",
+			Self::Double => "double",
+		}
+	}
+
+	/// Returns the IRI of the datatype.
+	pub fn iri(&self) -> &'static str {
+		match self {
+			Self::Byte => "http://www.w3.org/2001/XMLSchema#byte",
+			Self::Short => "http://www.w3.org/2001/XMLSchema#short",
+			Self::Int => "http://www.w3.org/2001/XMLSchema#int",
+			Self::Long => "http://www.w3.org/2001/XMLSchema#long",
+			Self::Integer => "http://www.w3.org/2001/XMLSchema#integer",
+			Self::Decimal => "http://www.w3.org/2001/XMLSchema#decimal",
+			Self::Double => "http://www.w3.org/2001/XMLSchema#double",
+		}
+	}
+}
+
+impl fmt::Display for XsdNumericType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "xsd:{}", self.name())
+	}
+}
+
+impl Number {
+	/// Returns the most specific XML Schema numeric datatype matching the
+	/// lexical representation of this number.
+	///
+	/// Numbers with an exponent part are `xsd:double`, numbers with a
+	/// decimal point are `xsd:decimal`, and integers are given the smallest
+	/// signed integer datatype containing them.
+	pub fn xsd_type(&self) -> XsdNumericType {
+		if self.has_exponent() {
+			XsdNumericType::Double
+		} else if self.has_decimal_point() {
+			XsdNumericType::Decimal
+		} else {
+			match self.as_i64() {
+				Some(i) if i8::try_from(i).is_ok() => XsdNumericType::Byte,
+				Some(i) if i16::try_from(i).is_ok() => XsdNumericType::Short,
+				Some(i) if i32::try_from(i).is_ok() => XsdNumericType::Int,
+				Some(_) => XsdNumericType::Long,
+				None => XsdNumericType::Integer,
+			}
+		}
+	}
+
+	/// Returns the canonical `xsd:decimal` lexical form of this number.
+	///
+	/// The exponent part is expanded, and leading and trailing zeros are
+	/// removed. Integers are written without decimal point. For instance
+	/// `1.50e1` becomes `15` and `-0.0250` becomes `-0.025`.
+	///
+	/// Beware that small inputs such as `1e1000000000` produce enormous
+	/// outputs.
+	pub fn to_xsd_decimal(&self) -> NumberBuf {
+		let d = Decimal::new(self);
+		let bytes = d.to_fixed(d.exponent.min(0).unsigned_abs() as usize);
+		unsafe { NumberBuf::new_unchecked(bytes) }
+	}
+
+	/// Returns the canonical `xsd:double` lexical form of the nearest `f64`
+	/// to this number.
+	///
+	/// The result is written in scientific notation with an uppercase `E`
+	/// exponent marker and at least one fractional digit, such as `1.5E3`
+	/// or `0.0E0`. Numbers out of the `f64` range produce `INF` or `-INF`.
+	pub fn to_xsd_double(&self) -> String {
+		let f = self.as_f64_lossy();
+		let bytes = if f.is_infinite() {
+			if f.is_sign_negative() {
+				b"-INF".to_vec()
+			} else {
+				b"INF".to_vec()
+			}
+		} else if f == 0.0 {
+			if f.is_sign_negative() {
+				b"-0.0E0".to_vec()
+			} else {
+				b"0.0E0".to_vec()
+			}
+		} else {
+			let shortest = float::format_f64(f);
+			let d = Decimal::new(unsafe { Number::new_unchecked(&shortest) });
+			let fraction_digits = d.digits.len().max(2) - 1;
+			d.to_scientific(b'E', Some(fraction_digits))
+		};
+
+		// `bytes` only contains ASCII characters.
+		unsafe { String::from_utf8_unchecked(bytes) }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::XsdNumericType;
+	use crate::Number;
+
+	fn n(s: &str) -> &Number {
+		Number::new(s).unwrap()
+	}
+
+	#[test]
+	fn xsd_type() {
+		assert_eq!(n("-128").xsd_type(), XsdNumericType::Byte);
+		assert_eq!(n("128").xsd_type(), XsdNumericType::Short);
+		assert_eq!(n("-32769").xsd_type(), XsdNumericType::Int);
+		assert_eq!(n("2147483648").xsd_type(), XsdNumericType::Long);
+		assert_eq!(n("9223372036854775808").xsd_type(), XsdNumericType::Integer);
+		assert_eq!(n("1.0").xsd_type(), XsdNumericType::Decimal);
+		assert_eq!(n("1e3").xsd_type(), XsdNumericType::Double);
+		assert_eq!(
+			XsdNumericType::Int.iri(),
+			"http://www.w3.org/2001/XMLSchema#int"
+		);
+		assert_eq!(XsdNumericType::Double.to_string(), "xsd:double");
+	}
+
+	#[test]
+	fn to_xsd_decimal() {
+		assert_eq!(n("1.50e1").to_xsd_decimal().as_str(), "15");
+		assert_eq!(n("-0.0250").to_xsd_decimal().as_str(), "-0.025");
+		assert_eq!(n("-0.0").to_xsd_decimal().as_str(), "0");
+		assert_eq!(n("12e-1").to_xsd_decimal().as_str(), "1.2");
+	}
+
+	#[test]
+	fn to_xsd_double() {
+		assert_eq!(n("1500").to_xsd_double(), "1.5E3");
+		assert_eq!(n("1").to_xsd_double(), "1.0E0");
+		assert_eq!(n("-0.00025").to_xsd_double(), "-2.5E-4");
+		assert_eq!(n("0.1").to_xsd_double(), "1.0E-1");
+		assert_eq!(n("0").to_xsd_double(), "0.0E0");
+		assert_eq!(n("-0.0").to_xsd_double(), "-0.0E0");
+		assert_eq!(n("1e999").to_xsd_double(), "INF");
+		assert_eq!(n("-1e999").to_xsd_double(), "-INF");
+	}
+}